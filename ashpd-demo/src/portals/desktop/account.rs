@@ -1,5 +1,6 @@
 use adw::subclass::prelude::*;
 use ashpd::{desktop::account::UserInformation, WindowIdentifier};
+use futures_util::TryFutureExt;
 use gtk::{gdk, glib, prelude::*};
 
 use crate::widgets::{PortalPage, PortalPageExt, PortalPageImpl};
@@ -60,7 +61,7 @@ impl AccountPage {
         let request = UserInformation::request()
             .identifier(identifier)
             .reason(&*reason);
-        match request.send().await.and_then(|r| r.response()) {
+        match request.send().and_then(|r| r.response()).await {
             Ok(user_info) => {
                 self.success("User information request was successful");
                 imp.id_label.set_text(user_info.id());
@@ -1,5 +1,6 @@
 use adw::subclass::prelude::*;
 use ashpd::{desktop::background::Background, WindowIdentifier};
+use futures_util::TryFutureExt;
 use gtk::{glib, prelude::*};
 
 use crate::{
@@ -79,7 +80,7 @@ impl BackgroundPage {
 
         self.info("Requesting background access");
 
-        match request.send().await.and_then(|r| r.response()) {
+        match request.send().and_then(|r| r.response()).await {
             Ok(response) => {
                 imp.response_group.set_visible(true);
                 imp.auto_start_label
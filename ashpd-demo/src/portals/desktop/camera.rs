@@ -157,7 +157,7 @@ impl CameraPage {
 
 async fn stream() -> ashpd::Result<std::os::fd::OwnedFd> {
     let proxy = camera::Camera::new().await?;
-    proxy.request_access().await?;
+    proxy.request_access().await?.response().await?;
     proxy.open_pipe_wire_remote().await
 }
 
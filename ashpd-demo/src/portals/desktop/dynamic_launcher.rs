@@ -396,7 +396,7 @@ impl DynamicLauncherPage {
         let response = proxy
             .prepare_install(&identifier, &launcher_name, icon, options)
             .await?
-            .response()?;
+            .response().await?;
 
         imp.response_group.set_visible(true);
         imp.token_label.set_text(response.token());
@@ -3,6 +3,7 @@ use ashpd::{
     desktop::file_chooser::{OpenFileRequest, SaveFileRequest, SaveFilesRequest},
     WindowIdentifier,
 };
+use futures_util::TryFutureExt;
 use gtk::glib;
 
 use crate::{
@@ -115,7 +116,7 @@ impl FileChooserPage {
             .title(&*title)
             .multiple(multiple)
             .accept_label(accept_label.as_deref());
-        match request.send().await.and_then(|r| r.response()) {
+        match request.send().and_then(|r| r.response()).await {
             Ok(files) => {
                 imp.open_response_group.set_visible(true);
 
@@ -154,7 +155,7 @@ impl FileChooserPage {
             .current_name(current_name.as_deref())
             .current_folder::<String>(current_folder)?
             .current_file::<String>(current_file)?;
-        match request.send().await.and_then(|r| r.response()) {
+        match request.send().and_then(|r| r.response()).await {
             Ok(files) => {
                 imp.save_file_response_group.set_visible(true);
 
@@ -194,7 +195,7 @@ impl FileChooserPage {
             .current_folder::<String>(current_folder)?
             .files::<Vec<_>>(files)?;
 
-        match request.send().await.and_then(|r| r.response()) {
+        match request.send().and_then(|r| r.response()).await {
             Ok(files) => {
                 imp.save_files_response_group.set_visible(true);
 
@@ -45,7 +45,7 @@ mod imp {
         #[template_child]
         pub rebind_count_label: TemplateChild<gtk::Label>,
         pub rebind_count: Arc<Mutex<u32>>,
-        pub session: Arc<Mutex<Option<Session<'static>>>>,
+        pub session: Arc<Mutex<Option<Session<'static, GlobalShortcuts<'static>>>>>,
         pub abort_handle: Arc<Mutex<Option<AbortHandle>>>,
         pub triggers: Arc<Mutex<Vec<RegisteredShortcut>>>,
         pub activations: Arc<Mutex<HashSet<String>>>,
@@ -110,11 +110,11 @@ impl GlobalShortcutsPage {
                 let global_shortcuts = GlobalShortcuts::new().await?;
                 let session = global_shortcuts.create_session().await?;
                 let request = global_shortcuts.bind_shortcuts(&session, &shortcuts[..], &identifier).await?;
-                let response = request.response();
+                let response = request.response().await;
                 if let Err(e) = &response {
                     self.error(&match e {
-                        ashpd::Error::Response(ResponseError::Cancelled) => "Cancelled".into(),
-                        ashpd::Error::Response(ResponseError::Other) => "Other response error".into(),
+                        ashpd::Error::Response(ResponseError::Cancelled, _) => "Cancelled".into(),
+                        ashpd::Error::Response(ResponseError::Other, _) => "Other response error".into(),
                         other => format!("{}", other),
                     })
                 };
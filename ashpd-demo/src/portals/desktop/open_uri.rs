@@ -77,8 +77,11 @@ impl OpenUriPage {
                     }
                 } else {
                     request.send_uri(&uri).await
-                }
-                .and_then(|r| r.response());
+                };
+                let response = match response {
+                    Ok(request) => request.response().await,
+                    Err(err) => Err(err),
+                };
                 match response {
                     Ok(_) => {
                         self.success("Open URI request was successful");
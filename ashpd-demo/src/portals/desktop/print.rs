@@ -111,7 +111,7 @@ async fn print(
             modal,
         )
         .await?
-        .response()?;
+        .response().await?;
 
     proxy
         .print(identifier, title, &file, Some(out.token), modal)
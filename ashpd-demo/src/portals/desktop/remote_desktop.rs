@@ -24,7 +24,7 @@ mod imp {
     pub struct RemoteDesktopPage {
         #[template_child]
         pub response_group: TemplateChild<adw::PreferencesGroup>,
-        pub session: Arc<Mutex<Option<Session<'static>>>>,
+        pub session: Arc<Mutex<Option<Session<'static, RemoteDesktop<'static>>>>>,
         #[template_child]
         pub screencast_switch: TemplateChild<adw::SwitchRow>,
         #[template_child]
@@ -195,7 +195,7 @@ impl RemoteDesktopPage {
         imp.response_group.set_visible(false);
     }
 
-    async fn remote(&self) -> ashpd::Result<(BitFlags<DeviceType>, Vec<Stream>, Session<'static>)> {
+    async fn remote(&self) -> ashpd::Result<(BitFlags<DeviceType>, Vec<Stream>, Session<'static, RemoteDesktop<'static>>)> {
         let imp = self.imp();
         let root = self.native().unwrap();
         let identifier = WindowIdentifier::from_native(&root).await;
@@ -218,12 +218,18 @@ impl RemoteDesktopPage {
                     None,
                     PersistMode::default(),
                 )
+                .await?
+                .response()
                 .await?;
         }
-        proxy.select_devices(&session, devices).await?;
+        proxy
+            .select_devices(&session, devices)
+            .await?
+            .response()
+            .await?;
 
         self.info("Starting a remote desktop session");
-        let response = proxy.start(&session, &identifier).await?.response()?;
+        let response = proxy.start(&session, &identifier).await?.response().await?;
         Ok((
             response.devices(),
             response.streams().unwrap_or_default().to_owned(),
@@ -29,7 +29,7 @@ mod imp {
         pub response_group: TemplateChild<adw::PreferencesGroup>,
         #[template_child]
         pub multiple_switch: TemplateChild<adw::SwitchRow>,
-        pub session: Arc<Mutex<Option<Session<'static>>>>,
+        pub session: Arc<Mutex<Option<Session<'static, Screencast<'static>>>>>,
         #[template_child]
         pub monitor_check: TemplateChild<gtk::CheckButton>,
         #[template_child]
@@ -209,7 +209,7 @@ impl ScreenCastPage {
         imp.response_group.set_visible(false);
     }
 
-    async fn screencast(&self) -> ashpd::Result<(Vec<Stream>, OwnedFd, Session<'static>)> {
+    async fn screencast(&self) -> ashpd::Result<(Vec<Stream>, OwnedFd, Session<'static, Screencast<'static>>)> {
         let imp = self.imp();
         let sources = self.selected_sources();
         let cursor_mode = self.selected_cursor_mode();
@@ -232,9 +232,11 @@ impl ScreenCastPage {
                 token.as_deref(),
                 persist_mode,
             )
+            .await?
+            .response()
             .await?;
         self.info("Starting a screen cast session");
-        let response = proxy.start(&session, &identifier).await?.response()?;
+        let response = proxy.start(&session, &identifier).await?.response().await?;
         if let Some(t) = response.restore_token() {
             token.replace(t.to_owned());
         }
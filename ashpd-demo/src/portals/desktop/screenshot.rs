@@ -1,5 +1,6 @@
 use adw::subclass::prelude::*;
 use ashpd::{desktop::screenshot, WindowIdentifier};
+use futures_util::TryFutureExt;
 use gtk::{gdk, gio, glib, prelude::*};
 
 use crate::widgets::{ColorWidget, PortalPage, PortalPageExt, PortalPageImpl};
@@ -67,8 +68,8 @@ impl ScreenshotPage {
         match screenshot::ColorRequest::default()
             .identifier(identifier)
             .send()
-            .await
             .and_then(|r| r.response())
+            .await
         {
             Ok(color) => {
                 self.imp().color_widget.set_rgba(gdk::RGBA::from(color));
@@ -95,8 +96,8 @@ impl ScreenshotPage {
             .interactive(interactive)
             .modal(modal)
             .send()
-            .await
             .and_then(|r| r.response())
+            .await
         {
             Ok(response) => {
                 let file = gio::File::for_uri(response.uri().as_str());
@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use zbus::{
+    fdo,
+    zvariant::{DeserializeDict, OwnedObjectPath, Type},
+};
+
+use super::{request, RequestImpl};
+use crate::{
+    desktop::{account::UserInformation, HandleToken, Response},
+    AppID, WindowIdentifierType,
+};
+
+#[derive(DeserializeDict, Type, Debug, Default)]
+/// Specified options for a [`AccountImpl::get_user_information`] request.
+#[zvariant(signature = "dict")]
+pub struct UserInformationOptions {
+    reason: Option<String>,
+}
+
+impl UserInformationOptions {
+    /// A user-visible reason for the request.
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+}
+
+/// The backend side of the Account portal.
+///
+/// Wrapper of the DBus interface: [`org.freedesktop.impl.portal.Account`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.impl.portal.Account.html).
+#[async_trait]
+pub trait AccountImpl: RequestImpl {
+    /// Gets information about the user.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`GetUserInformation`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.impl.portal.Account.html#org-freedesktop-impl-portal-account-getuserinformation).
+    #[doc(alias = "GetUserInformation")]
+    async fn get_user_information(
+        &self,
+        token: HandleToken,
+        app_id: Option<AppID>,
+        window_identifier: Option<WindowIdentifierType>,
+        options: UserInformationOptions,
+    ) -> Response<UserInformation>;
+}
+
+pub(crate) struct AccountInterface(pub(crate) Arc<dyn AccountImpl>);
+
+#[zbus::interface(name = "org.freedesktop.impl.portal.Account")]
+impl AccountInterface {
+    #[zbus(out_args("response", "results"))]
+    async fn get_user_information(
+        &self,
+        #[zbus(connection)] connection: &zbus::Connection,
+        handle: OwnedObjectPath,
+        app_id: &str,
+        window_identifier: &str,
+        options: UserInformationOptions,
+    ) -> fdo::Result<Response<UserInformation>> {
+        let token = request::token_from_path(&handle)?;
+        let imp = Arc::clone(&self.0);
+        request::run(
+            connection,
+            &handle,
+            imp.clone(),
+            imp.get_user_information(
+                token,
+                super::app_id(app_id),
+                super::window_identifier(window_identifier),
+                options,
+            ),
+        )
+        .await
+    }
+}
@@ -0,0 +1,303 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use zbus::{
+    fdo,
+    zvariant::{DeserializeDict, OwnedObjectPath, Type},
+};
+
+use super::{request, RequestImpl};
+use crate::{
+    desktop::{
+        file_chooser::{Choice, FileFilter, SelectedFiles},
+        HandleToken, Response,
+    },
+    AppID, FilePath, WindowIdentifierType,
+};
+
+#[derive(DeserializeDict, Type, Debug, Default)]
+/// Specified options for a [`FileChooserImpl::open_file`] request.
+#[zvariant(signature = "dict")]
+pub struct OpenFileOptions {
+    accept_label: Option<String>,
+    modal: Option<bool>,
+    multiple: Option<bool>,
+    directory: Option<bool>,
+    filters: Option<Vec<FileFilter>>,
+    current_filter: Option<FileFilter>,
+    choices: Option<Vec<Choice>>,
+    current_folder: Option<FilePath>,
+}
+
+impl OpenFileOptions {
+    /// The label for the accept button.
+    pub fn accept_label(&self) -> Option<&str> {
+        self.accept_label.as_deref()
+    }
+
+    /// Whether the dialog should be modal.
+    pub fn modal(&self) -> Option<bool> {
+        self.modal
+    }
+
+    /// Whether multiple files can be selected.
+    pub fn multiple(&self) -> Option<bool> {
+        self.multiple
+    }
+
+    /// Whether to select folders instead of files.
+    pub fn directory(&self) -> Option<bool> {
+        self.directory
+    }
+
+    /// The list of file filters.
+    pub fn filters(&self) -> &[FileFilter] {
+        self.filters.as_deref().unwrap_or_default()
+    }
+
+    /// The default filter.
+    pub fn current_filter(&self) -> Option<&FileFilter> {
+        self.current_filter.as_ref()
+    }
+
+    /// The list of choices.
+    pub fn choices(&self) -> &[Choice] {
+        self.choices.as_deref().unwrap_or_default()
+    }
+
+    /// The suggested folder to open the files from.
+    pub fn current_folder(&self) -> Option<&FilePath> {
+        self.current_folder.as_ref()
+    }
+}
+
+#[derive(DeserializeDict, Type, Debug, Default)]
+/// Specified options for a [`FileChooserImpl::save_file`] request.
+#[zvariant(signature = "dict")]
+pub struct SaveFileOptions {
+    accept_label: Option<String>,
+    modal: Option<bool>,
+    current_name: Option<String>,
+    current_folder: Option<FilePath>,
+    current_file: Option<FilePath>,
+    filters: Option<Vec<FileFilter>>,
+    current_filter: Option<FileFilter>,
+    choices: Option<Vec<Choice>>,
+}
+
+impl SaveFileOptions {
+    /// The label for the accept button.
+    pub fn accept_label(&self) -> Option<&str> {
+        self.accept_label.as_deref()
+    }
+
+    /// Whether the dialog should be modal.
+    pub fn modal(&self) -> Option<bool> {
+        self.modal
+    }
+
+    /// The suggested filename.
+    pub fn current_name(&self) -> Option<&str> {
+        self.current_name.as_deref()
+    }
+
+    /// The suggested folder to save the file in.
+    pub fn current_folder(&self) -> Option<&FilePath> {
+        self.current_folder.as_ref()
+    }
+
+    /// The current file, when saving an existing file.
+    pub fn current_file(&self) -> Option<&FilePath> {
+        self.current_file.as_ref()
+    }
+
+    /// The list of file filters.
+    pub fn filters(&self) -> &[FileFilter] {
+        self.filters.as_deref().unwrap_or_default()
+    }
+
+    /// The default filter.
+    pub fn current_filter(&self) -> Option<&FileFilter> {
+        self.current_filter.as_ref()
+    }
+
+    /// The list of choices.
+    pub fn choices(&self) -> &[Choice] {
+        self.choices.as_deref().unwrap_or_default()
+    }
+}
+
+#[derive(DeserializeDict, Type, Debug, Default)]
+/// Specified options for a [`FileChooserImpl::save_files`] request.
+#[zvariant(signature = "dict")]
+pub struct SaveFilesOptions {
+    accept_label: Option<String>,
+    modal: Option<bool>,
+    choices: Option<Vec<Choice>>,
+    current_folder: Option<FilePath>,
+    files: Option<Vec<FilePath>>,
+}
+
+impl SaveFilesOptions {
+    /// The label for the accept button.
+    pub fn accept_label(&self) -> Option<&str> {
+        self.accept_label.as_deref()
+    }
+
+    /// Whether the dialog should be modal.
+    pub fn modal(&self) -> Option<bool> {
+        self.modal
+    }
+
+    /// The list of choices.
+    pub fn choices(&self) -> &[Choice] {
+        self.choices.as_deref().unwrap_or_default()
+    }
+
+    /// The suggested folder to save the files in.
+    pub fn current_folder(&self) -> Option<&FilePath> {
+        self.current_folder.as_ref()
+    }
+
+    /// The files to be saved.
+    pub fn files(&self) -> &[FilePath] {
+        self.files.as_deref().unwrap_or_default()
+    }
+}
+
+/// The backend side of the FileChooser portal.
+///
+/// Wrapper of the DBus interface: [`org.freedesktop.impl.portal.FileChooser`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.impl.portal.FileChooser.html).
+#[async_trait]
+pub trait FileChooserImpl: RequestImpl {
+    /// Presents a file chooser dialog to the user to open one or more files.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`OpenFile`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.impl.portal.FileChooser.html#org-freedesktop-impl-portal-filechooser-openfile).
+    #[doc(alias = "OpenFile")]
+    async fn open_file(
+        &self,
+        token: HandleToken,
+        app_id: Option<AppID>,
+        window_identifier: Option<WindowIdentifierType>,
+        title: &str,
+        options: OpenFileOptions,
+    ) -> Response<SelectedFiles>;
+
+    /// Presents a file chooser dialog to the user to save a file.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`SaveFile`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.impl.portal.FileChooser.html#org-freedesktop-impl-portal-filechooser-savefile).
+    #[doc(alias = "SaveFile")]
+    async fn save_file(
+        &self,
+        token: HandleToken,
+        app_id: Option<AppID>,
+        window_identifier: Option<WindowIdentifierType>,
+        title: &str,
+        options: SaveFileOptions,
+    ) -> Response<SelectedFiles>;
+
+    /// Asks the user for a folder in which to save multiple files.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`SaveFiles`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.impl.portal.FileChooser.html#org-freedesktop-impl-portal-filechooser-savefiles).
+    #[doc(alias = "SaveFiles")]
+    async fn save_files(
+        &self,
+        token: HandleToken,
+        app_id: Option<AppID>,
+        window_identifier: Option<WindowIdentifierType>,
+        title: &str,
+        options: SaveFilesOptions,
+    ) -> Response<SelectedFiles>;
+}
+
+pub(crate) struct FileChooserInterface(pub(crate) Arc<dyn FileChooserImpl>);
+
+#[zbus::interface(name = "org.freedesktop.impl.portal.FileChooser")]
+impl FileChooserInterface {
+    #[zbus(out_args("response", "results"))]
+    async fn open_file(
+        &self,
+        #[zbus(connection)] connection: &zbus::Connection,
+        handle: OwnedObjectPath,
+        app_id: &str,
+        parent_window: &str,
+        title: &str,
+        options: OpenFileOptions,
+    ) -> fdo::Result<Response<SelectedFiles>> {
+        let token = request::token_from_path(&handle)?;
+        let imp = Arc::clone(&self.0);
+        request::run(
+            connection,
+            &handle,
+            imp.clone(),
+            imp.open_file(
+                token,
+                super::app_id(app_id),
+                super::window_identifier(parent_window),
+                title,
+                options,
+            ),
+        )
+        .await
+    }
+
+    #[zbus(out_args("response", "results"))]
+    async fn save_file(
+        &self,
+        #[zbus(connection)] connection: &zbus::Connection,
+        handle: OwnedObjectPath,
+        app_id: &str,
+        parent_window: &str,
+        title: &str,
+        options: SaveFileOptions,
+    ) -> fdo::Result<Response<SelectedFiles>> {
+        let token = request::token_from_path(&handle)?;
+        let imp = Arc::clone(&self.0);
+        request::run(
+            connection,
+            &handle,
+            imp.clone(),
+            imp.save_file(
+                token,
+                super::app_id(app_id),
+                super::window_identifier(parent_window),
+                title,
+                options,
+            ),
+        )
+        .await
+    }
+
+    #[zbus(out_args("response", "results"))]
+    async fn save_files(
+        &self,
+        #[zbus(connection)] connection: &zbus::Connection,
+        handle: OwnedObjectPath,
+        app_id: &str,
+        parent_window: &str,
+        title: &str,
+        options: SaveFilesOptions,
+    ) -> fdo::Result<Response<SelectedFiles>> {
+        let token = request::token_from_path(&handle)?;
+        let imp = Arc::clone(&self.0);
+        request::run(
+            connection,
+            &handle,
+            imp.clone(),
+            imp.save_files(
+                token,
+                super::app_id(app_id),
+                super::window_identifier(parent_window),
+                title,
+                options,
+            ),
+        )
+        .await
+    }
+}
@@ -0,0 +1,215 @@
+//! # Examples
+//!
+//! Serve the Account portal backend on the session bus.
+//!
+//! ```rust,no_run
+//! use ashpd::{
+//!     async_trait,
+//!     backend::{
+//!         account::{AccountImpl, UserInformationOptions},
+//!         Builder, RequestImpl,
+//!     },
+//!     desktop::{account::UserInformation, HandleToken, Response},
+//!     AppID, WindowIdentifierType,
+//! };
+//!
+//! struct Account;
+//!
+//! #[async_trait]
+//! impl RequestImpl for Account {
+//!     async fn close(&self, _token: HandleToken) {}
+//! }
+//!
+//! #[async_trait]
+//! impl AccountImpl for Account {
+//!     async fn get_user_information(
+//!         &self,
+//!         _token: HandleToken,
+//!         _app_id: Option<AppID>,
+//!         _window_identifier: Option<WindowIdentifierType>,
+//!         _options: UserInformationOptions,
+//!     ) -> Response<UserInformation> {
+//!         Response::ok(UserInformation::new(
+//!             "user",
+//!             "User",
+//!             "file:///home/user/.face".parse().unwrap(),
+//!         ))
+//!     }
+//! }
+//!
+//! async fn run() -> ashpd::Result<()> {
+//!     let _connection = Builder::new("org.freedesktop.impl.portal.desktop.example")?
+//!         .account(Account)
+//!         .build()
+//!         .await?;
+//!
+//!     std::future::pending::<()>().await;
+//!     Ok(())
+//! }
+//! ```
+
+use std::{collections::HashMap, str::FromStr, sync::Arc};
+
+use serde::Deserialize;
+use zbus::{
+    names::OwnedWellKnownName,
+    zvariant::{OwnedValue, Type},
+};
+
+use crate::{desktop::Response, proxy::DESKTOP_PATH, AppID, Error, WindowIdentifierType};
+
+/// Get user information.
+pub mod account;
+/// Open and save files.
+pub mod file_chooser;
+mod request;
+/// Record screens or windows over PipeWire.
+pub mod screencast;
+/// Take screenshots and pick colors.
+pub mod screenshot;
+mod session;
+
+use self::{
+    account::{AccountImpl, AccountInterface},
+    file_chooser::{FileChooserImpl, FileChooserInterface},
+    screencast::{ScreencastImpl, ScreencastInterface},
+    screenshot::{ScreenshotImpl, ScreenshotInterface},
+};
+pub use self::{request::RequestImpl, session::SessionImpl};
+
+pub(crate) type VarDict = HashMap<String, OwnedValue>;
+
+/// A [builder-pattern] type to export the portal backend interfaces and claim
+/// a well-known name.
+///
+/// [builder-pattern]: https://doc.rust-lang.org/1.0.0/style/ownership/builders.html
+pub struct Builder {
+    name: OwnedWellKnownName,
+    connection: Option<zbus::Connection>,
+    account: Option<Arc<dyn AccountImpl>>,
+    file_chooser: Option<Arc<dyn FileChooserImpl>>,
+    screencast: Option<Arc<dyn ScreencastImpl>>,
+    screenshot: Option<Arc<dyn ScreenshotImpl>>,
+}
+
+impl Builder {
+    /// Create a new builder for the backend that will be owning `name`.
+    ///
+    /// The name is the one referenced from the `DBusName` key of the
+    /// backend's `.portal` file.
+    pub fn new(name: &str) -> Result<Self, Error> {
+        let name = OwnedWellKnownName::try_from(name).map_err(zbus::Error::from)?;
+        Ok(Self {
+            name,
+            connection: None,
+            account: None,
+            file_chooser: None,
+            screencast: None,
+            screenshot: None,
+        })
+    }
+
+    /// Sets the connection to export the interfaces on, instead of the session
+    /// bus.
+    ///
+    /// **Note** a peer-to-peer connection should be built with its object
+    /// server running, e.g. by serving [`zbus::fdo::ObjectManager`] from the
+    /// connection builder, not to miss the calls made right after it's built.
+    #[must_use]
+    pub fn connection(mut self, connection: zbus::Connection) -> Self {
+        self.connection = Some(connection);
+        self
+    }
+
+    /// Implements the `org.freedesktop.impl.portal.Account` interface.
+    #[must_use]
+    pub fn account(mut self, imp: impl AccountImpl + 'static) -> Self {
+        self.account = Some(Arc::new(imp));
+        self
+    }
+
+    /// Implements the `org.freedesktop.impl.portal.FileChooser` interface.
+    #[must_use]
+    pub fn file_chooser(mut self, imp: impl FileChooserImpl + 'static) -> Self {
+        self.file_chooser = Some(Arc::new(imp));
+        self
+    }
+
+    /// Implements the `org.freedesktop.impl.portal.ScreenCast` interface.
+    #[must_use]
+    pub fn screencast(mut self, imp: impl ScreencastImpl + 'static) -> Self {
+        self.screencast = Some(Arc::new(imp));
+        self
+    }
+
+    /// Implements the `org.freedesktop.impl.portal.Screenshot` interface.
+    #[must_use]
+    pub fn screenshot(mut self, imp: impl ScreenshotImpl + 'static) -> Self {
+        self.screenshot = Some(Arc::new(imp));
+        self
+    }
+
+    /// Export the interfaces and request the well-known name, unless the
+    /// connection is a peer-to-peer one.
+    ///
+    /// The interfaces are served for as long as the returned connection is
+    /// alive.
+    pub async fn build(self) -> Result<zbus::Connection, Error> {
+        let connection = match self.connection {
+            Some(connection) => connection,
+            None => zbus::Connection::session().await?,
+        };
+        {
+            let server = connection.object_server();
+            if let Some(imp) = self.account {
+                server.at(DESKTOP_PATH, AccountInterface(imp)).await?;
+            }
+            if let Some(imp) = self.file_chooser {
+                server.at(DESKTOP_PATH, FileChooserInterface(imp)).await?;
+            }
+            if let Some(imp) = self.screencast {
+                server.at(DESKTOP_PATH, ScreencastInterface(imp)).await?;
+            }
+            if let Some(imp) = self.screenshot {
+                server.at(DESKTOP_PATH, ScreenshotInterface(imp)).await?;
+            }
+        }
+        if connection.is_bus() {
+            connection.request_name(self.name).await?;
+        }
+        Ok(connection)
+    }
+}
+
+impl std::fmt::Debug for Builder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Builder")
+            .field("name", &self.name.as_str())
+            .field("account", &self.account.is_some())
+            .field("file_chooser", &self.file_chooser.is_some())
+            .field("screencast", &self.screencast.is_some())
+            .field("screenshot", &self.screenshot.is_some())
+            .finish()
+    }
+}
+
+/// Host applications are passed with an empty app id.
+pub(crate) fn app_id(app_id: &str) -> Option<AppID> {
+    AppID::from_str(app_id).ok()
+}
+
+/// Applications without a window are passed with an empty identifier.
+pub(crate) fn window_identifier(identifier: &str) -> Option<WindowIdentifierType> {
+    WindowIdentifierType::from_str(identifier).ok()
+}
+
+/// Methods that don't return any result still have to send a vardict.
+pub(crate) fn empty_results<T>(response: Response<T>) -> Response<VarDict>
+where
+    T: for<'de> Deserialize<'de> + Type,
+{
+    match response {
+        Response::Ok(_) => Response::ok(VarDict::new()),
+        Response::Err(err) => Response::Err(err),
+    }
+}
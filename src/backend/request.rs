@@ -0,0 +1,110 @@
+use std::{future::Future, sync::Arc};
+
+use async_trait::async_trait;
+use futures_util::{
+    future::{AbortHandle, Abortable, BoxFuture},
+    FutureExt,
+};
+use serde::{Deserialize, Serialize};
+use zbus::{
+    fdo,
+    zvariant::{OwnedObjectPath, Type},
+};
+
+use crate::desktop::{HandleToken, Response};
+
+/// Shared by all the portal backend interfaces, the implementation is notified
+/// when the frontend closes a request before it was answered.
+///
+/// Wrapper of the DBus interface: [`org.freedesktop.impl.portal.Request`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.impl.portal.Request.html).
+#[async_trait]
+pub trait RequestImpl: Send + Sync {
+    /// Ends the user interaction related to the request identified by
+    /// `token`.
+    ///
+    /// The pending method call is answered with a cancelled [`Response`]
+    /// once this returns.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`Close`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.impl.portal.Request.html#org-freedesktop-impl-portal-request-close).
+    #[doc(alias = "Close")]
+    async fn close(&self, token: HandleToken);
+}
+
+pub(crate) struct RequestInterface {
+    token: HandleToken,
+    close: Box<dyn Fn(HandleToken) -> BoxFuture<'static, ()> + Send + Sync>,
+    abort_handle: AbortHandle,
+}
+
+#[zbus::interface(name = "org.freedesktop.impl.portal.Request")]
+impl RequestInterface {
+    async fn close(&self) {
+        #[cfg(feature = "tracing")]
+        tracing::debug!("Closing request {}", self.token);
+        (self.close)(self.token.clone()).await;
+        self.abort_handle.abort();
+    }
+}
+
+/// Exports a Request object at `handle` for as long as `response` is being
+/// computed, and answers with a cancelled response if it gets closed.
+pub(crate) async fn run<I, T, F>(
+    connection: &zbus::Connection,
+    handle: &OwnedObjectPath,
+    imp: Arc<I>,
+    response: F,
+) -> fdo::Result<Response<T>>
+where
+    I: RequestImpl + ?Sized + 'static,
+    T: for<'de> Deserialize<'de> + Serialize + Type,
+    F: Future<Output = Response<T>>,
+{
+    let token = token_from_path(handle)?;
+    let close = Box::new(move |token| {
+        let imp = Arc::clone(&imp);
+        async move { imp.close(token).await }.boxed()
+    });
+    let (abort_handle, registration) = AbortHandle::new_pair();
+    let server = connection.object_server();
+    server
+        .at(
+            handle,
+            RequestInterface {
+                token,
+                close,
+                abort_handle,
+            },
+        )
+        .await?;
+    let response = Abortable::new(response, registration)
+        .await
+        .unwrap_or_else(|_| Response::cancelled());
+    server.remove::<RequestInterface, _>(handle).await?;
+    Ok(response)
+}
+
+/// The handle token is the last element of a request or session object path.
+pub(crate) fn token_from_path(path: &OwnedObjectPath) -> fdo::Result<HandleToken> {
+    let element = path.as_str().rsplit('/').next().unwrap_or_default();
+    zbus::names::MemberName::try_from(element)
+        .map_err(|err| fdo::Error::InvalidArgs(err.to_string()))?;
+    HandleToken::try_from(element).map_err(|err| fdo::Error::InvalidArgs(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_from_request_path() {
+        let path =
+            OwnedObjectPath::try_from("/org/freedesktop/portal/desktop/request/1_42/ashpd_Abc123")
+                .unwrap();
+        assert_eq!(token_from_path(&path).unwrap().to_string(), "ashpd_Abc123");
+
+        let path = OwnedObjectPath::try_from("/").unwrap();
+        assert!(token_from_path(&path).is_err());
+    }
+}
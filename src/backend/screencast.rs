@@ -0,0 +1,483 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use enumflags2::BitFlags;
+use zbus::{
+    fdo,
+    zvariant::{DeserializeDict, OwnedObjectPath, OwnedValue, SerializeDict, Type},
+};
+
+use super::{request, session, RequestImpl, SessionImpl, VarDict};
+use crate::{
+    desktop::{
+        screencast::{CursorMode, SourceType, Stream},
+        HandleToken, PersistMode, Response,
+    },
+    AppID, WindowIdentifierType,
+};
+
+#[derive(DeserializeDict, Type, Debug, Default)]
+/// Specified options for a [`ScreencastImpl::select_sources`] request.
+#[zvariant(signature = "dict")]
+pub struct SelectSourcesOptions {
+    types: Option<BitFlags<SourceType>>,
+    multiple: Option<bool>,
+    cursor_mode: Option<CursorMode>,
+    persist_mode: Option<PersistMode>,
+    restore_data: Option<(String, u32, OwnedValue)>,
+}
+
+impl SelectSourcesOptions {
+    /// The types of content to record.
+    pub fn types(&self) -> Option<BitFlags<SourceType>> {
+        self.types
+    }
+
+    /// Whether to allow selecting multiple sources.
+    pub fn multiple(&self) -> Option<bool> {
+        self.multiple
+    }
+
+    /// How the cursor should be drawn in the screen cast stream.
+    pub fn cursor_mode(&self) -> Option<CursorMode> {
+        self.cursor_mode
+    }
+
+    /// How the session should be persisted.
+    pub fn persist_mode(&self) -> Option<PersistMode> {
+        self.persist_mode
+    }
+
+    /// The data stored by the backend with
+    /// [`StartCastResponse::restore_data`] in a previous session, to restore
+    /// it: the vendor, the version of the data format and the data.
+    pub fn restore_data(&self) -> Option<(&str, u32, &OwnedValue)> {
+        self.restore_data
+            .as_ref()
+            .map(|(vendor, version, data)| (vendor.as_str(), *version, data))
+    }
+}
+
+#[derive(SerializeDict, DeserializeDict, Type, Debug)]
+/// A response to a [`ScreencastImpl::start`] request.
+///
+/// The frontend turns the restore data into the restore token given to the
+/// application.
+#[zvariant(signature = "dict")]
+pub struct StartCastResponse {
+    streams: Vec<Stream>,
+    persist_mode: Option<PersistMode>,
+    restore_data: Option<(String, u32, OwnedValue)>,
+}
+
+impl StartCastResponse {
+    /// Create a new response from the started streams.
+    pub fn new(streams: Vec<Stream>) -> Self {
+        Self {
+            streams,
+            persist_mode: None,
+            restore_data: None,
+        }
+    }
+
+    /// How the session is persisted, at most the mode requested with
+    /// [`SelectSourcesOptions::persist_mode`].
+    #[must_use]
+    pub fn persist_mode(mut self, persist_mode: impl Into<Option<PersistMode>>) -> Self {
+        self.persist_mode = persist_mode.into();
+        self
+    }
+
+    /// The data to restore the session with, given back through
+    /// [`SelectSourcesOptions::restore_data`]: the vendor, the version of
+    /// the data format and the data.
+    #[must_use]
+    pub fn restore_data(mut self, vendor: &str, version: u32, data: impl Into<OwnedValue>) -> Self {
+        self.restore_data = Some((vendor.to_owned(), version, data.into()));
+        self
+    }
+
+    /// The started streams.
+    pub fn streams(&self) -> &[Stream] {
+        &self.streams
+    }
+}
+
+/// The backend side of the ScreenCast portal.
+///
+/// Wrapper of the DBus interface: [`org.freedesktop.impl.portal.ScreenCast`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.impl.portal.ScreenCast.html).
+#[async_trait]
+pub trait ScreencastImpl: RequestImpl + SessionImpl {
+    /// The source types the backend can record.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`AvailableSourceTypes`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.impl.portal.ScreenCast.html#org-freedesktop-impl-portal-screencast-availablesourcetypes).
+    #[doc(alias = "AvailableSourceTypes")]
+    fn available_source_types(&self) -> BitFlags<SourceType>;
+
+    /// The cursor modes the backend supports.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`AvailableCursorModes`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.impl.portal.ScreenCast.html#org-freedesktop-impl-portal-screencast-availablecursormodes).
+    #[doc(alias = "AvailableCursorModes")]
+    fn available_cursor_modes(&self) -> BitFlags<CursorMode>;
+
+    /// Creates a screen cast session identified by `session_token`.
+    ///
+    /// The session object is exported if the response is successful.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`CreateSession`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.impl.portal.ScreenCast.html#org-freedesktop-impl-portal-screencast-createsession).
+    #[doc(alias = "CreateSession")]
+    async fn create_session(
+        &self,
+        token: HandleToken,
+        session_token: HandleToken,
+        app_id: Option<AppID>,
+    ) -> Response<()>;
+
+    /// Configures what the screen cast session should record.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`SelectSources`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.impl.portal.ScreenCast.html#org-freedesktop-impl-portal-screencast-selectsources).
+    #[doc(alias = "SelectSources")]
+    async fn select_sources(
+        &self,
+        token: HandleToken,
+        session_token: HandleToken,
+        app_id: Option<AppID>,
+        options: SelectSourcesOptions,
+    ) -> Response<()>;
+
+    /// Starts the screen cast session, returning the PipeWire streams.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`Start`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.impl.portal.ScreenCast.html#org-freedesktop-impl-portal-screencast-start).
+    #[doc(alias = "Start")]
+    async fn start(
+        &self,
+        token: HandleToken,
+        session_token: HandleToken,
+        app_id: Option<AppID>,
+        window_identifier: Option<WindowIdentifierType>,
+    ) -> Response<StartCastResponse>;
+}
+
+pub(crate) struct ScreencastInterface(pub(crate) Arc<dyn ScreencastImpl>);
+
+#[zbus::interface(name = "org.freedesktop.impl.portal.ScreenCast")]
+impl ScreencastInterface {
+    #[zbus(property(emits_changed_signal = "const"), name = "version")]
+    fn version(&self) -> u32 {
+        4
+    }
+
+    #[zbus(property(emits_changed_signal = "const"))]
+    fn available_source_types(&self) -> u32 {
+        self.0.available_source_types().bits()
+    }
+
+    #[zbus(property(emits_changed_signal = "const"))]
+    fn available_cursor_modes(&self) -> u32 {
+        self.0.available_cursor_modes().bits()
+    }
+
+    #[zbus(out_args("response", "results"))]
+    async fn create_session(
+        &self,
+        #[zbus(connection)] connection: &zbus::Connection,
+        handle: OwnedObjectPath,
+        session_handle: OwnedObjectPath,
+        app_id: &str,
+        _options: VarDict,
+    ) -> fdo::Result<Response<VarDict>> {
+        let token = request::token_from_path(&handle)?;
+        let session_token = request::token_from_path(&session_handle)?;
+        let imp = Arc::clone(&self.0);
+        let response = request::run(
+            connection,
+            &handle,
+            imp.clone(),
+            imp.create_session(token, session_token, super::app_id(app_id)),
+        )
+        .await?;
+        if let Response::Ok(()) = response {
+            session::export(connection, &session_handle, imp).await?;
+        }
+        Ok(super::empty_results(response))
+    }
+
+    #[zbus(out_args("response", "results"))]
+    async fn select_sources(
+        &self,
+        #[zbus(connection)] connection: &zbus::Connection,
+        handle: OwnedObjectPath,
+        session_handle: OwnedObjectPath,
+        app_id: &str,
+        options: SelectSourcesOptions,
+    ) -> fdo::Result<Response<VarDict>> {
+        let token = request::token_from_path(&handle)?;
+        let session_token = request::token_from_path(&session_handle)?;
+        let imp = Arc::clone(&self.0);
+        let response = request::run(
+            connection,
+            &handle,
+            imp.clone(),
+            imp.select_sources(token, session_token, super::app_id(app_id), options),
+        )
+        .await?;
+        Ok(super::empty_results(response))
+    }
+
+    #[zbus(out_args("response", "results"))]
+    async fn start(
+        &self,
+        #[zbus(connection)] connection: &zbus::Connection,
+        handle: OwnedObjectPath,
+        session_handle: OwnedObjectPath,
+        app_id: &str,
+        parent_window: &str,
+        _options: VarDict,
+    ) -> fdo::Result<Response<StartCastResponse>> {
+        let token = request::token_from_path(&handle)?;
+        let session_token = request::token_from_path(&session_handle)?;
+        let imp = Arc::clone(&self.0);
+        request::run(
+            connection,
+            &handle,
+            imp.clone(),
+            imp.start(
+                token,
+                session_token,
+                super::app_id(app_id),
+                super::window_identifier(parent_window),
+            ),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use zbus::zvariant::{serialized::Context, to_bytes, Value, LE};
+
+    use super::*;
+
+    #[test]
+    fn restore_data_round_trip() {
+        let ctxt = Context::new_dbus(LE, 0);
+        let restore_data = ("GNOME", 1u32, Value::from("monitor-1"));
+        let options = HashMap::from([
+            ("persist_mode", Value::from(2u32)),
+            ("restore_data", Value::from(restore_data)),
+        ]);
+        let options = to_bytes(ctxt, &options)
+            .unwrap()
+            .deserialize::<SelectSourcesOptions>()
+            .unwrap()
+            .0;
+        assert_eq!(options.persist_mode(), Some(PersistMode::ExplicitlyRevoked));
+        let (vendor, version, data) = options.restore_data().unwrap();
+        assert_eq!((vendor, version), ("GNOME", 1));
+        assert_eq!(
+            String::try_from(data.try_clone().unwrap()).unwrap(),
+            "monitor-1"
+        );
+
+        let response = StartCastResponse::new(vec![Stream::builder(42).build()])
+            .persist_mode(PersistMode::ExplicitlyRevoked)
+            .restore_data("GNOME", 1, OwnedValue::from(7u32));
+        let bytes = to_bytes(ctxt, &response).unwrap();
+        let dict = bytes
+            .deserialize::<HashMap<String, OwnedValue>>()
+            .unwrap()
+            .0;
+        assert!(!dict.contains_key("restore_token"));
+        assert_eq!(u32::try_from(&dict["persist_mode"]).unwrap(), 2);
+        let response = bytes.deserialize::<StartCastResponse>().unwrap().0;
+        assert_eq!(response.streams()[0].pipe_wire_node_id(), 42);
+        assert_eq!(response.persist_mode, Some(PersistMode::ExplicitlyRevoked));
+        let (vendor, version, data) = response.restore_data.unwrap();
+        assert_eq!((vendor.as_str(), version), ("GNOME", 1));
+        assert_eq!(u32::try_from(data).unwrap(), 7);
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod portal_tests {
+    use std::{sync::Mutex, time::Duration};
+
+    use futures_util::StreamExt;
+    use zbus::{
+        message::Type as MessageType, proxy::CacheProperties, zvariant::ObjectPath, MatchRule,
+        MessageStream,
+    };
+
+    use super::*;
+    use crate::{backend::Builder, helpers::sleep, testing::p2p_pair};
+
+    const NAME: &str = "org.freedesktop.impl.portal.desktop.test";
+    const REQUEST_PATH: &str = "/org/freedesktop/portal/desktop/request/1_2";
+    const SESSION_PATH: &str = "/org/freedesktop/portal/desktop/session/1_2/ashpd_session";
+
+    /// Records the closed requests and sessions.
+    struct Backend(Arc<Mutex<Vec<String>>>);
+
+    #[async_trait]
+    impl RequestImpl for Backend {
+        async fn close(&self, token: HandleToken) {
+            self.0.lock().unwrap().push(format!("request {token}"));
+        }
+    }
+
+    #[async_trait]
+    impl SessionImpl for Backend {
+        async fn session_closed(&self, session_token: HandleToken) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("session {session_token}"));
+        }
+    }
+
+    #[async_trait]
+    impl ScreencastImpl for Backend {
+        fn available_source_types(&self) -> BitFlags<SourceType> {
+            SourceType::Monitor.into()
+        }
+
+        fn available_cursor_modes(&self) -> BitFlags<CursorMode> {
+            CursorMode::Embedded.into()
+        }
+
+        async fn create_session(
+            &self,
+            _token: HandleToken,
+            _session_token: HandleToken,
+            _app_id: Option<AppID>,
+        ) -> Response<()> {
+            Response::ok(())
+        }
+
+        async fn select_sources(
+            &self,
+            _token: HandleToken,
+            _session_token: HandleToken,
+            _app_id: Option<AppID>,
+            _options: SelectSourcesOptions,
+        ) -> Response<()> {
+            Response::ok(())
+        }
+
+        async fn start(
+            &self,
+            _token: HandleToken,
+            _session_token: HandleToken,
+            _app_id: Option<AppID>,
+            _window_identifier: Option<WindowIdentifierType>,
+        ) -> Response<StartCastResponse> {
+            // Until the request is closed.
+            std::future::pending().await
+        }
+    }
+
+    async fn proxy<'a>(
+        connection: &zbus::Connection,
+        path: &'a str,
+        interface: &'a str,
+    ) -> zbus::Proxy<'a> {
+        zbus::proxy::Builder::new(connection)
+            .destination(NAME)
+            .unwrap()
+            .path(path)
+            .unwrap()
+            .interface(interface)
+            .unwrap()
+            .cache_properties(CacheProperties::No)
+            .build()
+            .await
+            .unwrap()
+    }
+
+    #[test]
+    fn requests_and_sessions() {
+        zbus::block_on(async {
+            let (client, server) = p2p_pair(true).await.unwrap();
+            let closed = Arc::new(Mutex::new(Vec::new()));
+            let _server = Builder::new(NAME)
+                .unwrap()
+                .connection(server)
+                .screencast(Backend(closed.clone()))
+                .build()
+                .await
+                .unwrap();
+            let screencast = proxy(
+                &client,
+                "/org/freedesktop/portal/desktop",
+                "org.freedesktop.impl.portal.ScreenCast",
+            )
+            .await;
+            assert_eq!(screencast.get_property::<u32>("version").await.unwrap(), 4);
+
+            // The session is exported once created.
+            let session_handle = ObjectPath::from_static_str_unchecked(SESSION_PATH);
+            let handle = ObjectPath::try_from(format!("{REQUEST_PATH}/ashpd_create")).unwrap();
+            let (response, _) = screencast
+                .call::<_, _, (u32, VarDict)>(
+                    "CreateSession",
+                    &(&handle, &session_handle, "", VarDict::new()),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response, 0);
+            let session = proxy(&client, SESSION_PATH, "org.freedesktop.impl.portal.Session").await;
+            assert_eq!(session.get_property::<u32>("version").await.unwrap(), 1);
+
+            // Closing a pending request answers it with a cancelled response.
+            let start_path = format!("{REQUEST_PATH}/ashpd_start");
+            let handle = ObjectPath::try_from(start_path.as_str()).unwrap();
+            let request = proxy(&client, &start_path, "org.freedesktop.impl.portal.Request").await;
+            let body = (&handle, &session_handle, "", "", VarDict::new());
+            let (started, ()) = futures_util::join!(
+                screencast.call::<_, _, (u32, VarDict)>("Start", &body),
+                async {
+                    // The request is exported once the call is received.
+                    while request.call_method("Close", &()).await.is_err() {
+                        sleep(Duration::from_millis(1)).await;
+                    }
+                }
+            );
+            assert_eq!(started.unwrap().0, 1);
+            assert!(request.call_method("Close", &()).await.is_err());
+
+            // Closing the session emits Closed and removes it.
+            // Proxies only take the signals of the owner of their destination
+            // name, which a peer-to-peer connection doesn't have.
+            let rule = MatchRule::builder()
+                .msg_type(MessageType::Signal)
+                .path(SESSION_PATH)
+                .unwrap()
+                .member("Closed")
+                .unwrap()
+                .build();
+            let mut closed_signal = MessageStream::for_match_rule(rule, &client, None)
+                .await
+                .unwrap();
+            session.call_method("Close", &()).await.unwrap();
+            assert!(closed_signal.next().await.is_some());
+            assert!(session.get_property::<u32>("version").await.is_err());
+
+            assert_eq!(
+                *closed.lock().unwrap(),
+                ["request ashpd_start", "session ashpd_session"]
+            );
+        });
+    }
+}
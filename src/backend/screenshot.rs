@@ -0,0 +1,133 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use zbus::{
+    fdo,
+    zvariant::{DeserializeDict, OwnedObjectPath, Type},
+};
+
+use super::{request, RequestImpl, VarDict};
+use crate::{
+    desktop::{screenshot::Screenshot, Color, HandleToken, Response},
+    AppID, WindowIdentifierType,
+};
+
+#[derive(DeserializeDict, Type, Debug, Default)]
+/// Specified options for a [`ScreenshotImpl::screenshot`] request.
+#[zvariant(signature = "dict")]
+pub struct ScreenshotOptions {
+    modal: Option<bool>,
+    interactive: Option<bool>,
+    permission_store_checked: Option<bool>,
+}
+
+impl ScreenshotOptions {
+    /// Whether the dialog should be modal.
+    pub fn modal(&self) -> Option<bool> {
+        self.modal
+    }
+
+    /// Whether the dialog should offer customization before taking the
+    /// screenshot.
+    pub fn interactive(&self) -> Option<bool> {
+        self.interactive
+    }
+
+    /// Whether the frontend already checked the permission store for a
+    /// non-interactive screenshot.
+    pub fn permission_store_checked(&self) -> Option<bool> {
+        self.permission_store_checked
+    }
+}
+
+/// The backend side of the Screenshot portal.
+///
+/// Wrapper of the DBus interface: [`org.freedesktop.impl.portal.Screenshot`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.impl.portal.Screenshot.html).
+#[async_trait]
+pub trait ScreenshotImpl: RequestImpl {
+    /// Takes a screenshot.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`Screenshot`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.impl.portal.Screenshot.html#org-freedesktop-impl-portal-screenshot-screenshot).
+    #[doc(alias = "Screenshot")]
+    async fn screenshot(
+        &self,
+        token: HandleToken,
+        app_id: Option<AppID>,
+        window_identifier: Option<WindowIdentifierType>,
+        options: ScreenshotOptions,
+    ) -> Response<Screenshot>;
+
+    /// Obtains the color of a single pixel.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`PickColor`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.impl.portal.Screenshot.html#org-freedesktop-impl-portal-screenshot-pickcolor).
+    #[doc(alias = "PickColor")]
+    async fn pick_color(
+        &self,
+        token: HandleToken,
+        app_id: Option<AppID>,
+        window_identifier: Option<WindowIdentifierType>,
+    ) -> Response<Color>;
+}
+
+pub(crate) struct ScreenshotInterface(pub(crate) Arc<dyn ScreenshotImpl>);
+
+#[zbus::interface(name = "org.freedesktop.impl.portal.Screenshot")]
+impl ScreenshotInterface {
+    #[zbus(property(emits_changed_signal = "const"), name = "version")]
+    fn version(&self) -> u32 {
+        2
+    }
+
+    #[zbus(out_args("response", "results"))]
+    async fn screenshot(
+        &self,
+        #[zbus(connection)] connection: &zbus::Connection,
+        handle: OwnedObjectPath,
+        app_id: &str,
+        parent_window: &str,
+        options: ScreenshotOptions,
+    ) -> fdo::Result<Response<Screenshot>> {
+        let token = request::token_from_path(&handle)?;
+        let imp = Arc::clone(&self.0);
+        request::run(
+            connection,
+            &handle,
+            imp.clone(),
+            imp.screenshot(
+                token,
+                super::app_id(app_id),
+                super::window_identifier(parent_window),
+                options,
+            ),
+        )
+        .await
+    }
+
+    #[zbus(out_args("response", "results"))]
+    async fn pick_color(
+        &self,
+        #[zbus(connection)] connection: &zbus::Connection,
+        handle: OwnedObjectPath,
+        app_id: &str,
+        parent_window: &str,
+        _options: VarDict,
+    ) -> fdo::Result<Response<Color>> {
+        let token = request::token_from_path(&handle)?;
+        let imp = Arc::clone(&self.0);
+        request::run(
+            connection,
+            &handle,
+            imp.clone(),
+            imp.pick_color(
+                token,
+                super::app_id(app_id),
+                super::window_identifier(parent_window),
+            ),
+        )
+        .await
+    }
+}
@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures_util::{future::BoxFuture, FutureExt};
+use zbus::{
+    fdo,
+    object_server::SignalContext,
+    zvariant::{OwnedObjectPath, OwnedValue},
+    ObjectServer,
+};
+
+use super::request::token_from_path;
+use crate::desktop::HandleToken;
+
+/// Shared by all the portal backend interfaces that involve long lived
+/// sessions, the implementation is notified when the frontend closes one of
+/// them.
+///
+/// Wrapper of the DBus interface: [`org.freedesktop.impl.portal.Session`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.impl.portal.Session.html).
+#[async_trait]
+pub trait SessionImpl: Send + Sync {
+    /// Ends the session identified by `session_token` and all the related
+    /// user interaction.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`Close`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.impl.portal.Session.html#org-freedesktop-impl-portal-session-close).
+    #[doc(alias = "Close")]
+    async fn session_closed(&self, session_token: HandleToken);
+}
+
+pub(crate) struct SessionInterface {
+    token: HandleToken,
+    close: Box<dyn Fn(HandleToken) -> BoxFuture<'static, ()> + Send + Sync>,
+}
+
+#[zbus::interface(name = "org.freedesktop.impl.portal.Session")]
+impl SessionInterface {
+    async fn close(
+        &self,
+        #[zbus(object_server)] server: &ObjectServer,
+        #[zbus(signal_context)] ctxt: SignalContext<'_>,
+    ) -> fdo::Result<()> {
+        #[cfg(feature = "tracing")]
+        tracing::debug!("Closing session {}", self.token);
+        (self.close)(self.token.clone()).await;
+        Self::closed(&ctxt, Default::default()).await?;
+        server.remove::<Self, _>(ctxt.path()).await?;
+        Ok(())
+    }
+
+    #[zbus(signal)]
+    async fn closed(
+        ctxt: &SignalContext<'_>,
+        details: std::collections::HashMap<String, OwnedValue>,
+    ) -> zbus::Result<()>;
+
+    #[zbus(property(emits_changed_signal = "const"), name = "version")]
+    fn version(&self) -> u32 {
+        1
+    }
+}
+
+/// Exports a Session object at `handle`, it stays alive until the frontend
+/// closes it.
+pub(crate) async fn export<I>(
+    connection: &zbus::Connection,
+    handle: &OwnedObjectPath,
+    imp: Arc<I>,
+) -> fdo::Result<()>
+where
+    I: SessionImpl + ?Sized + 'static,
+{
+    let token = token_from_path(handle)?;
+    let close = Box::new(move |token| {
+        let imp = Arc::clone(&imp);
+        async move { imp.session_closed(token).await }.boxed()
+    });
+    connection
+        .object_server()
+        .at(handle, SessionInterface { token, close })
+        .await?;
+    Ok(())
+}
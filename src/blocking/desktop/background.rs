@@ -0,0 +1,43 @@
+//! Blocking wrapper of [`crate::desktop::background`].
+
+use crate::{
+    blocking::{block_on, Async},
+    desktop::background,
+    Error,
+};
+
+/// Blocking wrapper of [`background::BackgroundProxy`].
+pub struct BackgroundProxy<'a>(Async<background::BackgroundProxy<'a>>);
+
+impl<'a> BackgroundProxy<'a> {
+    /// Create a new instance of [`BackgroundProxy`].
+    pub fn new() -> Result<BackgroundProxy<'a>, Error> {
+        block_on(background::BackgroundProxy::new()).map(Self::from)
+    }
+
+    /// Create a new instance of [`BackgroundProxy`] on the given connection.
+    pub fn with_connection(connection: &zbus::Connection) -> Result<BackgroundProxy<'a>, Error> {
+        block_on(background::BackgroundProxy::with_connection(connection)).map(Self::from)
+    }
+
+    /// The asynchronous proxy this wraps.
+    pub fn inner(&self) -> &background::BackgroundProxy<'a> {
+        &self.0
+    }
+
+    /// Convert into the asynchronous proxy this wraps.
+    pub fn into_inner(self) -> background::BackgroundProxy<'a> {
+        self.0.into_inner()
+    }
+
+    /// Blocking version of [`background::BackgroundProxy::set_status`].
+    pub fn set_status(&self, message: &str) -> Result<(), Error> {
+        block_on(self.0.set_status(message))
+    }
+}
+
+impl<'a> From<background::BackgroundProxy<'a>> for BackgroundProxy<'a> {
+    fn from(proxy: background::BackgroundProxy<'a>) -> Self {
+        Self(Async::new(proxy))
+    }
+}
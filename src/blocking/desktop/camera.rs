@@ -0,0 +1,83 @@
+//! Blocking wrapper of [`crate::desktop::camera`].
+
+use std::os::fd::OwnedFd;
+
+use crate::{
+    blocking::{block_on, desktop::Request, Async},
+    desktop::camera,
+    Error,
+};
+
+/// Blocking wrapper of [`camera::Camera`].
+#[derive(Debug)]
+pub struct Camera<'a>(Async<camera::Camera<'a>>);
+
+impl<'a> Camera<'a> {
+    /// Create a new instance of [`Camera`].
+    pub fn new() -> Result<Camera<'a>, Error> {
+        block_on(camera::Camera::new()).map(Self::from)
+    }
+
+    /// Create a new instance of [`Camera`] on the given connection.
+    pub fn with_connection(connection: &zbus::Connection) -> Result<Camera<'a>, Error> {
+        block_on(camera::Camera::with_connection(connection)).map(Self::from)
+    }
+
+    /// The asynchronous proxy this wraps.
+    pub fn inner(&self) -> &camera::Camera<'a> {
+        &self.0
+    }
+
+    /// Convert into the asynchronous proxy this wraps.
+    pub fn into_inner(self) -> camera::Camera<'a> {
+        self.0.into_inner()
+    }
+
+    /// Blocking version of [`camera::Camera::request_access`].
+    #[doc(alias = "AccessCamera")]
+    #[doc(alias = "xdp_portal_access_camera")]
+    pub fn request_access(&self) -> Result<Request<()>, Error> {
+        block_on(self.0.request_access()).map(Request::from)
+    }
+
+    /// Blocking version of [`camera::Camera::open_pipe_wire_remote`].
+    #[doc(alias = "OpenPipeWireRemote")]
+    #[doc(alias = "xdp_portal_open_pipewire_remote_for_camera")]
+    pub fn open_pipe_wire_remote(&self) -> Result<OwnedFd, Error> {
+        block_on(self.0.open_pipe_wire_remote())
+    }
+
+    /// Blocking version of [`camera::Camera::is_present`].
+    #[doc(alias = "IsCameraPresent")]
+    #[doc(alias = "xdp_portal_is_camera_present")]
+    pub fn is_present(&self) -> Result<bool, Error> {
+        block_on(self.0.is_present())
+    }
+}
+
+impl<'a> From<camera::Camera<'a>> for Camera<'a> {
+    fn from(proxy: camera::Camera<'a>) -> Self {
+        Self(Async::new(proxy))
+    }
+}
+
+#[cfg(feature = "pipewire")]
+#[cfg_attr(docsrs, doc(cfg(feature = "pipewire")))]
+/// Blocking version of [`camera::pipewire_streams`].
+pub fn pipewire_streams(fd: OwnedFd) -> Result<Vec<camera::Stream>, pipewire::Error> {
+    block_on(camera::pipewire_streams(fd))
+}
+
+#[cfg(not(feature = "pipewire"))]
+#[cfg_attr(docsrs, doc(cfg(not(feature = "pipewire"))))]
+/// Blocking version of [`camera::request`].
+pub fn request() -> Result<Option<OwnedFd>, Error> {
+    block_on(camera::request())
+}
+
+#[cfg(feature = "pipewire")]
+#[cfg_attr(docsrs, doc(cfg(feature = "pipewire")))]
+/// Blocking version of [`camera::request`].
+pub fn request() -> Result<Option<(OwnedFd, Vec<camera::Stream>)>, Error> {
+    block_on(camera::request())
+}
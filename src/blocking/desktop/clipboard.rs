@@ -0,0 +1,111 @@
+//! Blocking wrapper of [`crate::desktop::clipboard`].
+
+use zbus::zvariant::OwnedFd;
+
+use crate::{
+    blocking::{block_on, block_on_stream, desktop::Session, Async},
+    desktop::{clipboard, clipboard::SelectionOwnerChanged, remote_desktop::RemoteDesktop},
+    Result,
+};
+
+/// Blocking wrapper of [`clipboard::Clipboard`].
+pub struct Clipboard<'a>(Async<clipboard::Clipboard<'a>>);
+
+impl<'a> Clipboard<'a> {
+    /// Create a new instance of [`Clipboard`].
+    pub fn new() -> Result<Clipboard<'a>> {
+        block_on(clipboard::Clipboard::new()).map(Self::from)
+    }
+
+    /// Create a new instance of [`Clipboard`] on the given connection.
+    pub fn with_connection(connection: &zbus::Connection) -> Result<Clipboard<'a>> {
+        block_on(clipboard::Clipboard::with_connection(connection)).map(Self::from)
+    }
+
+    /// The asynchronous proxy this wraps.
+    pub fn inner(&self) -> &clipboard::Clipboard<'a> {
+        &self.0
+    }
+
+    /// Convert into the asynchronous proxy this wraps.
+    pub fn into_inner(self) -> clipboard::Clipboard<'a> {
+        self.0.into_inner()
+    }
+
+    /// Blocking version of [`clipboard::Clipboard::request`].
+    #[doc(alias = "RequestClipboard")]
+    pub fn request(&self, session: &Session<'_, RemoteDesktop<'_>>) -> Result<()> {
+        block_on(self.0.request(&session.0))
+    }
+
+    /// Blocking version of [`clipboard::Clipboard::set_selection`].
+    #[doc(alias = "SetSelection")]
+    pub fn set_selection(
+        &self,
+        session: &Session<'_, RemoteDesktop<'_>>,
+        mime_types: &[&str],
+    ) -> Result<()> {
+        block_on(self.0.set_selection(&session.0, mime_types))
+    }
+
+    /// Blocking version of [`clipboard::Clipboard::selection_write`].
+    #[doc(alias = "SelectionWrite")]
+    pub fn selection_write(
+        &self,
+        session: &Session<'_, RemoteDesktop<'_>>,
+        serial: u32,
+    ) -> Result<OwnedFd> {
+        block_on(self.0.selection_write(&session.0, serial))
+    }
+
+    /// Blocking version of [`clipboard::Clipboard::selection_write_done`].
+    #[doc(alias = "SelectionWriteDone")]
+    pub fn selection_write_done(
+        &self,
+        session: &Session<'_, RemoteDesktop<'_>>,
+        serial: u32,
+        success: bool,
+    ) -> Result<()> {
+        block_on(self.0.selection_write_done(&session.0, serial, success))
+    }
+
+    /// Blocking version of [`clipboard::Clipboard::selection_read`].
+    #[doc(alias = "SelectionRead")]
+    pub fn selection_read(
+        &self,
+        session: &Session<'_, RemoteDesktop<'_>>,
+        mime_type: &str,
+    ) -> Result<OwnedFd> {
+        block_on(self.0.selection_read(&session.0, mime_type))
+    }
+
+    /// Blocking version of
+    /// [`clipboard::Clipboard::receive_selection_owner_changed`].
+    #[doc(alias = "SelectionOwnerChanged")]
+    pub fn receive_selection_owner_changed(
+        &self,
+    ) -> Result<impl Iterator<Item = (Session<'_, RemoteDesktop<'_>>, SelectionOwnerChanged)> + '_>
+    {
+        block_on(self.0.receive_selection_owner_changed()).map(|stream| {
+            block_on_stream(stream).map(|(session, owner)| (Session::from(session), owner))
+        })
+    }
+
+    /// Blocking version of
+    /// [`clipboard::Clipboard::receive_selection_transfer`].
+    #[doc(alias = "SelectionTransfer")]
+    pub fn receive_selection_transfer(
+        &self,
+    ) -> Result<impl Iterator<Item = (Session<'_, RemoteDesktop<'_>>, String, u32)> + '_> {
+        block_on(self.0.receive_selection_transfer()).map(|stream| {
+            block_on_stream(stream)
+                .map(|(session, mime_type, serial)| (Session::from(session), mime_type, serial))
+        })
+    }
+}
+
+impl<'a> From<clipboard::Clipboard<'a>> for Clipboard<'a> {
+    fn from(proxy: clipboard::Clipboard<'a>) -> Self {
+        Self(Async::new(proxy))
+    }
+}
@@ -0,0 +1,124 @@
+//! Blocking wrapper of [`crate::desktop::dynamic_launcher`].
+
+use enumflags2::BitFlags;
+
+use crate::{
+    blocking::{block_on, desktop::Request, Async},
+    desktop::{
+        dynamic_launcher::{
+            self, LauncherIcon, LauncherType, PrepareInstallOptions, PrepareInstallResponse,
+        },
+        Icon,
+    },
+    Error, WindowIdentifier,
+};
+
+/// Blocking wrapper of [`dynamic_launcher::DynamicLauncherProxy`].
+#[derive(Debug)]
+pub struct DynamicLauncherProxy<'a>(Async<dynamic_launcher::DynamicLauncherProxy<'a>>);
+
+impl<'a> DynamicLauncherProxy<'a> {
+    /// Create a new instance of [`DynamicLauncherProxy`].
+    pub fn new() -> Result<DynamicLauncherProxy<'a>, Error> {
+        block_on(dynamic_launcher::DynamicLauncherProxy::new()).map(Self::from)
+    }
+
+    /// Create a new instance of [`DynamicLauncherProxy`] on the given
+    /// connection.
+    pub fn with_connection(
+        connection: &zbus::Connection,
+    ) -> Result<DynamicLauncherProxy<'a>, Error> {
+        block_on(dynamic_launcher::DynamicLauncherProxy::with_connection(
+            connection,
+        ))
+        .map(Self::from)
+    }
+
+    /// The asynchronous proxy this wraps.
+    pub fn inner(&self) -> &dynamic_launcher::DynamicLauncherProxy<'a> {
+        &self.0
+    }
+
+    /// Convert into the asynchronous proxy this wraps.
+    pub fn into_inner(self) -> dynamic_launcher::DynamicLauncherProxy<'a> {
+        self.0.into_inner()
+    }
+
+    /// Blocking version of
+    /// [`dynamic_launcher::DynamicLauncherProxy::prepare_install`].
+    #[doc(alias = "PrepareInstall")]
+    #[doc(alias = "xdp_portal_dynamic_launcher_prepare_install")]
+    #[doc(alias = "xdp_portal_dynamic_launcher_prepare_install_finish")]
+    pub fn prepare_install(
+        &self,
+        parent_window: &WindowIdentifier,
+        name: &str,
+        icon: Icon,
+        options: PrepareInstallOptions,
+    ) -> Result<Request<PrepareInstallResponse>, Error> {
+        block_on(self.0.prepare_install(parent_window, name, icon, options)).map(Request::from)
+    }
+
+    /// Blocking version of
+    /// [`dynamic_launcher::DynamicLauncherProxy::request_install_token`].
+    #[doc(alias = "RequestInstallToken")]
+    #[doc(alias = "xdp_portal_dynamic_launcher_request_install_token")]
+    pub fn request_install_token(&self, name: &str, icon: Icon) -> Result<String, Error> {
+        block_on(self.0.request_install_token(name, icon))
+    }
+
+    /// Blocking version of [`dynamic_launcher::DynamicLauncherProxy::install`].
+    #[doc(alias = "Install")]
+    #[doc(alias = "xdp_portal_dynamic_launcher_install")]
+    pub fn install(
+        &self,
+        token: &str,
+        desktop_file_id: &str,
+        desktop_entry: &str,
+    ) -> Result<(), Error> {
+        block_on(self.0.install(token, desktop_file_id, desktop_entry))
+    }
+
+    /// Blocking version of
+    /// [`dynamic_launcher::DynamicLauncherProxy::uninstall`].
+    #[doc(alias = "Uninstall")]
+    #[doc(alias = "xdp_portal_dynamic_launcher_uninstall")]
+    pub fn uninstall(&self, desktop_file_id: &str) -> Result<(), Error> {
+        block_on(self.0.uninstall(desktop_file_id))
+    }
+
+    /// Blocking version of
+    /// [`dynamic_launcher::DynamicLauncherProxy::desktop_entry`].
+    #[doc(alias = "GetDesktopEntry")]
+    #[doc(alias = "xdp_portal_dynamic_launcher_get_desktop_entry")]
+    pub fn desktop_entry(&self, desktop_file_id: &str) -> Result<String, Error> {
+        block_on(self.0.desktop_entry(desktop_file_id))
+    }
+
+    /// Blocking version of [`dynamic_launcher::DynamicLauncherProxy::icon`].
+    #[doc(alias = "GetIcon")]
+    #[doc(alias = "xdp_portal_dynamic_launcher_get_icon")]
+    pub fn icon(&self, desktop_file_id: &str) -> Result<LauncherIcon, Error> {
+        block_on(self.0.icon(desktop_file_id))
+    }
+
+    /// Blocking version of [`dynamic_launcher::DynamicLauncherProxy::launch`].
+    #[doc(alias = "Launch")]
+    #[doc(alias = "xdp_portal_dynamic_launcher_launch")]
+    pub fn launch(&self, desktop_file_id: &str) -> Result<(), Error> {
+        block_on(self.0.launch(desktop_file_id))
+    }
+
+    /// Blocking version of
+    /// [`dynamic_launcher::DynamicLauncherProxy::supported_launcher_types`].
+    #[doc(alias = "SupportedLauncherTypes")]
+    pub fn supported_launcher_types(&self) -> Result<BitFlags<LauncherType>, Error> {
+        block_on(self.0.supported_launcher_types())
+    }
+}
+
+impl<'a> From<dynamic_launcher::DynamicLauncherProxy<'a>> for DynamicLauncherProxy<'a> {
+    fn from(proxy: dynamic_launcher::DynamicLauncherProxy<'a>) -> Self {
+        Self(Async::new(proxy))
+    }
+}
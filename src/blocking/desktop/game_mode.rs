@@ -0,0 +1,107 @@
+//! Blocking wrapper of [`crate::desktop::game_mode`].
+
+use std::os::fd::BorrowedFd;
+
+use crate::{
+    blocking::{block_on, Async},
+    desktop::{game_mode, game_mode::Status},
+    Error,
+};
+
+/// Blocking wrapper of [`game_mode::GameMode`].
+#[derive(Debug)]
+pub struct GameMode<'a>(Async<game_mode::GameMode<'a>>);
+
+impl<'a> GameMode<'a> {
+    /// Create a new instance of [`GameMode`].
+    pub fn new() -> Result<GameMode<'a>, Error> {
+        block_on(game_mode::GameMode::new()).map(Self::from)
+    }
+
+    /// Create a new instance of [`GameMode`] on the given connection.
+    pub fn with_connection(connection: &zbus::Connection) -> Result<GameMode<'a>, Error> {
+        block_on(game_mode::GameMode::with_connection(connection)).map(Self::from)
+    }
+
+    /// The asynchronous proxy this wraps.
+    pub fn inner(&self) -> &game_mode::GameMode<'a> {
+        &self.0
+    }
+
+    /// Convert into the asynchronous proxy this wraps.
+    pub fn into_inner(self) -> game_mode::GameMode<'a> {
+        self.0.into_inner()
+    }
+
+    /// Blocking version of [`game_mode::GameMode::query_status`].
+    #[doc(alias = "QueryStatus")]
+    pub fn query_status(&self, pid: u32) -> Result<Status, Error> {
+        block_on(self.0.query_status(pid))
+    }
+
+    /// Blocking version of [`game_mode::GameMode::query_status_by_pidfd`].
+    #[doc(alias = "QueryStatusByPIDFd")]
+    pub fn query_status_by_pidfd(
+        &self,
+        target: &BorrowedFd<'_>,
+        requester: &BorrowedFd<'_>,
+    ) -> Result<Status, Error> {
+        block_on(self.0.query_status_by_pidfd(target, requester))
+    }
+
+    /// Blocking version of [`game_mode::GameMode::query_status_by_pid`].
+    #[doc(alias = "QueryStatusByPid")]
+    pub fn query_status_by_pid(&self, target: u32, requester: u32) -> Result<Status, Error> {
+        block_on(self.0.query_status_by_pid(target, requester))
+    }
+
+    /// Blocking version of [`game_mode::GameMode::register`].
+    #[doc(alias = "RegisterGame")]
+    pub fn register(&self, pid: u32) -> Result<(), Error> {
+        block_on(self.0.register(pid))
+    }
+
+    /// Blocking version of [`game_mode::GameMode::register_by_pidfd`].
+    #[doc(alias = "RegisterGameByPIDFd")]
+    pub fn register_by_pidfd(
+        &self,
+        target: &BorrowedFd<'_>,
+        requester: &BorrowedFd<'_>,
+    ) -> Result<(), Error> {
+        block_on(self.0.register_by_pidfd(target, requester))
+    }
+
+    /// Blocking version of [`game_mode::GameMode::register_by_pid`].
+    #[doc(alias = "RegisterGameByPid")]
+    pub fn register_by_pid(&self, target: u32, requester: u32) -> Result<(), Error> {
+        block_on(self.0.register_by_pid(target, requester))
+    }
+
+    /// Blocking version of [`game_mode::GameMode::unregister`].
+    #[doc(alias = "UnregisterGame")]
+    pub fn unregister(&self, pid: u32) -> Result<(), Error> {
+        block_on(self.0.unregister(pid))
+    }
+
+    /// Blocking version of [`game_mode::GameMode::unregister_by_pidfd`].
+    #[doc(alias = "UnregisterGameByPIDFd")]
+    pub fn unregister_by_pidfd(
+        &self,
+        target: &BorrowedFd<'_>,
+        requester: &BorrowedFd<'_>,
+    ) -> Result<(), Error> {
+        block_on(self.0.unregister_by_pidfd(target, requester))
+    }
+
+    /// Blocking version of [`game_mode::GameMode::unregister_by_pid`].
+    #[doc(alias = "UnregisterGameByPid")]
+    pub fn unregister_by_pid(&self, target: u32, requester: u32) -> Result<(), Error> {
+        block_on(self.0.unregister_by_pid(target, requester))
+    }
+}
+
+impl<'a> From<game_mode::GameMode<'a>> for GameMode<'a> {
+    fn from(proxy: game_mode::GameMode<'a>) -> Self {
+        Self(Async::new(proxy))
+    }
+}
@@ -0,0 +1,102 @@
+//! Blocking wrapper of [`crate::desktop::global_shortcuts`].
+
+use crate::{
+    blocking::{
+        block_on, block_on_stream,
+        desktop::{Request, Session},
+        Async,
+    },
+    desktop::global_shortcuts::{
+        self, Activated, BindShortcuts, Deactivated, ListShortcuts, NewShortcut, ShortcutsChanged,
+    },
+    Error, WindowIdentifier,
+};
+
+/// Blocking wrapper of [`global_shortcuts::GlobalShortcuts`].
+#[derive(Debug)]
+pub struct GlobalShortcuts<'a>(Async<global_shortcuts::GlobalShortcuts<'a>>);
+
+impl<'a> GlobalShortcuts<'a> {
+    /// Create a new instance of [`GlobalShortcuts`].
+    pub fn new() -> Result<GlobalShortcuts<'a>, Error> {
+        block_on(global_shortcuts::GlobalShortcuts::new()).map(Self::from)
+    }
+
+    /// Create a new instance of [`GlobalShortcuts`] on the given connection.
+    pub fn with_connection(connection: &zbus::Connection) -> Result<GlobalShortcuts<'a>, Error> {
+        block_on(global_shortcuts::GlobalShortcuts::with_connection(
+            connection,
+        ))
+        .map(Self::from)
+    }
+
+    /// The asynchronous proxy this wraps.
+    pub fn inner(&self) -> &global_shortcuts::GlobalShortcuts<'a> {
+        &self.0
+    }
+
+    /// Convert into the asynchronous proxy this wraps.
+    pub fn into_inner(self) -> global_shortcuts::GlobalShortcuts<'a> {
+        self.0.into_inner()
+    }
+
+    /// Blocking version of
+    /// [`global_shortcuts::GlobalShortcuts::create_session`].
+    #[doc(alias = "CreateSession")]
+    pub fn create_session(
+        &self,
+    ) -> Result<Session<'a, global_shortcuts::GlobalShortcuts<'a>>, Error> {
+        block_on(self.0.create_session()).map(Session::from)
+    }
+
+    /// Blocking version of
+    /// [`global_shortcuts::GlobalShortcuts::bind_shortcuts`].
+    #[doc(alias = "BindShortcuts")]
+    pub fn bind_shortcuts(
+        &self,
+        session: &Session<'_, global_shortcuts::GlobalShortcuts<'a>>,
+        shortcuts: &[NewShortcut],
+        parent_window: &WindowIdentifier,
+    ) -> Result<Request<BindShortcuts>, Error> {
+        block_on(self.0.bind_shortcuts(&session.0, shortcuts, parent_window)).map(Request::from)
+    }
+
+    /// Blocking version of
+    /// [`global_shortcuts::GlobalShortcuts::list_shortcuts`].
+    #[doc(alias = "ListShortcuts")]
+    pub fn list_shortcuts(
+        &self,
+        session: &Session<'_, global_shortcuts::GlobalShortcuts<'a>>,
+    ) -> Result<Request<ListShortcuts>, Error> {
+        block_on(self.0.list_shortcuts(&session.0)).map(Request::from)
+    }
+
+    /// Blocking version of
+    /// [`global_shortcuts::GlobalShortcuts::receive_activated`].
+    #[doc(alias = "Activated")]
+    pub fn receive_activated(&self) -> Result<impl Iterator<Item = Activated> + '_, Error> {
+        block_on(self.0.receive_activated()).map(block_on_stream)
+    }
+
+    /// Blocking version of
+    /// [`global_shortcuts::GlobalShortcuts::receive_deactivated`].
+    #[doc(alias = "Deactivated")]
+    pub fn receive_deactivated(&self) -> Result<impl Iterator<Item = Deactivated> + '_, Error> {
+        block_on(self.0.receive_deactivated()).map(block_on_stream)
+    }
+
+    /// Blocking version of
+    /// [`global_shortcuts::GlobalShortcuts::receive_shortcuts_changed`].
+    #[doc(alias = "ShortcutsChanged")]
+    pub fn receive_shortcuts_changed(
+        &self,
+    ) -> Result<impl Iterator<Item = ShortcutsChanged> + '_, Error> {
+        block_on(self.0.receive_shortcuts_changed()).map(block_on_stream)
+    }
+}
+
+impl<'a> From<global_shortcuts::GlobalShortcuts<'a>> for GlobalShortcuts<'a> {
+    fn from(proxy: global_shortcuts::GlobalShortcuts<'a>) -> Self {
+        Self(Async::new(proxy))
+    }
+}
@@ -0,0 +1,84 @@
+//! Blocking wrapper of [`crate::desktop::inhibit`].
+
+use enumflags2::BitFlags;
+
+use crate::{
+    blocking::{
+        block_on, block_on_stream,
+        desktop::{Request, Session},
+        Async,
+    },
+    desktop::inhibit::{self, InhibitFlags, InhibitState},
+    Error, WindowIdentifier,
+};
+
+/// Blocking wrapper of [`inhibit::InhibitProxy`].
+#[derive(Debug)]
+pub struct InhibitProxy<'a>(Async<inhibit::InhibitProxy<'a>>);
+
+impl<'a> InhibitProxy<'a> {
+    /// Create a new instance of [`InhibitProxy`].
+    pub fn new() -> Result<InhibitProxy<'a>, Error> {
+        block_on(inhibit::InhibitProxy::new()).map(Self::from)
+    }
+
+    /// Create a new instance of [`InhibitProxy`] on the given connection.
+    pub fn with_connection(connection: &zbus::Connection) -> Result<InhibitProxy<'a>, Error> {
+        block_on(inhibit::InhibitProxy::with_connection(connection)).map(Self::from)
+    }
+
+    /// The asynchronous proxy this wraps.
+    pub fn inner(&self) -> &inhibit::InhibitProxy<'a> {
+        &self.0
+    }
+
+    /// Convert into the asynchronous proxy this wraps.
+    pub fn into_inner(self) -> inhibit::InhibitProxy<'a> {
+        self.0.into_inner()
+    }
+
+    /// Blocking version of [`inhibit::InhibitProxy::create_monitor`].
+    #[doc(alias = "CreateMonitor")]
+    #[doc(alias = "xdp_portal_session_monitor_start")]
+    pub fn create_monitor(
+        &self,
+        identifier: &WindowIdentifier,
+    ) -> Result<Session<'a, inhibit::InhibitProxy<'a>>, Error> {
+        block_on(self.0.create_monitor(identifier)).map(Session::from)
+    }
+
+    /// Blocking version of [`inhibit::InhibitProxy::inhibit`].
+    #[doc(alias = "Inhibit")]
+    #[doc(alias = "xdp_portal_session_inhibit")]
+    pub fn inhibit(
+        &self,
+        identifier: &WindowIdentifier,
+        flags: BitFlags<InhibitFlags>,
+        reason: &str,
+    ) -> Result<Request<()>, Error> {
+        block_on(self.0.inhibit(identifier, flags, reason)).map(Request::from)
+    }
+
+    /// Blocking version of [`inhibit::InhibitProxy::receive_state_changed`].
+    #[doc(alias = "StateChanged")]
+    #[doc(alias = "XdpPortal::session-state-changed")]
+    pub fn receive_state_changed(&self) -> Result<impl Iterator<Item = InhibitState> + '_, Error> {
+        block_on(self.0.receive_state_changed()).map(block_on_stream)
+    }
+
+    /// Blocking version of [`inhibit::InhibitProxy::query_end_response`].
+    #[doc(alias = "QueryEndResponse")]
+    #[doc(alias = "xdp_portal_session_monitor_query_end_response")]
+    pub fn query_end_response(
+        &self,
+        session: &Session<'_, inhibit::InhibitProxy<'a>>,
+    ) -> Result<(), Error> {
+        block_on(self.0.query_end_response(&session.0))
+    }
+}
+
+impl<'a> From<inhibit::InhibitProxy<'a>> for InhibitProxy<'a> {
+    fn from(proxy: inhibit::InhibitProxy<'a>) -> Self {
+        Self(Async::new(proxy))
+    }
+}
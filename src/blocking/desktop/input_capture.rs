@@ -0,0 +1,186 @@
+//! Blocking wrapper of [`crate::desktop::input_capture`].
+
+use std::os::fd::OwnedFd;
+
+use enumflags2::BitFlags;
+
+#[cfg(feature = "eis")]
+use crate::desktop::input_capture::CapturedEvent;
+use crate::{
+    blocking::{
+        block_on, block_on_stream,
+        desktop::{Request, Session},
+        Async,
+    },
+    desktop::input_capture::{
+        self, Activated, Barrier, BarrierLayout, Capabilities, Deactivated, Disabled,
+        SetPointerBarriersResponse, Zones, ZonesChanged,
+    },
+    Error, WindowIdentifier,
+};
+
+/// Blocking wrapper of [`input_capture::InputCapture`].
+pub struct InputCapture<'a>(Async<input_capture::InputCapture<'a>>);
+
+impl<'a> InputCapture<'a> {
+    /// Create a new instance of [`InputCapture`].
+    pub fn new() -> Result<InputCapture<'a>, Error> {
+        block_on(input_capture::InputCapture::new()).map(Self::from)
+    }
+
+    /// Create a new instance of [`InputCapture`] on the given connection.
+    pub fn with_connection(connection: &zbus::Connection) -> Result<InputCapture<'a>, Error> {
+        block_on(input_capture::InputCapture::with_connection(connection)).map(Self::from)
+    }
+
+    /// The asynchronous proxy this wraps.
+    pub fn inner(&self) -> &input_capture::InputCapture<'a> {
+        &self.0
+    }
+
+    /// Convert into the asynchronous proxy this wraps.
+    pub fn into_inner(self) -> input_capture::InputCapture<'a> {
+        self.0.into_inner()
+    }
+
+    /// Blocking version of [`input_capture::InputCapture::create_session`].
+    pub fn create_session(
+        &self,
+        parent_window: &WindowIdentifier,
+        capabilities: BitFlags<Capabilities>,
+    ) -> Result<
+        (
+            Session<'_, input_capture::InputCapture<'a>>,
+            BitFlags<Capabilities>,
+        ),
+        Error,
+    > {
+        block_on(self.0.create_session(parent_window, capabilities))
+            .map(|(session, capabilities)| (Session::from(session), capabilities))
+    }
+
+    /// Blocking version of [`input_capture::InputCapture::zones`].
+    #[doc(alias = "GetZones")]
+    pub fn zones(
+        &self,
+        session: &Session<'_, input_capture::InputCapture<'a>>,
+    ) -> Result<Request<Zones>, Error> {
+        block_on(self.0.zones(&session.0)).map(Request::from)
+    }
+
+    /// Blocking version of
+    /// [`input_capture::InputCapture::set_pointer_barriers`].
+    #[doc(alias = "SetPointerBarriers")]
+    pub fn set_pointer_barriers(
+        &self,
+        session: &Session<'_, input_capture::InputCapture<'a>>,
+        barriers: &[Barrier],
+        zone_set: u32,
+    ) -> Result<Request<SetPointerBarriersResponse>, Error> {
+        block_on(self.0.set_pointer_barriers(&session.0, barriers, zone_set)).map(Request::from)
+    }
+
+    /// Blocking version of
+    /// [`input_capture::InputCapture::set_barrier_layout`].
+    pub fn set_barrier_layout(
+        &self,
+        session: &Session<'_, input_capture::InputCapture<'a>>,
+        layout: &mut BarrierLayout,
+    ) -> Result<(), Error> {
+        block_on(self.0.set_barrier_layout(&session.0, layout))
+    }
+
+    /// Blocking version of
+    /// [`input_capture::InputCapture::track_barrier_layout`].
+    pub fn track_barrier_layout<'s>(
+        &'s self,
+        session: &'s Session<'_, input_capture::InputCapture<'a>>,
+        layout: BarrierLayout,
+    ) -> Result<impl Iterator<Item = Result<BarrierLayout, Error>> + 's, Error> {
+        block_on(self.0.track_barrier_layout(&session.0, layout)).map(block_on_stream)
+    }
+
+    /// Blocking version of [`input_capture::InputCapture::enable`].
+    pub fn enable(
+        &self,
+        session: &Session<'_, input_capture::InputCapture<'a>>,
+    ) -> Result<(), Error> {
+        block_on(self.0.enable(&session.0))
+    }
+
+    /// Blocking version of [`input_capture::InputCapture::disable`].
+    pub fn disable(
+        &self,
+        session: &Session<'_, input_capture::InputCapture<'a>>,
+    ) -> Result<(), Error> {
+        block_on(self.0.disable(&session.0))
+    }
+
+    /// Blocking version of [`input_capture::InputCapture::release`].
+    pub fn release(
+        &self,
+        session: &Session<'_, input_capture::InputCapture<'a>>,
+        activation_id: u32,
+        cursor_position: (f64, f64),
+    ) -> Result<(), Error> {
+        block_on(self.0.release(&session.0, activation_id, cursor_position))
+    }
+
+    /// Blocking version of [`input_capture::InputCapture::connect_to_eis`].
+    #[doc(alias = "ConnectToEIS")]
+    pub fn connect_to_eis(
+        &self,
+        session: &Session<'_, input_capture::InputCapture<'a>>,
+    ) -> Result<OwnedFd, Error> {
+        block_on(self.0.connect_to_eis(&session.0))
+    }
+
+    /// Blocking version of [`input_capture::InputCapture::events`].
+    #[cfg(feature = "eis")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "eis")))]
+    pub fn events(
+        &self,
+        session: &Session<'_, input_capture::InputCapture<'a>>,
+    ) -> Result<impl Iterator<Item = Result<CapturedEvent, Error>> + '_, Error> {
+        block_on(self.0.events(&session.0)).map(block_on_stream)
+    }
+
+    /// Blocking version of [`input_capture::InputCapture::receive_disabled`].
+    #[doc(alias = "Disabled")]
+    pub fn receive_disabled(&self) -> Result<impl Iterator<Item = Disabled> + '_, Error> {
+        block_on(self.0.receive_disabled()).map(block_on_stream)
+    }
+
+    /// Blocking version of [`input_capture::InputCapture::receive_activated`].
+    #[doc(alias = "Activated")]
+    pub fn receive_activated(&self) -> Result<impl Iterator<Item = Activated> + '_, Error> {
+        block_on(self.0.receive_activated()).map(block_on_stream)
+    }
+
+    /// Blocking version of
+    /// [`input_capture::InputCapture::receive_deactivated`].
+    #[doc(alias = "Deactivated")]
+    pub fn receive_deactivated(&self) -> Result<impl Iterator<Item = Deactivated> + '_, Error> {
+        block_on(self.0.receive_deactivated()).map(block_on_stream)
+    }
+
+    /// Blocking version of
+    /// [`input_capture::InputCapture::receive_zones_changed`].
+    #[doc(alias = "ZonesChanged")]
+    pub fn receive_zones_changed(&self) -> Result<impl Iterator<Item = ZonesChanged> + '_, Error> {
+        block_on(self.0.receive_zones_changed()).map(block_on_stream)
+    }
+
+    /// Blocking version of
+    /// [`input_capture::InputCapture::supported_capabilities`].
+    #[doc(alias = "SupportedCapabilities")]
+    pub fn supported_capabilities(&self) -> Result<BitFlags<Capabilities>, Error> {
+        block_on(self.0.supported_capabilities())
+    }
+}
+
+impl<'a> From<input_capture::InputCapture<'a>> for InputCapture<'a> {
+    fn from(proxy: input_capture::InputCapture<'a>) -> Self {
+        Self(Async::new(proxy))
+    }
+}
@@ -0,0 +1,77 @@
+//! Blocking wrapper of [`crate::desktop::location`].
+
+use crate::{
+    blocking::{
+        block_on, block_on_stream,
+        desktop::{Request, Session},
+        Async,
+    },
+    desktop::location::{self, Accuracy, Location},
+    Error, WindowIdentifier,
+};
+
+/// Blocking wrapper of [`location::LocationProxy`].
+#[derive(Debug)]
+pub struct LocationProxy<'a>(Async<location::LocationProxy<'a>>);
+
+impl<'a> LocationProxy<'a> {
+    /// Create a new instance of [`LocationProxy`].
+    pub fn new() -> Result<LocationProxy<'a>, Error> {
+        block_on(location::LocationProxy::new()).map(Self::from)
+    }
+
+    /// Create a new instance of [`LocationProxy`] on the given connection.
+    pub fn with_connection(connection: &zbus::Connection) -> Result<LocationProxy<'a>, Error> {
+        block_on(location::LocationProxy::with_connection(connection)).map(Self::from)
+    }
+
+    /// The asynchronous proxy this wraps.
+    pub fn inner(&self) -> &location::LocationProxy<'a> {
+        &self.0
+    }
+
+    /// Convert into the asynchronous proxy this wraps.
+    pub fn into_inner(self) -> location::LocationProxy<'a> {
+        self.0.into_inner()
+    }
+
+    /// Blocking version of
+    /// [`location::LocationProxy::receive_location_updated`].
+    #[doc(alias = "LocationUpdated")]
+    #[doc(alias = "XdpPortal::location-updated")]
+    pub fn receive_location_updated(&self) -> Result<impl Iterator<Item = Location> + '_, Error> {
+        block_on(self.0.receive_location_updated()).map(block_on_stream)
+    }
+
+    /// Blocking version of [`location::LocationProxy::create_session`].
+    #[doc(alias = "CreateSession")]
+    pub fn create_session(
+        &self,
+        distance_threshold: Option<u32>,
+        time_threshold: Option<u32>,
+        accuracy: Option<Accuracy>,
+    ) -> Result<Session<'a, location::LocationProxy<'a>>, Error> {
+        block_on(
+            self.0
+                .create_session(distance_threshold, time_threshold, accuracy),
+        )
+        .map(Session::from)
+    }
+
+    /// Blocking version of [`location::LocationProxy::start`].
+    #[doc(alias = "Start")]
+    #[doc(alias = "xdp_portal_location_monitor_start")]
+    pub fn start(
+        &self,
+        session: &Session<'_, location::LocationProxy<'a>>,
+        identifier: &WindowIdentifier,
+    ) -> Result<Request<()>, Error> {
+        block_on(self.0.start(&session.0, identifier)).map(Request::from)
+    }
+}
+
+impl<'a> From<location::LocationProxy<'a>> for LocationProxy<'a> {
+    fn from(proxy: location::LocationProxy<'a>) -> Self {
+        Self(Async::new(proxy))
+    }
+}
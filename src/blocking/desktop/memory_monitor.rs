@@ -0,0 +1,46 @@
+//! Blocking wrapper of [`crate::desktop::memory_monitor`].
+
+use crate::{
+    blocking::{block_on, block_on_stream, Async},
+    desktop::memory_monitor,
+    Error,
+};
+
+/// Blocking wrapper of [`memory_monitor::MemoryMonitor`].
+#[derive(Debug)]
+pub struct MemoryMonitor<'a>(Async<memory_monitor::MemoryMonitor<'a>>);
+
+impl<'a> MemoryMonitor<'a> {
+    /// Create a new instance of [`MemoryMonitor`].
+    pub fn new() -> Result<MemoryMonitor<'a>, Error> {
+        block_on(memory_monitor::MemoryMonitor::new()).map(Self::from)
+    }
+
+    /// Create a new instance of [`MemoryMonitor`] on the given connection.
+    pub fn with_connection(connection: &zbus::Connection) -> Result<MemoryMonitor<'a>, Error> {
+        block_on(memory_monitor::MemoryMonitor::with_connection(connection)).map(Self::from)
+    }
+
+    /// The asynchronous proxy this wraps.
+    pub fn inner(&self) -> &memory_monitor::MemoryMonitor<'a> {
+        &self.0
+    }
+
+    /// Convert into the asynchronous proxy this wraps.
+    pub fn into_inner(self) -> memory_monitor::MemoryMonitor<'a> {
+        self.0.into_inner()
+    }
+
+    /// Blocking version of
+    /// [`memory_monitor::MemoryMonitor::receive_low_memory_warning`].
+    #[doc(alias = "LowMemoryWarning")]
+    pub fn receive_low_memory_warning(&self) -> Result<impl Iterator<Item = i32> + '_, Error> {
+        block_on(self.0.receive_low_memory_warning()).map(block_on_stream)
+    }
+}
+
+impl<'a> From<memory_monitor::MemoryMonitor<'a>> for MemoryMonitor<'a> {
+    fn from(proxy: memory_monitor::MemoryMonitor<'a>) -> Self {
+        Self(Async::new(proxy))
+    }
+}
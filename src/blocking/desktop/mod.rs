@@ -0,0 +1,135 @@
+//! Blocking wrappers of the [`crate::desktop`] portals.
+
+use std::{fmt::Debug, time::Duration};
+
+use serde::Deserialize;
+use zbus::zvariant::Type;
+
+use super::{block_on, block_on_stream, Async};
+use crate::{
+    desktop::{self, SessionDetails, SessionPortal},
+    Error,
+};
+
+pub mod background;
+pub mod camera;
+pub mod clipboard;
+pub mod dynamic_launcher;
+pub mod game_mode;
+pub mod global_shortcuts;
+pub mod inhibit;
+pub mod input_capture;
+pub mod location;
+pub mod memory_monitor;
+pub mod network_monitor;
+pub mod notification;
+pub mod power_profile_monitor;
+pub mod print;
+pub mod proxy_resolver;
+pub mod realtime;
+pub mod remote_desktop;
+pub mod screencast;
+pub mod secret;
+pub mod settings;
+pub mod trash;
+
+/// Blocking wrapper of [`desktop::Request`].
+#[derive(Debug)]
+#[doc(alias = "org.freedesktop.portal.Request")]
+pub struct Request<T>(Async<desktop::Request<T>>)
+where
+    T: for<'de> Deserialize<'de> + Type + Debug;
+
+impl<T> Request<T>
+where
+    T: for<'de> Deserialize<'de> + Type + Debug,
+{
+    /// See [`desktop::Request::response_timeout`].
+    #[must_use]
+    pub fn response_timeout(self, timeout: Duration) -> Self {
+        Self::from(self.into_inner().response_timeout(timeout))
+    }
+
+    /// See [`desktop::Request::close_on_drop`].
+    #[must_use]
+    pub fn close_on_drop(self, close_on_drop: bool) -> Self {
+        Self::from(self.into_inner().close_on_drop(close_on_drop))
+    }
+
+    /// Block until the portal responds.
+    ///
+    /// See [`desktop::Request::response`].
+    pub fn response(self) -> Result<T, Error> {
+        let request = self.into_inner();
+        block_on(request)
+    }
+
+    /// Blocking version of [`desktop::Request::close`].
+    #[doc(alias = "Close")]
+    pub fn close(&self) -> Result<(), Error> {
+        block_on(self.0.close())
+    }
+
+    /// Convert into the asynchronous request this wraps.
+    pub fn into_inner(self) -> desktop::Request<T> {
+        self.0.into_inner()
+    }
+}
+
+impl<T> From<desktop::Request<T>> for Request<T>
+where
+    T: for<'de> Deserialize<'de> + Type + Debug,
+{
+    fn from(request: desktop::Request<T>) -> Self {
+        Self(Async::new(request))
+    }
+}
+
+/// Blocking wrapper of [`desktop::Session`].
+///
+/// Like the asynchronous one, it is parameterised by the portal that created
+/// it, e.g. `Session<'_, ashpd::desktop::screencast::Screencast<'_>>`.
+#[derive(Debug)]
+#[doc(alias = "org.freedesktop.portal.Session")]
+pub struct Session<'a, P: SessionPortal>(Async<desktop::Session<'a, P>>);
+
+impl<'a, P: SessionPortal> Session<'a, P> {
+    /// See [`desktop::Session::close_on_drop`].
+    #[must_use]
+    pub fn close_on_drop(self, close_on_drop: bool) -> Self {
+        Self::from(self.into_inner().close_on_drop(close_on_drop))
+    }
+
+    /// Blocking version of [`desktop::Session::receive_closed`].
+    #[doc(alias = "Closed")]
+    pub fn receive_closed(&self) -> Result<impl Iterator<Item = SessionDetails> + '_, Error> {
+        block_on(self.0.receive_closed()).map(block_on_stream)
+    }
+
+    /// Blocking version of [`desktop::Session::closed`].
+    pub fn closed(&self) -> Result<SessionDetails, Error> {
+        block_on(self.0.closed())
+    }
+
+    /// Blocking version of [`desktop::Session::close`].
+    #[doc(alias = "Close")]
+    pub fn close(&self) -> Result<(), Error> {
+        block_on(self.0.close())
+    }
+
+    /// The asynchronous session this wraps.
+    pub fn inner(&self) -> &desktop::Session<'a, P> {
+        &self.0
+    }
+
+    /// Convert into the asynchronous session this wraps.
+    pub fn into_inner(self) -> desktop::Session<'a, P> {
+        self.0.into_inner()
+    }
+}
+
+impl<'a, P: SessionPortal> From<desktop::Session<'a, P>> for Session<'a, P> {
+    fn from(session: desktop::Session<'a, P>) -> Self {
+        Self(Async::new(session))
+    }
+}
@@ -0,0 +1,79 @@
+//! Blocking wrapper of [`crate::desktop::network_monitor`].
+
+use crate::{
+    blocking::{block_on, block_on_stream, Async},
+    desktop::network_monitor::{self, Connectivity, NetworkStatus},
+    Error,
+};
+
+/// Blocking wrapper of [`network_monitor::NetworkMonitor`].
+#[derive(Debug)]
+pub struct NetworkMonitor<'a>(Async<network_monitor::NetworkMonitor<'a>>);
+
+impl<'a> NetworkMonitor<'a> {
+    /// Create a new instance of [`NetworkMonitor`].
+    pub fn new() -> Result<NetworkMonitor<'a>, Error> {
+        block_on(network_monitor::NetworkMonitor::new()).map(Self::from)
+    }
+
+    /// Create a new instance of [`NetworkMonitor`] on the given connection.
+    pub fn with_connection(connection: &zbus::Connection) -> Result<NetworkMonitor<'a>, Error> {
+        block_on(network_monitor::NetworkMonitor::with_connection(connection)).map(Self::from)
+    }
+
+    /// The asynchronous proxy this wraps.
+    pub fn inner(&self) -> &network_monitor::NetworkMonitor<'a> {
+        &self.0
+    }
+
+    /// Convert into the asynchronous proxy this wraps.
+    pub fn into_inner(self) -> network_monitor::NetworkMonitor<'a> {
+        self.0.into_inner()
+    }
+
+    /// Blocking version of [`network_monitor::NetworkMonitor::can_reach`].
+    #[doc(alias = "CanReach")]
+    pub fn can_reach(&self, hostname: &str, port: u32) -> Result<bool, Error> {
+        block_on(self.0.can_reach(hostname, port))
+    }
+
+    /// Blocking version of [`network_monitor::NetworkMonitor::is_available`].
+    #[doc(alias = "GetAvailable")]
+    #[doc(alias = "get_available")]
+    pub fn is_available(&self) -> Result<bool, Error> {
+        block_on(self.0.is_available())
+    }
+
+    /// Blocking version of [`network_monitor::NetworkMonitor::connectivity`].
+    #[doc(alias = "GetConnectivity")]
+    #[doc(alias = "get_connectivity")]
+    pub fn connectivity(&self) -> Result<Connectivity, Error> {
+        block_on(self.0.connectivity())
+    }
+
+    /// Blocking version of [`network_monitor::NetworkMonitor::is_metered`].
+    #[doc(alias = "GetMetered")]
+    #[doc(alias = "get_metered")]
+    pub fn is_metered(&self) -> Result<bool, Error> {
+        block_on(self.0.is_metered())
+    }
+
+    /// Blocking version of [`network_monitor::NetworkMonitor::status`].
+    #[doc(alias = "GetStatus")]
+    #[doc(alias = "get_status")]
+    pub fn status(&self) -> Result<NetworkStatus, Error> {
+        block_on(self.0.status())
+    }
+
+    /// Blocking version of
+    /// [`network_monitor::NetworkMonitor::receive_changed`].
+    pub fn receive_changed(&self) -> Result<impl Iterator<Item = ()> + '_, Error> {
+        block_on(self.0.receive_changed()).map(block_on_stream)
+    }
+}
+
+impl<'a> From<network_monitor::NetworkMonitor<'a>> for NetworkMonitor<'a> {
+    fn from(proxy: network_monitor::NetworkMonitor<'a>) -> Self {
+        Self(Async::new(proxy))
+    }
+}
@@ -0,0 +1,63 @@
+//! Blocking wrapper of [`crate::desktop::notification`].
+
+use crate::{
+    blocking::{block_on, block_on_stream, Async},
+    desktop::notification::{self, Action, Notification},
+    Error,
+};
+
+/// Blocking wrapper of [`notification::NotificationProxy`].
+#[derive(Debug)]
+pub struct NotificationProxy<'a>(Async<notification::NotificationProxy<'a>>);
+
+impl<'a> NotificationProxy<'a> {
+    /// Create a new instance of [`NotificationProxy`].
+    pub fn new() -> Result<NotificationProxy<'a>, Error> {
+        block_on(notification::NotificationProxy::new()).map(Self::from)
+    }
+
+    /// Create a new instance of [`NotificationProxy`] on the given connection.
+    pub fn with_connection(connection: &zbus::Connection) -> Result<NotificationProxy<'a>, Error> {
+        block_on(notification::NotificationProxy::with_connection(connection)).map(Self::from)
+    }
+
+    /// The asynchronous proxy this wraps.
+    pub fn inner(&self) -> &notification::NotificationProxy<'a> {
+        &self.0
+    }
+
+    /// Convert into the asynchronous proxy this wraps.
+    pub fn into_inner(self) -> notification::NotificationProxy<'a> {
+        self.0.into_inner()
+    }
+
+    /// Blocking version of
+    /// [`notification::NotificationProxy::receive_action_invoked`].
+    #[doc(alias = "ActionInvoked")]
+    #[doc(alias = "XdpPortal::notification-action-invoked")]
+    pub fn receive_action_invoked(&self) -> Result<impl Iterator<Item = Action> + '_, Error> {
+        block_on(self.0.receive_action_invoked()).map(block_on_stream)
+    }
+
+    /// Blocking version of
+    /// [`notification::NotificationProxy::add_notification`].
+    #[doc(alias = "AddNotification")]
+    #[doc(alias = "xdp_portal_add_notification")]
+    pub fn add_notification(&self, id: &str, notification: Notification) -> Result<(), Error> {
+        block_on(self.0.add_notification(id, notification))
+    }
+
+    /// Blocking version of
+    /// [`notification::NotificationProxy::remove_notification`].
+    #[doc(alias = "RemoveNotification")]
+    #[doc(alias = "xdp_portal_remove_notification")]
+    pub fn remove_notification(&self, id: &str) -> Result<(), Error> {
+        block_on(self.0.remove_notification(id))
+    }
+}
+
+impl<'a> From<notification::NotificationProxy<'a>> for NotificationProxy<'a> {
+    fn from(proxy: notification::NotificationProxy<'a>) -> Self {
+        Self(Async::new(proxy))
+    }
+}
@@ -0,0 +1,52 @@
+//! Blocking wrapper of [`crate::desktop::power_profile_monitor`].
+
+use crate::{
+    blocking::{block_on, Async},
+    desktop::power_profile_monitor,
+    Error,
+};
+
+/// Blocking wrapper of [`power_profile_monitor::PowerProfileMonitor`].
+#[derive(Debug)]
+pub struct PowerProfileMonitor<'a>(Async<power_profile_monitor::PowerProfileMonitor<'a>>);
+
+impl<'a> PowerProfileMonitor<'a> {
+    /// Create a new instance of [`PowerProfileMonitor`].
+    pub fn new() -> Result<PowerProfileMonitor<'a>, Error> {
+        block_on(power_profile_monitor::PowerProfileMonitor::new()).map(Self::from)
+    }
+
+    /// Create a new instance of [`PowerProfileMonitor`] on the given
+    /// connection.
+    pub fn with_connection(
+        connection: &zbus::Connection,
+    ) -> Result<PowerProfileMonitor<'a>, Error> {
+        block_on(power_profile_monitor::PowerProfileMonitor::with_connection(
+            connection,
+        ))
+        .map(Self::from)
+    }
+
+    /// The asynchronous proxy this wraps.
+    pub fn inner(&self) -> &power_profile_monitor::PowerProfileMonitor<'a> {
+        &self.0
+    }
+
+    /// Convert into the asynchronous proxy this wraps.
+    pub fn into_inner(self) -> power_profile_monitor::PowerProfileMonitor<'a> {
+        self.0.into_inner()
+    }
+
+    /// Blocking version of
+    /// [`power_profile_monitor::PowerProfileMonitor::is_enabled`].
+    #[doc(alias = "power-saver-enabled")]
+    pub fn is_enabled(&self) -> Result<bool, Error> {
+        block_on(self.0.is_enabled())
+    }
+}
+
+impl<'a> From<power_profile_monitor::PowerProfileMonitor<'a>> for PowerProfileMonitor<'a> {
+    fn from(proxy: power_profile_monitor::PowerProfileMonitor<'a>) -> Self {
+        Self(Async::new(proxy))
+    }
+}
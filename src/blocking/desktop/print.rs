@@ -0,0 +1,74 @@
+//! Blocking wrapper of [`crate::desktop::print`].
+
+use std::os::fd::BorrowedFd;
+
+use crate::{
+    blocking::{block_on, desktop::Request, Async},
+    desktop::print::{self, PageSetup, PreparePrint, Settings},
+    Error, WindowIdentifier,
+};
+
+/// Blocking wrapper of [`print::PrintProxy`].
+#[derive(Debug)]
+pub struct PrintProxy<'a>(Async<print::PrintProxy<'a>>);
+
+impl<'a> PrintProxy<'a> {
+    /// Create a new instance of [`PrintProxy`].
+    pub fn new() -> Result<PrintProxy<'a>, Error> {
+        block_on(print::PrintProxy::new()).map(Self::from)
+    }
+
+    /// Create a new instance of [`PrintProxy`] on the given connection.
+    pub fn with_connection(connection: &zbus::Connection) -> Result<PrintProxy<'a>, Error> {
+        block_on(print::PrintProxy::with_connection(connection)).map(Self::from)
+    }
+
+    /// The asynchronous proxy this wraps.
+    pub fn inner(&self) -> &print::PrintProxy<'a> {
+        &self.0
+    }
+
+    /// Convert into the asynchronous proxy this wraps.
+    pub fn into_inner(self) -> print::PrintProxy<'a> {
+        self.0.into_inner()
+    }
+
+    /// Blocking version of [`print::PrintProxy::prepare_print`].
+    #[doc(alias = "PreparePrint")]
+    #[doc(alias = "xdp_portal_prepare_print")]
+    pub fn prepare_print(
+        &self,
+        identifier: &WindowIdentifier,
+        title: &str,
+        settings: Settings,
+        page_setup: PageSetup,
+        accept_label: impl Into<Option<&'a str>>,
+        modal: bool,
+    ) -> Result<Request<PreparePrint>, Error> {
+        block_on(
+            self.0
+                .prepare_print(identifier, title, settings, page_setup, accept_label, modal),
+        )
+        .map(Request::from)
+    }
+
+    /// Blocking version of [`print::PrintProxy::print`].
+    #[doc(alias = "Print")]
+    #[doc(alias = "xdp_portal_print_file")]
+    pub fn print(
+        &self,
+        identifier: &WindowIdentifier,
+        title: &str,
+        fd: &BorrowedFd<'_>,
+        token: Option<u32>,
+        modal: bool,
+    ) -> Result<Request<()>, Error> {
+        block_on(self.0.print(identifier, title, fd, token, modal)).map(Request::from)
+    }
+}
+
+impl<'a> From<print::PrintProxy<'a>> for PrintProxy<'a> {
+    fn from(proxy: print::PrintProxy<'a>) -> Self {
+        Self(Async::new(proxy))
+    }
+}
@@ -0,0 +1,45 @@
+//! Blocking wrapper of [`crate::desktop::proxy_resolver`].
+
+use crate::{
+    blocking::{block_on, Async},
+    desktop::proxy_resolver,
+    Error,
+};
+
+/// Blocking wrapper of [`proxy_resolver::ProxyResolver`].
+#[derive(Debug)]
+pub struct ProxyResolver<'a>(Async<proxy_resolver::ProxyResolver<'a>>);
+
+impl<'a> ProxyResolver<'a> {
+    /// Create a new instance of [`ProxyResolver`].
+    pub fn new() -> Result<ProxyResolver<'a>, Error> {
+        block_on(proxy_resolver::ProxyResolver::new()).map(Self::from)
+    }
+
+    /// Create a new instance of [`ProxyResolver`] on the given connection.
+    pub fn with_connection(connection: &zbus::Connection) -> Result<ProxyResolver<'a>, Error> {
+        block_on(proxy_resolver::ProxyResolver::with_connection(connection)).map(Self::from)
+    }
+
+    /// The asynchronous proxy this wraps.
+    pub fn inner(&self) -> &proxy_resolver::ProxyResolver<'a> {
+        &self.0
+    }
+
+    /// Convert into the asynchronous proxy this wraps.
+    pub fn into_inner(self) -> proxy_resolver::ProxyResolver<'a> {
+        self.0.into_inner()
+    }
+
+    /// Blocking version of [`proxy_resolver::ProxyResolver::lookup`].
+    #[doc(alias = "Lookup")]
+    pub fn lookup(&self, uri: &url::Url) -> Result<Vec<url::Url>, Error> {
+        block_on(self.0.lookup(uri))
+    }
+}
+
+impl<'a> From<proxy_resolver::ProxyResolver<'a>> for ProxyResolver<'a> {
+    fn from(proxy: proxy_resolver::ProxyResolver<'a>) -> Self {
+        Self(Async::new(proxy))
+    }
+}
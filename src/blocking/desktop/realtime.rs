@@ -0,0 +1,87 @@
+//! Blocking wrapper of [`crate::desktop::realtime`].
+
+use crate::{
+    blocking::{block_on, Async},
+    desktop::realtime,
+    Error,
+};
+
+/// Blocking wrapper of [`realtime::Realtime`].
+#[derive(Debug)]
+pub struct Realtime<'a>(Async<realtime::Realtime<'a>>);
+
+impl<'a> Realtime<'a> {
+    /// Create a new instance of [`Realtime`].
+    pub fn new() -> Result<Realtime<'a>, Error> {
+        block_on(realtime::Realtime::new()).map(Self::from)
+    }
+
+    /// Create a new instance of [`Realtime`] on the given connection.
+    pub fn with_connection(connection: &zbus::Connection) -> Result<Realtime<'a>, Error> {
+        block_on(realtime::Realtime::with_connection(connection)).map(Self::from)
+    }
+
+    /// The asynchronous proxy this wraps.
+    pub fn inner(&self) -> &realtime::Realtime<'a> {
+        &self.0
+    }
+
+    /// Convert into the asynchronous proxy this wraps.
+    pub fn into_inner(self) -> realtime::Realtime<'a> {
+        self.0.into_inner()
+    }
+
+    /// Blocking version of
+    /// [`realtime::Realtime::max_thread_realtime_with_pid`].
+    #[doc(alias = "MakeThreadRealtimeWithPID")]
+    pub fn max_thread_realtime_with_pid(
+        &self,
+        process: u64,
+        thread: u64,
+        priority: u32,
+    ) -> Result<(), Error> {
+        block_on(
+            self.0
+                .max_thread_realtime_with_pid(process, thread, priority),
+        )
+    }
+
+    /// Blocking version of
+    /// [`realtime::Realtime::max_thread_high_priority_with_pid`].
+    #[doc(alias = "MakeThreadHighPriorityWithPID")]
+    pub fn max_thread_high_priority_with_pid(
+        &self,
+        process: u64,
+        thread: u64,
+        priority: i32,
+    ) -> Result<(), Error> {
+        block_on(
+            self.0
+                .max_thread_high_priority_with_pid(process, thread, priority),
+        )
+    }
+
+    /// Blocking version of [`realtime::Realtime::max_realtime_priority`].
+    #[doc(alias = "MaxRealtimePriority")]
+    pub fn max_realtime_priority(&self) -> Result<i64, Error> {
+        block_on(self.0.max_realtime_priority())
+    }
+
+    /// Blocking version of [`realtime::Realtime::min_nice_level`].
+    #[doc(alias = "MinNiceLevel")]
+    pub fn min_nice_level(&self) -> Result<u32, Error> {
+        block_on(self.0.min_nice_level())
+    }
+
+    /// Blocking version of [`realtime::Realtime::rt_time_usec_max`].
+    #[doc(alias = "RTTimeUSecMax")]
+    pub fn rt_time_usec_max(&self) -> Result<u32, Error> {
+        block_on(self.0.rt_time_usec_max())
+    }
+}
+
+impl<'a> From<realtime::Realtime<'a>> for Realtime<'a> {
+    fn from(proxy: realtime::Realtime<'a>) -> Self {
+        Self(Async::new(proxy))
+    }
+}
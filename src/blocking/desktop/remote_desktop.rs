@@ -0,0 +1,296 @@
+//! Blocking wrapper of [`crate::desktop::remote_desktop`].
+
+use std::os::fd::OwnedFd;
+
+use enumflags2::BitFlags;
+
+use crate::{
+    blocking::{
+        block_on,
+        desktop::{Request, Session},
+        Async,
+    },
+    desktop::{
+        remote_desktop::{self, Axis, DeviceType, Gesture, Key, KeyState, SelectedDevices},
+        screencast::Stream,
+        PersistMode, RestoreTokenStore,
+    },
+    Error, WindowIdentifier,
+};
+
+/// Blocking wrapper of [`remote_desktop::RemoteDesktop`].
+#[derive(Debug)]
+pub struct RemoteDesktop<'a>(Async<remote_desktop::RemoteDesktop<'a>>);
+
+impl<'a> RemoteDesktop<'a> {
+    /// Create a new instance of [`RemoteDesktop`].
+    pub fn new() -> Result<RemoteDesktop<'a>, Error> {
+        block_on(remote_desktop::RemoteDesktop::new()).map(Self::from)
+    }
+
+    /// Create a new instance of [`RemoteDesktop`] on the given connection.
+    pub fn with_connection(connection: &zbus::Connection) -> Result<RemoteDesktop<'a>, Error> {
+        block_on(remote_desktop::RemoteDesktop::with_connection(connection)).map(Self::from)
+    }
+
+    /// The asynchronous proxy this wraps.
+    pub fn inner(&self) -> &remote_desktop::RemoteDesktop<'a> {
+        &self.0
+    }
+
+    /// Convert into the asynchronous proxy this wraps.
+    pub fn into_inner(self) -> remote_desktop::RemoteDesktop<'a> {
+        self.0.into_inner()
+    }
+
+    /// Blocking version of [`remote_desktop::RemoteDesktop::create_session`].
+    #[doc(alias = "CreateSession")]
+    #[doc(alias = "xdp_portal_create_remote_desktop_session")]
+    pub fn create_session(&self) -> Result<Session<'a, remote_desktop::RemoteDesktop<'a>>, Error> {
+        block_on(self.0.create_session()).map(Session::from)
+    }
+
+    /// Blocking version of [`remote_desktop::RemoteDesktop::select_devices`].
+    #[doc(alias = "SelectDevices")]
+    pub fn select_devices(
+        &self,
+        session: &Session<'_, remote_desktop::RemoteDesktop<'a>>,
+        types: BitFlags<DeviceType>,
+        restore_token: Option<&str>,
+        persist_mode: PersistMode,
+    ) -> Result<Request<()>, Error> {
+        block_on(
+            self.0
+                .select_devices(&session.0, types, restore_token, persist_mode),
+        )
+        .map(Request::from)
+    }
+
+    /// Blocking version of
+    /// [`remote_desktop::RemoteDesktop::select_devices_with_store`].
+    pub fn select_devices_with_store(
+        &self,
+        session: &Session<'_, remote_desktop::RemoteDesktop<'a>>,
+        types: BitFlags<DeviceType>,
+        persist_mode: PersistMode,
+        store: &impl RestoreTokenStore,
+        profile: &str,
+    ) -> Result<Request<()>, Error> {
+        block_on(
+            self.0
+                .select_devices_with_store(&session.0, types, persist_mode, store, profile),
+        )
+        .map(Request::from)
+    }
+
+    /// Blocking version of [`remote_desktop::RemoteDesktop::start`].
+    #[doc(alias = "Start")]
+    pub fn start(
+        &self,
+        session: &Session<'_, remote_desktop::RemoteDesktop<'a>>,
+        identifier: &WindowIdentifier,
+    ) -> Result<Request<SelectedDevices>, Error> {
+        block_on(self.0.start(&session.0, identifier)).map(Request::from)
+    }
+
+    /// Blocking version of [`remote_desktop::RemoteDesktop::start_with_store`].
+    pub fn start_with_store(
+        &self,
+        session: &Session<'_, remote_desktop::RemoteDesktop<'a>>,
+        identifier: &WindowIdentifier,
+        store: &impl RestoreTokenStore,
+        profile: &str,
+    ) -> Result<SelectedDevices, Error> {
+        block_on(
+            self.0
+                .start_with_store(&session.0, identifier, store, profile),
+        )
+    }
+
+    /// Blocking version of
+    /// [`remote_desktop::RemoteDesktop::notify_keyboard_keycode`].
+    #[doc(alias = "NotifyKeyboardKeycode")]
+    pub fn notify_keyboard_keycode(
+        &self,
+        session: &Session<'_, remote_desktop::RemoteDesktop<'a>>,
+        keycode: i32,
+        state: KeyState,
+    ) -> Result<(), Error> {
+        block_on(self.0.notify_keyboard_keycode(&session.0, keycode, state))
+    }
+
+    /// Blocking version of
+    /// [`remote_desktop::RemoteDesktop::notify_keyboard_keysym`].
+    #[doc(alias = "NotifyKeyboardKeysym")]
+    pub fn notify_keyboard_keysym(
+        &self,
+        session: &Session<'_, remote_desktop::RemoteDesktop<'a>>,
+        keysym: i32,
+        state: KeyState,
+    ) -> Result<(), Error> {
+        block_on(self.0.notify_keyboard_keysym(&session.0, keysym, state))
+    }
+
+    /// Blocking version of [`remote_desktop::RemoteDesktop::type_text`].
+    pub fn type_text(
+        &self,
+        session: &Session<'_, remote_desktop::RemoteDesktop<'a>>,
+        text: &str,
+    ) -> Result<(), Error> {
+        block_on(self.0.type_text(&session.0, text))
+    }
+
+    /// Blocking version of
+    /// [`remote_desktop::RemoteDesktop::type_text_with_dead_keys`].
+    pub fn type_text_with_dead_keys(
+        &self,
+        session: &Session<'_, remote_desktop::RemoteDesktop<'a>>,
+        text: &str,
+    ) -> Result<(), Error> {
+        block_on(self.0.type_text_with_dead_keys(&session.0, text))
+    }
+
+    /// Blocking version of [`remote_desktop::RemoteDesktop::press_combo`].
+    pub fn press_combo(
+        &self,
+        session: &Session<'_, remote_desktop::RemoteDesktop<'a>>,
+        keys: &[Key],
+    ) -> Result<(), Error> {
+        block_on(self.0.press_combo(&session.0, keys))
+    }
+
+    /// Blocking version of [`remote_desktop::RemoteDesktop::notify_touch_up`].
+    #[doc(alias = "NotifyTouchUp")]
+    pub fn notify_touch_up(
+        &self,
+        session: &Session<'_, remote_desktop::RemoteDesktop<'a>>,
+        slot: u32,
+    ) -> Result<(), Error> {
+        block_on(self.0.notify_touch_up(&session.0, slot))
+    }
+
+    /// Blocking version of
+    /// [`remote_desktop::RemoteDesktop::notify_touch_down`].
+    #[doc(alias = "NotifyTouchDown")]
+    pub fn notify_touch_down(
+        &self,
+        session: &Session<'_, remote_desktop::RemoteDesktop<'a>>,
+        stream: u32,
+        slot: u32,
+        x: f64,
+        y: f64,
+    ) -> Result<(), Error> {
+        block_on(self.0.notify_touch_down(&session.0, stream, slot, x, y))
+    }
+
+    /// Blocking version of
+    /// [`remote_desktop::RemoteDesktop::notify_touch_motion`].
+    #[doc(alias = "NotifyTouchMotion")]
+    pub fn notify_touch_motion(
+        &self,
+        session: &Session<'_, remote_desktop::RemoteDesktop<'a>>,
+        stream: u32,
+        slot: u32,
+        x: f64,
+        y: f64,
+    ) -> Result<(), Error> {
+        block_on(self.0.notify_touch_motion(&session.0, stream, slot, x, y))
+    }
+
+    /// Blocking version of [`remote_desktop::RemoteDesktop::perform_gesture`].
+    pub fn perform_gesture(
+        &self,
+        session: &Session<'_, remote_desktop::RemoteDesktop<'a>>,
+        stream: &Stream,
+        gesture: &Gesture,
+    ) -> Result<(), Error> {
+        block_on(self.0.perform_gesture(&session.0, stream, gesture))
+    }
+
+    /// Blocking version of
+    /// [`remote_desktop::RemoteDesktop::notify_pointer_motion_absolute`].
+    #[doc(alias = "NotifyPointerMotionAbsolute")]
+    pub fn notify_pointer_motion_absolute(
+        &self,
+        session: &Session<'_, remote_desktop::RemoteDesktop<'a>>,
+        stream: u32,
+        x: f64,
+        y: f64,
+    ) -> Result<(), Error> {
+        block_on(
+            self.0
+                .notify_pointer_motion_absolute(&session.0, stream, x, y),
+        )
+    }
+
+    /// Blocking version of
+    /// [`remote_desktop::RemoteDesktop::notify_pointer_motion`].
+    #[doc(alias = "NotifyPointerMotion")]
+    pub fn notify_pointer_motion(
+        &self,
+        session: &Session<'_, remote_desktop::RemoteDesktop<'a>>,
+        dx: f64,
+        dy: f64,
+    ) -> Result<(), Error> {
+        block_on(self.0.notify_pointer_motion(&session.0, dx, dy))
+    }
+
+    /// Blocking version of
+    /// [`remote_desktop::RemoteDesktop::notify_pointer_button`].
+    #[doc(alias = "NotifyPointerButton")]
+    pub fn notify_pointer_button(
+        &self,
+        session: &Session<'_, remote_desktop::RemoteDesktop<'a>>,
+        button: i32,
+        state: KeyState,
+    ) -> Result<(), Error> {
+        block_on(self.0.notify_pointer_button(&session.0, button, state))
+    }
+
+    /// Blocking version of
+    /// [`remote_desktop::RemoteDesktop::notify_pointer_axis_discrete`].
+    #[doc(alias = "NotifyPointerAxisDiscrete")]
+    pub fn notify_pointer_axis_discrete(
+        &self,
+        session: &Session<'_, remote_desktop::RemoteDesktop<'a>>,
+        axis: Axis,
+        steps: i32,
+    ) -> Result<(), Error> {
+        block_on(self.0.notify_pointer_axis_discrete(&session.0, axis, steps))
+    }
+
+    /// Blocking version of
+    /// [`remote_desktop::RemoteDesktop::notify_pointer_axis`].
+    #[doc(alias = "NotifyPointerAxis")]
+    pub fn notify_pointer_axis(
+        &self,
+        session: &Session<'_, remote_desktop::RemoteDesktop<'a>>,
+        dx: f64,
+        dy: f64,
+        finish: bool,
+    ) -> Result<(), Error> {
+        block_on(self.0.notify_pointer_axis(&session.0, dx, dy, finish))
+    }
+
+    /// Blocking version of [`remote_desktop::RemoteDesktop::connect_to_eis`].
+    #[doc(alias = "ConnectToEIS")]
+    pub fn connect_to_eis(
+        &self,
+        session: &Session<'_, remote_desktop::RemoteDesktop<'a>>,
+    ) -> Result<OwnedFd, Error> {
+        block_on(self.0.connect_to_eis(&session.0))
+    }
+
+    /// Blocking version of
+    /// [`remote_desktop::RemoteDesktop::available_device_types`].
+    #[doc(alias = "AvailableDeviceTypes")]
+    pub fn available_device_types(&self) -> Result<BitFlags<DeviceType>, Error> {
+        block_on(self.0.available_device_types())
+    }
+}
+
+impl<'a> From<remote_desktop::RemoteDesktop<'a>> for RemoteDesktop<'a> {
+    fn from(proxy: remote_desktop::RemoteDesktop<'a>) -> Self {
+        Self(Async::new(proxy))
+    }
+}
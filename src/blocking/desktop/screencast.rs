@@ -0,0 +1,162 @@
+//! Blocking wrapper of [`crate::desktop::screencast`].
+
+use std::os::fd::OwnedFd;
+
+use enumflags2::BitFlags;
+
+#[cfg(feature = "pipewire")]
+use crate::blocking::block_on_stream;
+use crate::{
+    blocking::{
+        block_on,
+        desktop::{Request, Session},
+        Async,
+    },
+    desktop::{
+        screencast::{self, CursorMode, SourceType, Streams},
+        HasScreencast, PersistMode, RestoreTokenStore,
+    },
+    Error, WindowIdentifier,
+};
+
+/// Blocking wrapper of [`screencast::Screencast`].
+#[derive(Debug)]
+pub struct Screencast<'a>(Async<screencast::Screencast<'a>>);
+
+impl<'a> Screencast<'a> {
+    /// Create a new instance of [`Screencast`].
+    pub fn new() -> Result<Screencast<'a>, Error> {
+        block_on(screencast::Screencast::new()).map(Self::from)
+    }
+
+    /// Create a new instance of [`Screencast`] on the given connection.
+    pub fn with_connection(connection: &zbus::Connection) -> Result<Screencast<'a>, Error> {
+        block_on(screencast::Screencast::with_connection(connection)).map(Self::from)
+    }
+
+    /// The asynchronous proxy this wraps.
+    pub fn inner(&self) -> &screencast::Screencast<'a> {
+        &self.0
+    }
+
+    /// Convert into the asynchronous proxy this wraps.
+    pub fn into_inner(self) -> screencast::Screencast<'a> {
+        self.0.into_inner()
+    }
+
+    /// Blocking version of [`screencast::Screencast::create_session`].
+    #[doc(alias = "CreateSession")]
+    #[doc(alias = "xdp_portal_create_screencast_session")]
+    pub fn create_session(&self) -> Result<Session<'a, screencast::Screencast<'a>>, Error> {
+        block_on(self.0.create_session()).map(Session::from)
+    }
+
+    /// Blocking version of [`screencast::Screencast::open_pipe_wire_remote`].
+    #[doc(alias = "OpenPipeWireRemote")]
+    pub fn open_pipe_wire_remote(
+        &self,
+        session: &Session<'_, impl HasScreencast>,
+    ) -> Result<OwnedFd, Error> {
+        block_on(self.0.open_pipe_wire_remote(&session.0))
+    }
+
+    /// Blocking version of [`screencast::Screencast::select_sources`].
+    #[doc(alias = "SelectSources")]
+    pub fn select_sources(
+        &self,
+        session: &Session<'_, impl HasScreencast>,
+        cursor_mode: CursorMode,
+        types: BitFlags<SourceType>,
+        multiple: bool,
+        restore_token: Option<&str>,
+        persist_mode: PersistMode,
+    ) -> Result<Request<()>, Error> {
+        block_on(self.0.select_sources(
+            &session.0,
+            cursor_mode,
+            types,
+            multiple,
+            restore_token,
+            persist_mode,
+        ))
+        .map(Request::from)
+    }
+
+    /// Blocking version of
+    /// [`screencast::Screencast::select_sources_with_store`].
+    #[allow(clippy::too_many_arguments)]
+    pub fn select_sources_with_store(
+        &self,
+        session: &Session<'_, impl HasScreencast>,
+        cursor_mode: CursorMode,
+        types: BitFlags<SourceType>,
+        multiple: bool,
+        persist_mode: PersistMode,
+        store: &impl RestoreTokenStore,
+        profile: &str,
+    ) -> Result<Request<()>, Error> {
+        block_on(self.0.select_sources_with_store(
+            &session.0,
+            cursor_mode,
+            types,
+            multiple,
+            persist_mode,
+            store,
+            profile,
+        ))
+        .map(Request::from)
+    }
+
+    /// Blocking version of [`screencast::Screencast::start`].
+    #[doc(alias = "Start")]
+    pub fn start(
+        &self,
+        session: &Session<'_, screencast::Screencast<'a>>,
+        identifier: &WindowIdentifier,
+    ) -> Result<Request<Streams>, Error> {
+        block_on(self.0.start(&session.0, identifier)).map(Request::from)
+    }
+
+    /// Blocking version of [`screencast::Screencast::start_with_store`].
+    pub fn start_with_store(
+        &self,
+        session: &Session<'_, screencast::Screencast<'a>>,
+        identifier: &WindowIdentifier,
+        store: &impl RestoreTokenStore,
+        profile: &str,
+    ) -> Result<Streams, Error> {
+        block_on(
+            self.0
+                .start_with_store(&session.0, identifier, store, profile),
+        )
+    }
+
+    /// Blocking version of [`screencast::Screencast::available_cursor_modes`].
+    #[doc(alias = "AvailableCursorModes")]
+    pub fn available_cursor_modes(&self) -> Result<BitFlags<CursorMode>, Error> {
+        block_on(self.0.available_cursor_modes())
+    }
+
+    /// Blocking version of [`screencast::Screencast::available_source_types`].
+    #[doc(alias = "AvailableSourceTypes")]
+    pub fn available_source_types(&self) -> Result<BitFlags<SourceType>, Error> {
+        block_on(self.0.available_source_types())
+    }
+}
+
+impl<'a> From<screencast::Screencast<'a>> for Screencast<'a> {
+    fn from(proxy: screencast::Screencast<'a>) -> Self {
+        Self(Async::new(proxy))
+    }
+}
+
+#[cfg(feature = "pipewire")]
+#[cfg_attr(docsrs, doc(cfg(feature = "pipewire")))]
+/// Blocking version of [`screencast::FrameStream::new`], returning an
+/// iterator blocking until the next frame is received.
+pub fn frame_stream(
+    fd: OwnedFd,
+    node_id: u32,
+) -> Result<impl Iterator<Item = screencast::Frame>, pipewire::Error> {
+    block_on(screencast::FrameStream::new(fd, node_id)).map(block_on_stream)
+}
@@ -0,0 +1,52 @@
+//! Blocking wrapper of [`crate::desktop::secret`].
+
+use std::os::fd::BorrowedFd;
+
+use crate::{
+    blocking::{block_on, desktop::Request, Async},
+    desktop::secret,
+    Error,
+};
+
+/// Blocking wrapper of [`secret::Secret`].
+#[derive(Debug)]
+pub struct Secret<'a>(Async<secret::Secret<'a>>);
+
+impl<'a> Secret<'a> {
+    /// Create a new instance of [`Secret`].
+    pub fn new() -> Result<Secret<'a>, Error> {
+        block_on(secret::Secret::new()).map(Self::from)
+    }
+
+    /// Create a new instance of [`Secret`] on the given connection.
+    pub fn with_connection(connection: &zbus::Connection) -> Result<Secret<'a>, Error> {
+        block_on(secret::Secret::with_connection(connection)).map(Self::from)
+    }
+
+    /// The asynchronous proxy this wraps.
+    pub fn inner(&self) -> &secret::Secret<'a> {
+        &self.0
+    }
+
+    /// Convert into the asynchronous proxy this wraps.
+    pub fn into_inner(self) -> secret::Secret<'a> {
+        self.0.into_inner()
+    }
+
+    /// Blocking version of [`secret::Secret::retrieve`].
+    #[doc(alias = "RetrieveSecret")]
+    pub fn retrieve(&self, fd: &BorrowedFd<'_>) -> Result<Request<()>, Error> {
+        block_on(self.0.retrieve(fd)).map(Request::from)
+    }
+}
+
+impl<'a> From<secret::Secret<'a>> for Secret<'a> {
+    fn from(proxy: secret::Secret<'a>) -> Self {
+        Self(Async::new(proxy))
+    }
+}
+
+/// Blocking version of [`secret::retrieve`].
+pub fn retrieve() -> Result<Vec<u8>, Error> {
+    block_on(secret::retrieve())
+}
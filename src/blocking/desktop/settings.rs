@@ -0,0 +1,121 @@
+//! Blocking wrapper of [`crate::desktop::settings`].
+
+use std::{collections::HashMap, fmt::Debug};
+
+use serde::Serialize;
+use zbus::zvariant::{OwnedValue, Type};
+
+use crate::{
+    blocking::{block_on, block_on_stream, Async},
+    desktop::{
+        settings::{self, ColorScheme, Contrast, Namespace, Setting},
+        Color,
+    },
+    Error,
+};
+
+/// Blocking wrapper of [`settings::Settings`].
+#[derive(Debug)]
+pub struct Settings<'a>(Async<settings::Settings<'a>>);
+
+impl<'a> Settings<'a> {
+    /// Create a new instance of [`Settings`].
+    pub fn new() -> Result<Settings<'a>, Error> {
+        block_on(settings::Settings::new()).map(Self::from)
+    }
+
+    /// Create a new instance of [`Settings`] on the given connection.
+    pub fn with_connection(connection: &zbus::Connection) -> Result<Settings<'a>, Error> {
+        block_on(settings::Settings::with_connection(connection)).map(Self::from)
+    }
+
+    /// The asynchronous proxy this wraps.
+    pub fn inner(&self) -> &settings::Settings<'a> {
+        &self.0
+    }
+
+    /// Convert into the asynchronous proxy this wraps.
+    pub fn into_inner(self) -> settings::Settings<'a> {
+        self.0.into_inner()
+    }
+
+    /// Blocking version of [`settings::Settings::read_all`].
+    #[doc(alias = "ReadAll")]
+    pub fn read_all(
+        &self,
+        namespaces: &[impl AsRef<str> + Type + Serialize + Debug],
+    ) -> Result<HashMap<String, Namespace>, Error> {
+        block_on(self.0.read_all(namespaces))
+    }
+
+    /// Blocking version of [`settings::Settings::read`].
+    #[doc(alias = "Read")]
+    #[doc(alias = "ReadOne")]
+    pub fn read<T>(&self, namespace: &str, key: &str) -> Result<T, Error>
+    where
+        T: TryFrom<OwnedValue>,
+        Error: From<<T as TryFrom<OwnedValue>>::Error>,
+    {
+        block_on(self.0.read(namespace, key))
+    }
+
+    /// Blocking version of [`settings::Settings::accent_color`].
+    pub fn accent_color(&self) -> Result<Color, Error> {
+        block_on(self.0.accent_color())
+    }
+
+    /// Blocking version of [`settings::Settings::color_scheme`].
+    pub fn color_scheme(&self) -> Result<ColorScheme, Error> {
+        block_on(self.0.color_scheme())
+    }
+
+    /// Blocking version of [`settings::Settings::contrast`].
+    pub fn contrast(&self) -> Result<Contrast, Error> {
+        block_on(self.0.contrast())
+    }
+
+    /// Blocking version of
+    /// [`settings::Settings::receive_color_scheme_changed`].
+    pub fn receive_color_scheme_changed(
+        &self,
+    ) -> Result<impl Iterator<Item = ColorScheme> + '_, Error> {
+        block_on(self.0.receive_color_scheme_changed()).map(block_on_stream)
+    }
+
+    /// Blocking version of
+    /// [`settings::Settings::receive_accent_color_changed`].
+    pub fn receive_accent_color_changed(&self) -> Result<impl Iterator<Item = Color> + '_, Error> {
+        block_on(self.0.receive_accent_color_changed()).map(block_on_stream)
+    }
+
+    /// Blocking version of [`settings::Settings::receive_contrast_changed`].
+    pub fn receive_contrast_changed(&self) -> Result<impl Iterator<Item = Contrast> + '_, Error> {
+        block_on(self.0.receive_contrast_changed()).map(block_on_stream)
+    }
+
+    /// Blocking version of [`settings::Settings::receive_setting_changed`].
+    #[doc(alias = "SettingChanged")]
+    pub fn receive_setting_changed(&self) -> Result<impl Iterator<Item = Setting> + '_, Error> {
+        block_on(self.0.receive_setting_changed()).map(block_on_stream)
+    }
+
+    /// Blocking version of
+    /// [`settings::Settings::receive_setting_changed_with_args`].
+    pub fn receive_setting_changed_with_args<'s, T>(
+        &'s self,
+        namespace: &'s str,
+        key: &'s str,
+    ) -> Result<impl Iterator<Item = Result<T, Error>> + 's, Error>
+    where
+        T: TryFrom<OwnedValue> + 's,
+        Error: From<<T as TryFrom<OwnedValue>>::Error>,
+    {
+        block_on(self.0.receive_setting_changed_with_args(namespace, key)).map(block_on_stream)
+    }
+}
+
+impl<'a> From<settings::Settings<'a>> for Settings<'a> {
+    fn from(proxy: settings::Settings<'a>) -> Self {
+        Self(Async::new(proxy))
+    }
+}
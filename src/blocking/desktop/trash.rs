@@ -0,0 +1,53 @@
+//! Blocking wrapper of [`crate::desktop::trash`].
+
+use std::os::fd::BorrowedFd;
+
+use crate::{
+    blocking::{block_on, Async},
+    desktop::trash,
+    Error,
+};
+
+/// Blocking wrapper of [`trash::TrashProxy`].
+#[derive(Debug)]
+pub struct TrashProxy<'a>(Async<trash::TrashProxy<'a>>);
+
+impl<'a> TrashProxy<'a> {
+    /// Create a new instance of [`TrashProxy`].
+    pub fn new() -> Result<TrashProxy<'a>, Error> {
+        block_on(trash::TrashProxy::new()).map(Self::from)
+    }
+
+    /// Create a new instance of [`TrashProxy`] on the given connection.
+    pub fn with_connection(connection: &zbus::Connection) -> Result<TrashProxy<'a>, Error> {
+        block_on(trash::TrashProxy::with_connection(connection)).map(Self::from)
+    }
+
+    /// The asynchronous proxy this wraps.
+    pub fn inner(&self) -> &trash::TrashProxy<'a> {
+        &self.0
+    }
+
+    /// Convert into the asynchronous proxy this wraps.
+    pub fn into_inner(self) -> trash::TrashProxy<'a> {
+        self.0.into_inner()
+    }
+
+    /// Blocking version of [`trash::TrashProxy::trash_file`].
+    #[doc(alias = "TrashFile")]
+    #[doc(alias = "xdp_portal_trash_file")]
+    pub fn trash_file(&self, fd: &BorrowedFd<'_>) -> Result<(), Error> {
+        block_on(self.0.trash_file(fd))
+    }
+}
+
+impl<'a> From<trash::TrashProxy<'a>> for TrashProxy<'a> {
+    fn from(proxy: trash::TrashProxy<'a>) -> Self {
+        Self(Async::new(proxy))
+    }
+}
+
+/// Blocking version of [`trash::trash_file`].
+pub fn trash_file(fd: &BorrowedFd<'_>) -> Result<(), Error> {
+    block_on(trash::trash_file(fd))
+}
@@ -0,0 +1,67 @@
+use std::os::fd::BorrowedFd;
+
+use crate::{
+    blocking::{block_on, block_on_stream, Async},
+    documents, Error,
+};
+
+/// Blocking wrapper of [`documents::FileTransfer`].
+#[derive(Debug)]
+pub struct FileTransfer<'a>(Async<documents::FileTransfer<'a>>);
+
+impl<'a> FileTransfer<'a> {
+    /// Create a new instance of [`FileTransfer`].
+    pub fn new() -> Result<FileTransfer<'a>, Error> {
+        block_on(documents::FileTransfer::new()).map(Self::from)
+    }
+
+    /// Create a new instance of [`FileTransfer`] on the given connection.
+    pub fn with_connection(connection: &zbus::Connection) -> Result<FileTransfer<'a>, Error> {
+        block_on(documents::FileTransfer::with_connection(connection)).map(Self::from)
+    }
+
+    /// The asynchronous proxy this wraps.
+    pub fn inner(&self) -> &documents::FileTransfer<'a> {
+        &self.0
+    }
+
+    /// Convert into the asynchronous proxy this wraps.
+    pub fn into_inner(self) -> documents::FileTransfer<'a> {
+        self.0.into_inner()
+    }
+
+    /// Blocking version of [`documents::FileTransfer::add_files`].
+    #[doc(alias = "AddFiles")]
+    pub fn add_files(&self, key: &str, fds: &[&BorrowedFd<'_>]) -> Result<(), Error> {
+        block_on(self.0.add_files(key, fds))
+    }
+
+    /// Blocking version of [`documents::FileTransfer::retrieve_files`].
+    #[doc(alias = "RetrieveFiles")]
+    pub fn retrieve_files(&self, key: &str) -> Result<Vec<String>, Error> {
+        block_on(self.0.retrieve_files(key))
+    }
+
+    /// Blocking version of [`documents::FileTransfer::start_transfer`].
+    pub fn start_transfer(&self, writeable: bool, auto_stop: bool) -> Result<String, Error> {
+        block_on(self.0.start_transfer(writeable, auto_stop))
+    }
+
+    /// Blocking version of [`documents::FileTransfer::stop_transfer`].
+    #[doc(alias = "StopTransfer")]
+    pub fn stop_transfer(&self, key: &str) -> Result<(), Error> {
+        block_on(self.0.stop_transfer(key))
+    }
+
+    /// Blocking version of [`documents::FileTransfer::transfer_closed`].
+    #[doc(alias = "TransferClosed")]
+    pub fn transfer_closed(&self) -> Result<impl Iterator<Item = String> + '_, Error> {
+        block_on(self.0.transfer_closed()).map(block_on_stream)
+    }
+}
+
+impl<'a> From<documents::FileTransfer<'a>> for FileTransfer<'a> {
+    fn from(proxy: documents::FileTransfer<'a>) -> Self {
+        Self(Async::new(proxy))
+    }
+}
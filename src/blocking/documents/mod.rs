@@ -0,0 +1,163 @@
+//! Blocking wrappers of the [`crate::documents`] portals.
+
+use std::{collections::HashMap, os::fd::BorrowedFd, path::Path};
+
+use enumflags2::BitFlags;
+use zbus::zvariant::OwnedValue;
+
+use crate::{
+    blocking::{block_on, Async},
+    documents::{self, DocumentFlags, DocumentID, Permission, Permissions},
+    AppID, Error, FilePath,
+};
+
+/// Blocking wrapper of [`documents::Documents`].
+#[derive(Debug)]
+pub struct Documents<'a>(Async<documents::Documents<'a>>);
+
+impl<'a> Documents<'a> {
+    /// Create a new instance of [`Documents`].
+    pub fn new() -> Result<Documents<'a>, Error> {
+        block_on(documents::Documents::new()).map(Self::from)
+    }
+
+    /// Create a new instance of [`Documents`] on the given connection.
+    pub fn with_connection(connection: &zbus::Connection) -> Result<Documents<'a>, Error> {
+        block_on(documents::Documents::with_connection(connection)).map(Self::from)
+    }
+
+    /// The asynchronous proxy this wraps.
+    pub fn inner(&self) -> &documents::Documents<'a> {
+        &self.0
+    }
+
+    /// Convert into the asynchronous proxy this wraps.
+    pub fn into_inner(self) -> documents::Documents<'a> {
+        self.0.into_inner()
+    }
+
+    /// Blocking version of [`documents::Documents::add`].
+    #[doc(alias = "Add")]
+    pub fn add(
+        &self,
+        o_path_fd: &BorrowedFd<'_>,
+        reuse_existing: bool,
+        persistent: bool,
+    ) -> Result<DocumentID, Error> {
+        block_on(self.0.add(o_path_fd, reuse_existing, persistent))
+    }
+
+    /// Blocking version of [`documents::Documents::add_full`].
+    #[doc(alias = "AddFull")]
+    pub fn add_full(
+        &self,
+        o_path_fds: &[&BorrowedFd<'_>],
+        flags: BitFlags<DocumentFlags>,
+        app_id: Option<&AppID>,
+        permissions: &[Permission],
+    ) -> Result<(Vec<DocumentID>, HashMap<String, OwnedValue>), Error> {
+        block_on(self.0.add_full(o_path_fds, flags, app_id, permissions))
+    }
+
+    /// Blocking version of [`documents::Documents::add_named`].
+    #[doc(alias = "AddNamed")]
+    pub fn add_named(
+        &self,
+        o_path_parent_fd: &BorrowedFd<'_>,
+        filename: impl AsRef<Path>,
+        reuse_existing: bool,
+        persistent: bool,
+    ) -> Result<DocumentID, Error> {
+        block_on(
+            self.0
+                .add_named(o_path_parent_fd, filename, reuse_existing, persistent),
+        )
+    }
+
+    /// Blocking version of [`documents::Documents::add_named_full`].
+    #[doc(alias = "AddNamedFull")]
+    pub fn add_named_full(
+        &self,
+        o_path_fd: &BorrowedFd<'_>,
+        filename: impl AsRef<Path>,
+        flags: BitFlags<DocumentFlags>,
+        app_id: Option<&AppID>,
+        permissions: &[Permission],
+    ) -> Result<(DocumentID, HashMap<String, OwnedValue>), Error> {
+        block_on(
+            self.0
+                .add_named_full(o_path_fd, filename, flags, app_id, permissions),
+        )
+    }
+
+    /// Blocking version of [`documents::Documents::delete`].
+    #[doc(alias = "Delete")]
+    pub fn delete(&self, doc_id: impl Into<DocumentID>) -> Result<(), Error> {
+        block_on(self.0.delete(doc_id))
+    }
+
+    /// Blocking version of [`documents::Documents::mount_point`].
+    #[doc(alias = "GetMountPoint")]
+    #[doc(alias = "get_mount_point")]
+    pub fn mount_point(&self) -> Result<FilePath, Error> {
+        block_on(self.0.mount_point())
+    }
+
+    /// Blocking version of [`documents::Documents::grant_permissions`].
+    #[doc(alias = "GrantPermissions")]
+    pub fn grant_permissions(
+        &self,
+        doc_id: impl Into<DocumentID>,
+        app_id: &AppID,
+        permissions: &[Permission],
+    ) -> Result<(), Error> {
+        block_on(self.0.grant_permissions(doc_id, app_id, permissions))
+    }
+
+    /// Blocking version of [`documents::Documents::info`].
+    #[doc(alias = "Info")]
+    pub fn info(&self, doc_id: impl Into<DocumentID>) -> Result<(FilePath, Permissions), Error> {
+        block_on(self.0.info(doc_id))
+    }
+
+    /// Blocking version of [`documents::Documents::list`].
+    #[doc(alias = "List")]
+    pub fn list(&self, app_id: Option<&AppID>) -> Result<HashMap<DocumentID, FilePath>, Error> {
+        block_on(self.0.list(app_id))
+    }
+
+    /// Blocking version of [`documents::Documents::lookup`].
+    #[doc(alias = "Lookup")]
+    pub fn lookup(&self, filename: impl AsRef<Path>) -> Result<Option<DocumentID>, Error> {
+        block_on(self.0.lookup(filename))
+    }
+
+    /// Blocking version of [`documents::Documents::revoke_permissions`].
+    #[doc(alias = "RevokePermissions")]
+    pub fn revoke_permissions(
+        &self,
+        doc_id: impl Into<DocumentID>,
+        app_id: &AppID,
+        permissions: &[Permission],
+    ) -> Result<(), Error> {
+        block_on(self.0.revoke_permissions(doc_id, app_id, permissions))
+    }
+
+    /// Blocking version of [`documents::Documents::host_paths`].
+    #[doc(alias = "GetHostPaths")]
+    pub fn host_paths(
+        &self,
+        doc_ids: &[DocumentID],
+    ) -> Result<HashMap<DocumentID, FilePath>, Error> {
+        block_on(self.0.host_paths(doc_ids))
+    }
+}
+
+impl<'a> From<documents::Documents<'a>> for Documents<'a> {
+    fn from(proxy: documents::Documents<'a>) -> Self {
+        Self(Async::new(proxy))
+    }
+}
+
+mod file_transfer;
+pub use file_transfer::FileTransfer;
@@ -0,0 +1,71 @@
+use std::{collections::HashMap, os::fd::BorrowedFd, path::Path};
+
+use enumflags2::BitFlags;
+
+use crate::{
+    blocking::{block_on, block_on_stream, Async},
+    flatpak::{self, HostCommandFlags},
+    Error,
+};
+
+/// Blocking wrapper of [`flatpak::Development`].
+#[derive(Debug)]
+pub struct Development<'a>(Async<flatpak::Development<'a>>);
+
+impl<'a> Development<'a> {
+    /// Create a new instance of [`Development`].
+    pub fn new() -> Result<Development<'a>, Error> {
+        block_on(flatpak::Development::new()).map(Self::from)
+    }
+
+    /// Create a new instance of [`Development`] on the given connection.
+    pub fn with_connection(connection: &zbus::Connection) -> Result<Development<'a>, Error> {
+        block_on(flatpak::Development::with_connection(connection)).map(Self::from)
+    }
+
+    /// The asynchronous proxy this wraps.
+    pub fn inner(&self) -> &flatpak::Development<'a> {
+        &self.0
+    }
+
+    /// Convert into the asynchronous proxy this wraps.
+    pub fn into_inner(self) -> flatpak::Development<'a> {
+        self.0.into_inner()
+    }
+
+    /// Blocking version of [`flatpak::Development::receive_spawn_exited`].
+    #[doc(alias = "HostCommandExited")]
+    pub fn receive_spawn_exited(&self) -> Result<impl Iterator<Item = (u32, u32)> + '_, Error> {
+        block_on(self.0.receive_spawn_exited()).map(block_on_stream)
+    }
+
+    /// Blocking version of [`flatpak::Development::host_command`].
+    pub fn host_command(
+        &self,
+        cwd_path: impl AsRef<Path>,
+        argv: &[impl AsRef<Path>],
+        fds: HashMap<u32, BorrowedFd<'_>>,
+        envs: HashMap<&str, &str>,
+        flags: BitFlags<HostCommandFlags>,
+    ) -> Result<u32, Error> {
+        block_on(self.0.host_command(cwd_path, argv, fds, envs, flags))
+    }
+
+    /// Blocking version of [`flatpak::Development::host_command_signal`].
+    #[doc(alias = "SpawnSignal")]
+    #[doc(alias = "xdp_portal_spawn_signal")]
+    pub fn host_command_signal(
+        &self,
+        pid: u32,
+        signal: u32,
+        to_process_group: bool,
+    ) -> Result<(), Error> {
+        block_on(self.0.host_command_signal(pid, signal, to_process_group))
+    }
+}
+
+impl<'a> From<flatpak::Development<'a>> for Development<'a> {
+    fn from(proxy: flatpak::Development<'a>) -> Self {
+        Self(Async::new(proxy))
+    }
+}
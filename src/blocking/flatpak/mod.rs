@@ -0,0 +1,95 @@
+//! Blocking wrappers of the [`crate::flatpak`] portals.
+
+use std::{collections::HashMap, os::fd::BorrowedFd, path::Path};
+
+use enumflags2::BitFlags;
+
+use crate::{
+    blocking::{block_on, block_on_stream, Async},
+    flatpak::{self, SpawnFlags, SpawnOptions, SupportsFlags},
+    Error,
+};
+
+/// Blocking wrapper of [`flatpak::Flatpak`].
+#[derive(Debug)]
+pub struct Flatpak<'a>(Async<flatpak::Flatpak<'a>>);
+
+impl<'a> Flatpak<'a> {
+    /// Create a new instance of [`Flatpak`].
+    pub fn new() -> Result<Flatpak<'a>, Error> {
+        block_on(flatpak::Flatpak::new()).map(Self::from)
+    }
+
+    /// Create a new instance of [`Flatpak`] on the given connection.
+    pub fn with_connection(connection: &zbus::Connection) -> Result<Flatpak<'a>, Error> {
+        block_on(flatpak::Flatpak::with_connection(connection)).map(Self::from)
+    }
+
+    /// The asynchronous proxy this wraps.
+    pub fn inner(&self) -> &flatpak::Flatpak<'a> {
+        &self.0
+    }
+
+    /// Convert into the asynchronous proxy this wraps.
+    pub fn into_inner(self) -> flatpak::Flatpak<'a> {
+        self.0.into_inner()
+    }
+
+    /// Blocking version of [`flatpak::Flatpak::create_update_monitor`].
+    #[doc(alias = "CreateUpdateMonitor")]
+    #[doc(alias = "xdp_portal_update_monitor_start")]
+    pub fn create_update_monitor(&self) -> Result<UpdateMonitor<'a>, Error> {
+        block_on(self.0.create_update_monitor()).map(UpdateMonitor::from)
+    }
+
+    /// Blocking version of [`flatpak::Flatpak::receive_spawn_started`].
+    #[doc(alias = "SpawnStarted")]
+    pub fn receive_spawn_started(&self) -> Result<impl Iterator<Item = (u32, u32)> + '_, Error> {
+        block_on(self.0.receive_spawn_started()).map(block_on_stream)
+    }
+
+    /// Blocking version of [`flatpak::Flatpak::receive_spawn_exited`].
+    #[doc(alias = "SpawnExited")]
+    #[doc(alias = "XdpPortal::spawn-exited")]
+    pub fn receive_spawn_exited(&self) -> Result<impl Iterator<Item = (u32, u32)> + '_, Error> {
+        block_on(self.0.receive_spawn_exited()).map(block_on_stream)
+    }
+
+    /// Blocking version of [`flatpak::Flatpak::spawn`].
+    #[doc(alias = "Spawn")]
+    #[doc(alias = "xdp_portal_spawn")]
+    pub fn spawn(
+        &self,
+        cwd_path: impl AsRef<Path>,
+        argv: &[impl AsRef<Path>],
+        fds: HashMap<u32, BorrowedFd<'_>>,
+        envs: HashMap<&str, &str>,
+        flags: BitFlags<SpawnFlags>,
+        options: SpawnOptions,
+    ) -> Result<u32, Error> {
+        block_on(self.0.spawn(cwd_path, argv, fds, envs, flags, options))
+    }
+
+    /// Blocking version of [`flatpak::Flatpak::spawn_signal`].
+    #[doc(alias = "SpawnSignal")]
+    #[doc(alias = "xdp_portal_spawn_signal")]
+    pub fn spawn_signal(&self, pid: u32, signal: u32, to_process_group: bool) -> Result<(), Error> {
+        block_on(self.0.spawn_signal(pid, signal, to_process_group))
+    }
+
+    /// Blocking version of [`flatpak::Flatpak::supports`].
+    pub fn supports(&self) -> Result<BitFlags<SupportsFlags>, Error> {
+        block_on(self.0.supports())
+    }
+}
+
+impl<'a> From<flatpak::Flatpak<'a>> for Flatpak<'a> {
+    fn from(proxy: flatpak::Flatpak<'a>) -> Self {
+        Self(Async::new(proxy))
+    }
+}
+
+mod development;
+pub use development::Development;
+mod update_monitor;
+pub use update_monitor::UpdateMonitor;
@@ -0,0 +1,55 @@
+use crate::{
+    blocking::{block_on, block_on_stream, Async},
+    flatpak::{self, UpdateInfo, UpdateProgress},
+    Error, WindowIdentifier,
+};
+
+/// Blocking wrapper of [`flatpak::UpdateMonitor`].
+#[derive(Debug)]
+pub struct UpdateMonitor<'a>(Async<flatpak::UpdateMonitor<'a>>);
+
+impl<'a> UpdateMonitor<'a> {
+    /// The asynchronous proxy this wraps.
+    pub fn inner(&self) -> &flatpak::UpdateMonitor<'a> {
+        &self.0
+    }
+
+    /// Convert into the asynchronous proxy this wraps.
+    pub fn into_inner(self) -> flatpak::UpdateMonitor<'a> {
+        self.0.into_inner()
+    }
+
+    /// Blocking version of [`flatpak::UpdateMonitor::receive_progress`].
+    #[doc(alias = "Progress")]
+    #[doc(alias = "XdpPortal::update-progress")]
+    pub fn receive_progress(&self) -> Result<impl Iterator<Item = UpdateProgress> + '_, Error> {
+        block_on(self.0.receive_progress()).map(block_on_stream)
+    }
+
+    /// Blocking version of
+    /// [`flatpak::UpdateMonitor::receive_update_available`].
+    #[doc(alias = "UpdateAvailable")]
+    #[doc(alias = "XdpPortal::update-available")]
+    pub fn receive_update_available(&self) -> Result<impl Iterator<Item = UpdateInfo> + '_, Error> {
+        block_on(self.0.receive_update_available()).map(block_on_stream)
+    }
+
+    /// Blocking version of [`flatpak::UpdateMonitor::update`].
+    #[doc(alias = "Update")]
+    #[doc(alias = "xdp_portal_update_install")]
+    pub fn update(&self, identifier: &WindowIdentifier) -> Result<(), Error> {
+        block_on(self.0.update(identifier))
+    }
+
+    /// Blocking version of [`flatpak::UpdateMonitor::close`].
+    #[doc(alias = "Close")]
+    pub fn close(&self) -> Result<(), Error> {
+        block_on(self.0.close())
+    }
+}
+
+impl<'a> From<flatpak::UpdateMonitor<'a>> for UpdateMonitor<'a> {
+    fn from(proxy: flatpak::UpdateMonitor<'a>) -> Self {
+        Self(Async::new(proxy))
+    }
+}
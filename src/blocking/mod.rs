@@ -0,0 +1,161 @@
+//! Blocking wrappers of the portal proxies, for applications that don't run
+//! an async runtime.
+//!
+//! Every proxy of [`desktop`](crate::desktop),
+//! [`documents`](crate::documents) and [`flatpak`](crate::flatpak), except
+//! the deprecated device one, has a blocking counterpart with the same name and
+//! methods, that wraps it and blocks the calling thread until each call
+//! completes. Signal streams are turned into iterators that block until the
+//! next signal is received.
+//!
+//! The request builders, like
+//! [`SelectedFiles::open_file()`](crate::desktop::file_chooser::SelectedFiles::open_file),
+//! can be sent with their `send_blocking()` variants.
+//!
+//! **Note** These wrappers must not be used from within an async context, as
+//! they would block the executor thread.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use std::{fs::File, os::fd::AsFd};
+//!
+//! use ashpd::blocking::desktop::{secret, trash};
+//!
+//! fn run() -> ashpd::Result<()> {
+//!     let file = File::open("/home/bilelmoussaoui/Downloads/adwaita-night.jpg").unwrap();
+//!     trash::trash_file(&file.as_fd())?;
+//!
+//!     let secret = secret::retrieve()?;
+//!     println!("{}", secret.len());
+//!     Ok(())
+//! }
+//! ```
+//!
+//! ```rust,no_run
+//! use ashpd::{blocking::desktop::settings::Settings, desktop::settings::ColorScheme};
+//!
+//! fn run() -> ashpd::Result<()> {
+//!     let settings = Settings::new()?;
+//!     for color_scheme in settings.receive_color_scheme_changed()? {
+//!         if color_scheme == ColorScheme::PreferDark {
+//!             println!("Switched to the dark style");
+//!         }
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use std::{
+    fmt::{self, Debug},
+    future::Future,
+    ops::{Deref, DerefMut},
+};
+
+use futures_util::{Stream, StreamExt};
+
+pub mod desktop;
+pub mod documents;
+pub mod flatpak;
+
+/// Run a future to completion on the current thread.
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    zbus::block_on(future)
+}
+
+/// Turn a stream into an iterator blocking on each item.
+pub(crate) fn block_on_stream<S: Stream>(stream: S) -> impl Iterator<Item = S::Item> {
+    let mut stream = Async::new(Box::pin(stream));
+    std::iter::from_fn(move || block_on(stream.next()))
+}
+
+/// An async object wrapped by a blocking one.
+///
+/// The destructors of signal streams and sessions may spawn tasks, which
+/// requires running inside the runtime with tokio. The object is therefore
+/// dropped within [`block_on`].
+pub(crate) struct Async<T>(Option<T>);
+
+impl<T> Async<T> {
+    pub(crate) fn new(inner: T) -> Self {
+        Self(Some(inner))
+    }
+
+    pub(crate) fn into_inner(mut self) -> T {
+        self.0.take().unwrap()
+    }
+}
+
+impl<T> Deref for Async<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref().unwrap()
+    }
+}
+
+impl<T> DerefMut for Async<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.0.as_mut().unwrap()
+    }
+}
+
+impl<T> Drop for Async<T> {
+    fn drop(&mut self) {
+        if let Some(inner) = self.0.take() {
+            block_on(async move { drop(inner) });
+        }
+    }
+}
+
+impl<T: Debug> Debug for Async<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.deref().fmt(f)
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use zbus::zvariant::Value;
+
+    use super::{block_on, desktop::settings::Settings};
+    use crate::{
+        desktop::{file_chooser::SelectedFiles, request::Response, settings::ColorScheme},
+        testing::FakePortal,
+    };
+
+    #[test]
+    fn blocking_calls() {
+        let portal = block_on(FakePortal::new()).unwrap();
+        let uri = url::Url::parse("file:///home/user/file.txt").unwrap();
+        portal.respond(
+            "org.freedesktop.portal.FileChooser",
+            "OpenFile",
+            Response::ok(SelectedFiles::default().uri(uri.clone())),
+        );
+        let files = SelectedFiles::open_file()
+            .connection(portal.connection().clone())
+            .send_blocking()
+            .unwrap()
+            .response()
+            .unwrap();
+        assert_eq!(files.uris(), &[uri]);
+
+        let settings = Settings::with_connection(portal.connection()).unwrap();
+        portal.reply("org.freedesktop.portal.Settings", "Read", Value::from(1u32));
+        assert_eq!(settings.color_scheme().unwrap(), ColorScheme::PreferDark);
+
+        let mut changes = settings.receive_color_scheme_changed().unwrap();
+        block_on(portal.emit_signal(
+            "org.freedesktop.portal.Settings",
+            "SettingChanged",
+            &(
+                "org.freedesktop.appearance",
+                "color-scheme",
+                Value::from(2u32),
+            ),
+        ))
+        .unwrap();
+        assert_eq!(changes.next(), Some(ColorScheme::PreferLight));
+    }
+}
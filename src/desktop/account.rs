@@ -13,7 +13,8 @@
 //!         .reason("App would like to access user information")
 //!         .send()
 //!         .await?
-//!         .response()?;
+//!         .response()
+//!         .await?;
 //!
 //!     println!("Name: {}", response.name());
 //!     println!("ID: {}", response.id());
@@ -25,7 +26,7 @@
 use zbus::zvariant::{DeserializeDict, SerializeDict, Type};
 
 use super::HandleToken;
-use crate::{desktop::request::Request, proxy::Proxy, Error, WindowIdentifier};
+use crate::{blocking, desktop::request::Request, proxy::Proxy, Error, WindowIdentifier};
 
 #[derive(SerializeDict, Type, Debug, Default)]
 #[zvariant(signature = "dict")]
@@ -44,6 +45,17 @@ pub struct UserInformation {
 }
 
 impl UserInformation {
+    #[cfg(any(feature = "backend", feature = "testing"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "backend", feature = "testing"))))]
+    /// Create a new user information response.
+    pub fn new(id: &str, name: &str, image: url::Url) -> Self {
+        Self {
+            id: id.to_owned(),
+            name: name.to_owned(),
+            image,
+        }
+    }
+
     /// User identifier.
     pub fn id(&self) -> &str {
         &self.id
@@ -76,6 +88,13 @@ impl<'a> AccountProxy<'a> {
         Ok(Self(proxy))
     }
 
+    pub async fn with_connection(connection: &zbus::Connection) -> Result<AccountProxy<'a>, Error> {
+        let proxy =
+            Proxy::new_desktop_with_connection(connection, "org.freedesktop.portal.Account")
+                .await?;
+        Ok(Self(proxy))
+    }
+
     pub async fn user_information(
         &self,
         identifier: &WindowIdentifier,
@@ -108,9 +127,17 @@ impl<'a> std::ops::Deref for AccountProxy<'a> {
 pub struct UserInformationRequest {
     options: UserInformationOptions,
     identifier: WindowIdentifier,
+    connection: Option<zbus::Connection>,
 }
 
 impl UserInformationRequest {
+    #[must_use]
+    /// Sets the connection to send the request on, instead of the session bus.
+    pub fn connection(mut self, connection: impl Into<Option<zbus::Connection>>) -> Self {
+        self.connection = connection.into();
+        self
+    }
+
     #[must_use]
     /// Sets a user-visible reason for the request.
     pub fn reason<'a>(mut self, reason: impl Into<Option<&'a str>>) -> Self {
@@ -127,7 +154,15 @@ impl UserInformationRequest {
 
     /// Build the [`UserInformation`].
     pub async fn send(self) -> Result<Request<UserInformation>, Error> {
-        let proxy = AccountProxy::new().await?;
+        let proxy = match self.connection {
+            Some(connection) => AccountProxy::with_connection(&connection).await?,
+            None => AccountProxy::new().await?,
+        };
         proxy.user_information(&self.identifier, self.options).await
     }
+
+    /// Blocking version of [`send()`][`Self::send`].
+    pub fn send_blocking(self) -> Result<blocking::desktop::Request<UserInformation>, Error> {
+        blocking::block_on(self.send()).map(blocking::desktop::Request::from)
+    }
 }
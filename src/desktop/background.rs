@@ -18,7 +18,8 @@
 //!         .dbus_activatable(false)
 //!         .send()
 //!         .await?
-//!         .response()?;
+//!         .response()
+//!         .await?;
 //!
 //!     println!("{}", response.auto_start());
 //!     println!("{}", response.run_in_background());
@@ -34,7 +35,7 @@ use serde::Serialize;
 use zbus::zvariant::{DeserializeDict, SerializeDict, Type};
 
 use super::{HandleToken, Request};
-use crate::{proxy::Proxy, Error, WindowIdentifier};
+use crate::{blocking, proxy::Proxy, Error, WindowIdentifier};
 
 #[derive(SerializeDict, Type, Debug, Default)]
 #[zvariant(signature = "dict")]
@@ -97,6 +98,16 @@ impl<'a> BackgroundProxy<'a> {
         Ok(Self(proxy))
     }
 
+    /// Create a new instance of [`BackgroundProxy`] on the given connection.
+    pub async fn with_connection(
+        connection: &zbus::Connection,
+    ) -> Result<BackgroundProxy<'a>, Error> {
+        let proxy =
+            Proxy::new_desktop_with_connection(connection, "org.freedesktop.portal.Background")
+                .await?;
+        Ok(Self(proxy))
+    }
+
     ///  Sets the status of the application running in background.
     ///
     /// # Arguments
@@ -112,7 +123,6 @@ impl<'a> BackgroundProxy<'a> {
     /// # Specifications
     ///
     /// See also [`SetStatus`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.portal.Background.html#org-freedesktop-portal-background-setstatus).
-
     pub async fn set_status(&self, message: &str) -> Result<(), Error> {
         self.0
             .call_versioned(
@@ -156,9 +166,17 @@ impl<'a> std::ops::Deref for BackgroundProxy<'a> {
 pub struct BackgroundRequest {
     identifier: WindowIdentifier,
     options: BackgroundOptions,
+    connection: Option<zbus::Connection>,
 }
 
 impl BackgroundRequest {
+    #[must_use]
+    /// Sets the connection to send the request on, instead of the session bus.
+    pub fn connection(mut self, connection: impl Into<Option<zbus::Connection>>) -> Self {
+        self.connection = connection.into();
+        self
+    }
+
     #[must_use]
     /// Sets a window identifier.
     pub fn identifier(mut self, identifier: impl Into<Option<WindowIdentifier>>) -> Self {
@@ -203,9 +221,17 @@ impl BackgroundRequest {
 
     /// Build the [`Background`].
     pub async fn send(self) -> Result<Request<Background>, Error> {
-        let proxy = BackgroundProxy::new().await?;
+        let proxy = match self.connection {
+            Some(connection) => BackgroundProxy::with_connection(&connection).await?,
+            None => BackgroundProxy::new().await?,
+        };
         proxy
             .request_background(&self.identifier, self.options)
             .await
     }
+
+    /// Blocking version of [`send()`][`Self::send`].
+    pub fn send_blocking(self) -> Result<blocking::desktop::Request<Background>, Error> {
+        blocking::block_on(self.send()).map(blocking::desktop::Request::from)
+    }
 }
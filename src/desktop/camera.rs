@@ -9,7 +9,7 @@
 //! pub async fn run() -> ashpd::Result<()> {
 //!     let camera = Camera::new().await?;
 //!     if camera.is_present().await? {
-//!         camera.request_access().await?;
+//!         camera.request_access().await?.response().await?;
 //!         let remote_fd = camera.open_pipe_wire_remote().await?;
 //!         // pass the remote fd to GStreamer for example
 //!     }
@@ -47,6 +47,13 @@ impl<'a> Camera<'a> {
         Ok(Self(proxy))
     }
 
+    /// Create a new instance of [`Camera`] on the given connection.
+    pub async fn with_connection(connection: &zbus::Connection) -> Result<Camera<'a>, Error> {
+        let proxy =
+            Proxy::new_desktop_with_connection(connection, "org.freedesktop.portal.Camera").await?;
+        Ok(Self(proxy))
+    }
+
     /// Requests an access to the camera.
     ///
     /// # Specifications
@@ -242,7 +249,7 @@ pub async fn pipewire_streams(fd: OwnedFd) -> Result<Vec<Stream>, pipewire::Erro
 /// available.
 pub async fn request() -> Result<Option<OwnedFd>, Error> {
     let proxy = Camera::new().await?;
-    proxy.request_access().await?;
+    proxy.request_access().await?.response().await?;
     if proxy.is_present().await? {
         Ok(Some(proxy.open_pipe_wire_remote().await?))
     } else {
@@ -256,7 +263,7 @@ pub async fn request() -> Result<Option<OwnedFd>, Error> {
 /// available streams, one per camera.
 pub async fn request() -> Result<Option<(OwnedFd, Vec<Stream>)>, Error> {
     let proxy = Camera::new().await?;
-    proxy.request_access().await?;
+    proxy.request_access().await?.response().await?;
     if proxy.is_present().await? {
         let fd = proxy.open_pipe_wire_remote().await?;
         let streams = pipewire_streams(fd.try_clone()?).await?;
@@ -1,16 +1,20 @@
 //! Interact with the clipboard.
 //!
 //! The portal is mostly meant to be used along with
-//! [`RemoteDesktop`](crate::desktop::remote_desktop::RemoteDesktop)
+//! [`RemoteDesktop`]. The [`ClipboardManager`] shares the clipboard of a
+//! session on top of the raw calls of [`Clipboard`].
 
 use std::collections::HashMap;
 
 use futures_util::{Stream, StreamExt};
 use zbus::zvariant::{DeserializeDict, OwnedFd, OwnedObjectPath, SerializeDict, Type, Value};
 
-use super::Session;
+pub use self::manager::{ClipboardContent, ClipboardManager, SelectionOwner};
+use super::{remote_desktop::RemoteDesktop, Session};
 use crate::{proxy::Proxy, Result};
 
+mod manager;
+
 #[derive(Debug, Type, SerializeDict)]
 #[zvariant(signature = "dict")]
 struct SetSelectionOptions<'a> {
@@ -49,11 +53,19 @@ impl<'a> Clipboard<'a> {
         ))
     }
 
+    /// Create a new instance of [`Clipboard`] on the given connection.
+    pub async fn with_connection(connection: &zbus::Connection) -> Result<Clipboard<'a>> {
+        let proxy =
+            Proxy::new_desktop_with_connection(connection, "org.freedesktop.portal.Clipboard")
+                .await?;
+        Ok(Self(proxy))
+    }
+
     /// # Specifications
     ///
     /// See also [`RequestClipboard`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.portal.Clipboard.html#org-freedesktop-portal-clipboard-requestclipboard).
     #[doc(alias = "RequestClipboard")]
-    pub async fn request(&self, session: &Session<'_>) -> Result<()> {
+    pub async fn request(&self, session: &Session<'_, RemoteDesktop<'_>>) -> Result<()> {
         let options: HashMap<&str, Value<'_>> = HashMap::default();
         self.0
             .call_method("RequestClipboard", &(session, options))
@@ -65,18 +77,39 @@ impl<'a> Clipboard<'a> {
     ///
     /// See also [`SetSelection`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.portal.Clipboard.html#org-freedesktop-portal-clipboard-setselection).
     #[doc(alias = "SetSelection")]
-    pub async fn set_selection(&self, session: &Session<'_>, mime_types: &[&str]) -> Result<()> {
-        let options = SetSelectionOptions { mime_types };
-        self.0.call("SetSelection", &(session, options)).await?;
+    pub async fn set_selection(
+        &self,
+        session: &Session<'_, RemoteDesktop<'_>>,
+        mime_types: &[&str],
+    ) -> Result<()> {
+        self.set_selection_reply(session, mime_types).await?;
 
         Ok(())
     }
 
+    /// Same as [`Clipboard::set_selection`], returning the reply to order it
+    /// with the signals.
+    pub(crate) async fn set_selection_reply(
+        &self,
+        session: &Session<'_, RemoteDesktop<'_>>,
+        mime_types: &[&str],
+    ) -> Result<zbus::Message> {
+        let options = SetSelectionOptions { mime_types };
+        self.0
+            .call_method("SetSelection", &(session, options))
+            .await
+            .map_err(|err| self.0.call_error("SetSelection", err))
+    }
+
     /// # Specifications
     ///
     /// See also [`SelectionWrite`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.portal.Clipboard.html#org-freedesktop-portal-clipboard-selectionwrite).
     #[doc(alias = "SelectionWrite")]
-    pub async fn selection_write(&self, session: &Session<'_>, serial: u32) -> Result<OwnedFd> {
+    pub async fn selection_write(
+        &self,
+        session: &Session<'_, RemoteDesktop<'_>>,
+        serial: u32,
+    ) -> Result<OwnedFd> {
         let fd = self
             .0
             .call::<OwnedFd>("SelectionWrite", &(session, serial))
@@ -90,7 +123,7 @@ impl<'a> Clipboard<'a> {
     #[doc(alias = "SelectionWriteDone")]
     pub async fn selection_write_done(
         &self,
-        session: &Session<'_>,
+        session: &Session<'_, RemoteDesktop<'_>>,
         serial: u32,
         success: bool,
     ) -> Result<()> {
@@ -103,7 +136,11 @@ impl<'a> Clipboard<'a> {
     ///
     /// See also [`SelectionRead`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.portal.Clipboard.html#org-freedesktop-portal-clipboard-selectionread).
     #[doc(alias = "SelectionRead")]
-    pub async fn selection_read(&self, session: &Session<'_>, mime_type: &str) -> Result<OwnedFd> {
+    pub async fn selection_read(
+        &self,
+        session: &Session<'_, RemoteDesktop<'_>>,
+        mime_type: &str,
+    ) -> Result<OwnedFd> {
         let fd = self
             .0
             .call::<OwnedFd>("SelectionRead", &(session, mime_type))
@@ -118,12 +155,16 @@ impl<'a> Clipboard<'a> {
     #[doc(alias = "SelectionOwnerChanged")]
     pub async fn receive_selection_owner_changed(
         &self,
-    ) -> Result<impl Stream<Item = (Session, SelectionOwnerChanged)>> {
+    ) -> Result<impl Stream<Item = (Session<'_, RemoteDesktop<'_>>, SelectionOwnerChanged)>> {
+        let connection = self.0.connection().clone();
         Ok(self
             .0
             .signal::<(OwnedObjectPath, SelectionOwnerChanged)>("SelectionOwnerChanged")
             .await?
-            .filter_map(|(p, o)| async move { Session::new(p).await.map(|s| (s, o)).ok() }))
+            .filter_map(move |(p, o)| {
+                let connection = connection.clone();
+                async move { Session::new(&connection, p).await.map(|s| (s, o)).ok() }
+            }))
     }
 
     /// # Specifications
@@ -132,16 +173,20 @@ impl<'a> Clipboard<'a> {
     #[doc(alias = "SelectionTransfer")]
     pub async fn receive_selection_transfer(
         &self,
-    ) -> Result<impl Stream<Item = (Session, String, u32)>> {
+    ) -> Result<impl Stream<Item = (Session<'_, RemoteDesktop<'_>>, String, u32)>> {
+        let connection = self.0.connection().clone();
         Ok(self
             .0
             .signal::<(OwnedObjectPath, String, u32)>("SelectionTransfer")
             .await?
-            .filter_map(|(p, mime_type, serial)| async move {
-                Session::new(p)
-                    .await
-                    .map(|session| (session, mime_type, serial))
-                    .ok()
+            .filter_map(move |(p, mime_type, serial)| {
+                let connection = connection.clone();
+                async move {
+                    Session::new(&connection, p)
+                        .await
+                        .map(|session| (session, mime_type, serial))
+                        .ok()
+                }
             }))
     }
 }
@@ -0,0 +1,621 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use futures_util::{
+    future::{AbortHandle, Abortable, Either},
+    pin_mut, stream, Stream, StreamExt,
+};
+use zbus::{message::Sequence, zvariant::OwnedObjectPath};
+
+use super::{Clipboard, SelectionOwnerChanged};
+use crate::{
+    desktop::{remote_desktop::RemoteDesktop, Session},
+    helpers::{read_fd, write_fd},
+    Error,
+};
+
+/// The text MIME types, in order of preference.
+const TEXT_MIME_TYPES: &[&str] = &["text/plain;charset=utf-8", "text/plain", "UTF8_STRING"];
+
+/// The default [`ClipboardManager::size_limit`], 16 MiB.
+const DEFAULT_SIZE_LIMIT: usize = 16 * 1024 * 1024;
+
+type Provider = Arc<dyn Fn() -> Vec<u8> + Send + Sync>;
+
+/// The content offered by a [`ClipboardManager`], with a provider of the data
+/// for each of its MIME types.
+///
+/// A provider is only called when another client pastes the content in its
+/// MIME type. It is called from the executor of the connection and must not
+/// block.
+#[derive(Default)]
+pub struct ClipboardContent {
+    providers: Vec<(String, Provider)>,
+}
+
+impl ClipboardContent {
+    /// Create an empty [`ClipboardContent`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Offer the data returned by `provider` as `mime_type`, replacing a
+    /// previous provider of the same MIME type.
+    #[must_use]
+    pub fn provider(
+        mut self,
+        mime_type: &str,
+        provider: impl Fn() -> Vec<u8> + Send + Sync + 'static,
+    ) -> Self {
+        self.providers.retain(|(offered, _)| offered != mime_type);
+        self.providers
+            .push((mime_type.to_owned(), Arc::new(provider)));
+        self
+    }
+
+    /// Offer `text` as UTF-8 plain text.
+    #[must_use]
+    pub fn text(self, text: &str) -> Self {
+        let text: Arc<str> = Arc::from(text);
+        TEXT_MIME_TYPES.iter().fold(self, |content, mime_type| {
+            let text = text.clone();
+            content.provider(mime_type, move || text.as_bytes().to_vec())
+        })
+    }
+
+    /// The offered MIME types.
+    pub fn mime_types(&self) -> Vec<&str> {
+        self.providers
+            .iter()
+            .map(|(mime_type, _)| mime_type.as_str())
+            .collect()
+    }
+}
+
+impl fmt::Debug for ClipboardContent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClipboardContent")
+            .field("mime_types", &self.mime_types())
+            .finish()
+    }
+}
+
+/// The owner of the clipboard selection, as announced by
+/// [`SelectionOwnerChanged`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum SelectionOwner {
+    /// No change of the selection was announced yet.
+    #[default]
+    Unknown,
+    /// The session owns the selection, with content in the given MIME types.
+    Session(Vec<String>),
+    /// Another client owns the selection, with content in the given MIME
+    /// types.
+    Other(Vec<String>),
+}
+
+impl SelectionOwner {
+    /// The MIME types of the selection.
+    pub fn mime_types(&self) -> &[String] {
+        match self {
+            Self::Unknown => &[],
+            Self::Session(mime_types) | Self::Other(mime_types) => mime_types,
+        }
+    }
+}
+
+#[derive(Default)]
+struct State {
+    owner: SelectionOwner,
+    providers: HashMap<String, Provider>,
+    /// The position of the reply to the last `SetSelection` call. The owner
+    /// changes received before it predate the providers.
+    content_set_at: Option<Sequence>,
+    /// Whether a `SetSelection` call is in flight.
+    setting: bool,
+    /// The position of the last change of the owner to another client
+    /// received while setting the content.
+    taken_at: Option<Sequence>,
+}
+
+/// Share the clipboard of a [`RemoteDesktop`] session.
+///
+/// The manager answers the
+/// [`SelectionTransfer`](Clipboard::receive_selection_transfer) requests of the
+/// portal with the [`ClipboardContent`] it offers, and keeps track of the
+/// [`SelectionOwner`]. It runs in the background until it is dropped.
+///
+/// **Note** the clipboard must be [requested](Clipboard::request) before
+/// starting the session.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use ashpd::desktop::{
+///     clipboard::{Clipboard, ClipboardContent, ClipboardManager},
+///     remote_desktop::RemoteDesktop,
+///     Session,
+/// };
+///
+/// async fn run(
+///     clipboard: &Clipboard<'_>,
+///     session: &Session<'_, RemoteDesktop<'_>>,
+/// ) -> ashpd::Result<()> {
+///     let manager = ClipboardManager::new(clipboard, session).await?;
+///     manager
+///         .set_content(ClipboardContent::new().text("Hello"))
+///         .await?;
+///     println!("{}", manager.read_text().await?);
+///     Ok(())
+/// }
+/// ```
+pub struct ClipboardManager<'a> {
+    clipboard: &'a Clipboard<'a>,
+    session: &'a Session<'a, RemoteDesktop<'a>>,
+    state: Arc<Mutex<State>>,
+    size_limit: usize,
+    abort_handle: AbortHandle,
+}
+
+impl<'a> ClipboardManager<'a> {
+    /// Create a new [`ClipboardManager`] for `session`, answering transfers
+    /// once this returns.
+    pub async fn new(
+        clipboard: &'a Clipboard<'a>,
+        session: &'a Session<'a, RemoteDesktop<'a>>,
+    ) -> Result<ClipboardManager<'a>, Error> {
+        let connection = clipboard.connection().clone();
+        let session_path = OwnedObjectPath::from(session.path().to_owned());
+        let state = Arc::new(Mutex::new(State::default()));
+        // Subscribe before returning, so that no transfer is missed.
+        let served_clipboard = Clipboard::with_connection(&connection).await?;
+        let served_session =
+            Session::<RemoteDesktop<'_>>::new(&connection, session_path.clone()).await?;
+        let owner_changes = served_clipboard
+            .0
+            .receive_signal("SelectionOwnerChanged")
+            .await?
+            .map(Either::Left);
+        let transfers = served_clipboard
+            .0
+            .signal::<(OwnedObjectPath, String, u32)>("SelectionTransfer")
+            .await?
+            .map(Either::Right);
+        let signals = stream::select(owner_changes, transfers);
+        let (abort_handle, registration) = AbortHandle::new_pair();
+        connection
+            .executor()
+            .spawn(
+                Abortable::new(
+                    serve(
+                        Arc::new(served_clipboard),
+                        Arc::new(served_session),
+                        signals,
+                        session_path,
+                        state.clone(),
+                    ),
+                    registration,
+                ),
+                "ashpd clipboard manager",
+            )
+            .detach();
+        Ok(Self {
+            clipboard,
+            session,
+            state,
+            size_limit: DEFAULT_SIZE_LIMIT,
+            abort_handle,
+        })
+    }
+
+    /// The maximum size of the content read from the selection, defaults to
+    /// 16 MiB.
+    #[must_use]
+    pub fn size_limit(mut self, size_limit: usize) -> Self {
+        self.size_limit = size_limit;
+        self
+    }
+
+    /// The current owner of the selection.
+    pub fn owner(&self) -> SelectionOwner {
+        self.state.lock().unwrap().owner.clone()
+    }
+
+    /// Offer `content` as the new selection.
+    ///
+    /// The providers are installed before announcing the selection, so that
+    /// the transfers following it are answered.
+    pub async fn set_content(&self, content: ClipboardContent) -> Result<(), Error> {
+        let previous = {
+            let mut state = self.state.lock().unwrap();
+            state.setting = true;
+            state.taken_at = None;
+            let providers = content.providers.iter().cloned().collect();
+            std::mem::replace(&mut state.providers, providers)
+        };
+        let reply = self
+            .clipboard
+            .set_selection_reply(self.session, &content.mime_types())
+            .await;
+
+        let mut state = self.state.lock().unwrap();
+        state.setting = false;
+        let taken_at = state.taken_at.take();
+        match reply {
+            Ok(reply) => {
+                let set_at = reply.recv_position();
+                state.content_set_at = Some(set_at);
+                if taken_at.is_some_and(|taken_at| taken_at > set_at) {
+                    state.providers.clear();
+                }
+                Ok(())
+            }
+            Err(err) => {
+                state.providers = if taken_at.is_some() {
+                    HashMap::new()
+                } else {
+                    previous
+                };
+                Err(err)
+            }
+        }
+    }
+
+    /// Read the content of the selection as `mime_type`.
+    ///
+    /// Content offered by the session itself is read from its provider.
+    /// Content exceeding the [size limit](Self::size_limit) fails with an
+    /// [`std::io::ErrorKind::InvalidData`] error.
+    pub async fn read(&self, mime_type: &str) -> Result<Vec<u8>, Error> {
+        let provider = {
+            let state = self.state.lock().unwrap();
+            match state.owner {
+                SelectionOwner::Session(_) => state.providers.get(mime_type).cloned(),
+                _ => None,
+            }
+        };
+        let data = match provider {
+            Some(provider) => provider(),
+            None => {
+                let fd = self
+                    .clipboard
+                    .selection_read(self.session, mime_type)
+                    .await?;
+                read_fd(fd.into(), self.size_limit).await?
+            }
+        };
+        if data.len() > self.size_limit {
+            return Err(
+                std::io::Error::new(std::io::ErrorKind::InvalidData, "content too large").into(),
+            );
+        }
+        Ok(data)
+    }
+
+    /// Read the selection as UTF-8 plain text.
+    ///
+    /// Content which isn't valid UTF-8 fails with an
+    /// [`std::io::ErrorKind::InvalidData`] error.
+    pub async fn read_text(&self) -> Result<String, Error> {
+        let mime_type = {
+            let owner = self.owner();
+            TEXT_MIME_TYPES
+                .iter()
+                .find(|mime_type| owner.mime_types().iter().any(|m| m == *mime_type))
+                .unwrap_or(&TEXT_MIME_TYPES[0])
+        };
+        let data = self.read(mime_type).await?;
+        String::from_utf8(data)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err).into())
+    }
+}
+
+impl fmt::Debug for ClipboardManager<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClipboardManager")
+            .field("session", &self.session)
+            .field("owner", &self.owner())
+            .field("size_limit", &self.size_limit)
+            .finish()
+    }
+}
+
+impl Drop for ClipboardManager<'_> {
+    fn drop(&mut self) {
+        self.abort_handle.abort();
+    }
+}
+
+/// Answer the transfers of `session_path` and track its selection owner.
+async fn serve(
+    clipboard: Arc<Clipboard<'static>>,
+    session: Arc<Session<'static, RemoteDesktop<'static>>>,
+    signals: impl Stream<Item = Either<zbus::Message, (OwnedObjectPath, String, u32)>>,
+    session_path: OwnedObjectPath,
+    state: Arc<Mutex<State>>,
+) {
+    pin_mut!(signals);
+    while let Some(signal) = signals.next().await {
+        match signal {
+            Either::Left(message) => {
+                let Ok((path, changed)) = message
+                    .body()
+                    .deserialize::<(OwnedObjectPath, SelectionOwnerChanged)>()
+                else {
+                    continue;
+                };
+                if path != session_path {
+                    continue;
+                }
+                let mut state = state.lock().unwrap();
+                if changed.session_is_owner() == Some(true) {
+                    state.owner = SelectionOwner::Session(changed.mime_types());
+                    continue;
+                }
+                state.owner = SelectionOwner::Other(changed.mime_types());
+                // Changes predating the content set last don't drop it.
+                let changed_at = message.recv_position();
+                if state.setting {
+                    state.taken_at = Some(changed_at);
+                } else if state
+                    .content_set_at
+                    .map_or(true, |set_at| changed_at > set_at)
+                {
+                    state.providers.clear();
+                }
+            }
+            Either::Right((path, mime_type, serial)) if path == session_path => {
+                let provider = state.lock().unwrap().providers.get(&mime_type).cloned();
+                // A slow paste target doesn't hold up the other transfers.
+                let executor = clipboard.connection().executor().clone();
+                let (clipboard, session) = (clipboard.clone(), session.clone());
+                executor
+                    .spawn(
+                        async move {
+                            let success = match provider {
+                                Some(provider) => {
+                                    transfer(&clipboard, &session, serial, provider())
+                                        .await
+                                        .is_ok()
+                                }
+                                None => false,
+                            };
+                            let _ = clipboard
+                                .selection_write_done(&session, serial, success)
+                                .await;
+                        },
+                        "ashpd clipboard transfer",
+                    )
+                    .detach();
+            }
+            Either::Right(_) => {}
+        }
+    }
+}
+
+async fn transfer(
+    clipboard: &Clipboard<'_>,
+    session: &Session<'_, RemoteDesktop<'_>>,
+    serial: u32,
+    data: Vec<u8>,
+) -> Result<(), Error> {
+    let fd = clipboard.selection_write(session, serial).await?;
+    write_fd(fd.into(), data).await?;
+    Ok(())
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod portal_tests {
+    use std::{io::Read, os::unix::net::UnixStream};
+
+    use zbus::zvariant::{ObjectPath, OwnedValue, Value};
+
+    use super::*;
+    use crate::{helpers::sleep, testing::FakePortal};
+
+    const INTERFACE: &str = "org.freedesktop.portal.Clipboard";
+
+    fn owner_changed(
+        mime_types: &'static [&'static str],
+        session_is_owner: bool,
+    ) -> HashMap<&'static str, Value<'static>> {
+        HashMap::from([
+            ("mime_types", Value::from(mime_types.to_vec())),
+            ("session_is_owner", Value::from(session_is_owner)),
+        ])
+    }
+
+    fn fd(stream: UnixStream) -> zbus::zvariant::OwnedFd {
+        std::os::fd::OwnedFd::from(stream).into()
+    }
+
+    #[test]
+    fn share_selection() {
+        zbus::block_on(async {
+            let portal = FakePortal::new().await.unwrap();
+            let clipboard = Clipboard::with_connection(portal.connection())
+                .await
+                .unwrap();
+            let session = portal.session().await.unwrap();
+            let manager = ClipboardManager::new(&clipboard, &session)
+                .await
+                .unwrap()
+                .size_limit(4);
+
+            // Another client owned the selection before, which doesn't drop
+            // the content set afterwards.
+            portal
+                .emit_signal(
+                    INTERFACE,
+                    "SelectionOwnerChanged",
+                    &(
+                        ObjectPath::from_static_str_unchecked(FakePortal::SESSION_PATH),
+                        owner_changed(&["text/html"], false),
+                    ),
+                )
+                .await
+                .unwrap();
+            portal.reply(INTERFACE, "SetSelection", ());
+            manager
+                .set_content(ClipboardContent::new().text("hello"))
+                .await
+                .unwrap();
+            let (_, options) = portal.calls(INTERFACE, "SetSelection")[0]
+                .body()
+                .deserialize::<(ObjectPath<'_>, HashMap<String, OwnedValue>)>()
+                .unwrap();
+            let mime_types =
+                Vec::<String>::try_from(options["mime_types"].try_clone().unwrap()).unwrap();
+            assert_eq!(mime_types, TEXT_MIME_TYPES);
+
+            // The session owns the selection, whose content exceeds the limit.
+            portal
+                .emit_signal(
+                    INTERFACE,
+                    "SelectionOwnerChanged",
+                    &(
+                        ObjectPath::from_static_str_unchecked(FakePortal::SESSION_PATH),
+                        owner_changed(TEXT_MIME_TYPES, true),
+                    ),
+                )
+                .await
+                .unwrap();
+            while !matches!(manager.owner(), SelectionOwner::Session(_)) {
+                sleep(std::time::Duration::from_millis(1)).await;
+            }
+            assert!(manager.read_text().await.is_err());
+
+            // Another client pastes it.
+            let (sender, mut receiver) = UnixStream::pair().unwrap();
+            // Read on another thread, the manager may run on this one.
+            let pasted = std::thread::spawn(move || {
+                let mut pasted = String::new();
+                receiver.read_to_string(&mut pasted).map(|_| pasted)
+            });
+            portal.reply(INTERFACE, "SelectionWrite", fd(sender));
+            portal.reply(INTERFACE, "SelectionWriteDone", ());
+            portal
+                .emit_signal(
+                    INTERFACE,
+                    "SelectionTransfer",
+                    &(
+                        ObjectPath::from_static_str_unchecked(FakePortal::SESSION_PATH),
+                        "text/plain",
+                        7u32,
+                    ),
+                )
+                .await
+                .unwrap();
+            while portal.calls(INTERFACE, "SelectionWriteDone").is_empty() {
+                sleep(std::time::Duration::from_millis(1)).await;
+            }
+            let (_, serial, success) = portal.calls(INTERFACE, "SelectionWriteDone")[0]
+                .body()
+                .deserialize::<(ObjectPath<'_>, u32, bool)>()
+                .unwrap();
+            assert_eq!((serial, success), (7, true));
+            assert_eq!(pasted.join().unwrap().unwrap(), "hello");
+
+            // Another client takes the selection.
+            portal
+                .emit_signal(
+                    INTERFACE,
+                    "SelectionOwnerChanged",
+                    &(
+                        ObjectPath::from_static_str_unchecked(FakePortal::SESSION_PATH),
+                        owner_changed(&["text/plain"], false),
+                    ),
+                )
+                .await
+                .unwrap();
+            while manager.owner() != SelectionOwner::Other(vec!["text/plain".to_owned()]) {
+                sleep(std::time::Duration::from_millis(1)).await;
+            }
+            let (sender, receiver) = UnixStream::pair().unwrap();
+            portal.reply(INTERFACE, "SelectionRead", fd(receiver));
+            std::io::Write::write_all(&mut &sender, b"hi").unwrap();
+            drop(sender);
+            assert_eq!(manager.read_text().await.unwrap(), "hi");
+            let (_, mime_type) = portal.calls(INTERFACE, "SelectionRead")[0]
+                .body()
+                .deserialize::<(ObjectPath<'_>, String)>()
+                .unwrap();
+            assert_eq!(mime_type, "text/plain");
+        });
+    }
+
+    #[test]
+    fn stalled_transfer() {
+        zbus::block_on(async {
+            let portal = FakePortal::new().await.unwrap();
+            let clipboard = Clipboard::with_connection(portal.connection())
+                .await
+                .unwrap();
+            let session = portal.session().await.unwrap();
+            let manager = ClipboardManager::new(&clipboard, &session).await.unwrap();
+            portal.reply(INTERFACE, "SetSelection", ());
+            manager
+                .set_content(
+                    ClipboardContent::new()
+                        .provider("application/octet-stream", || vec![0; 1024 * 1024])
+                        .text("hello"),
+                )
+                .await
+                .unwrap();
+
+            // The first paste target never reads, which doesn't hold up the
+            // second one.
+            let (stalled, stalled_receiver) = UnixStream::pair().unwrap();
+            let (sender, mut receiver) = UnixStream::pair().unwrap();
+            let pasted = std::thread::spawn(move || {
+                let mut pasted = String::new();
+                receiver.read_to_string(&mut pasted).map(|_| pasted)
+            });
+            portal.reply(INTERFACE, "SelectionWrite", fd(stalled));
+            portal.reply(INTERFACE, "SelectionWrite", fd(sender));
+            portal.reply(INTERFACE, "SelectionWriteDone", ());
+            portal.reply(INTERFACE, "SelectionWriteDone", ());
+            for (mime_type, serial) in [("application/octet-stream", 1u32), ("text/plain", 2)] {
+                portal
+                    .emit_signal(
+                        INTERFACE,
+                        "SelectionTransfer",
+                        &(
+                            ObjectPath::from_static_str_unchecked(FakePortal::SESSION_PATH),
+                            mime_type,
+                            serial,
+                        ),
+                    )
+                    .await
+                    .unwrap();
+            }
+            let done = |portal: &FakePortal| {
+                portal
+                    .calls(INTERFACE, "SelectionWriteDone")
+                    .iter()
+                    .map(|call| {
+                        let (_, serial, success) = call
+                            .body()
+                            .deserialize::<(ObjectPath<'_>, u32, bool)>()
+                            .unwrap();
+                        (serial, success)
+                    })
+                    .collect::<Vec<_>>()
+            };
+            while done(&portal).is_empty() {
+                sleep(std::time::Duration::from_millis(1)).await;
+            }
+            assert_eq!(done(&portal), [(2, true)]);
+            assert_eq!(pasted.join().unwrap().unwrap(), "hello");
+
+            drop(stalled_receiver);
+            while done(&portal).len() < 2 {
+                sleep(std::time::Duration::from_millis(1)).await;
+            }
+            assert_eq!(done(&portal)[1], (1, false));
+        });
+    }
+}
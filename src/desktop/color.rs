@@ -1,6 +1,9 @@
+#[cfg(any(feature = "backend", feature = "testing"))]
+use crate::zvariant::SerializeDict;
 use crate::zvariant::{self, DeserializeDict, Type};
 
 #[derive(DeserializeDict, Clone, Copy, PartialEq, Type, zvariant::Value, zvariant::OwnedValue)]
+#[cfg_attr(any(feature = "backend", feature = "testing"), derive(SerializeDict))]
 /// A color as a RGB tuple.
 ///
 /// **Note** the values are normalized in the [0.0, 1.0] range.
@@ -10,7 +13,8 @@ pub struct Color {
 }
 
 impl Color {
-    pub(crate) fn new(color: (f64, f64, f64)) -> Self {
+    /// Create a new color from normalized (red, green, blue) values.
+    pub fn new(color: (f64, f64, f64)) -> Self {
         Self { color }
     }
 
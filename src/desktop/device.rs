@@ -107,6 +107,13 @@ impl<'a> DeviceProxy<'a> {
         Ok(Self(proxy))
     }
 
+    /// Create a new instance of [`DeviceProxy`] on the given connection.
+    pub async fn with_connection(connection: &zbus::Connection) -> Result<DeviceProxy<'a>, Error> {
+        let proxy =
+            Proxy::new_desktop_with_connection(connection, "org.freedesktop.portal.Device").await?;
+        Ok(Self(proxy))
+    }
+
     /// Asks for access to a device.
     ///
     /// # Arguments
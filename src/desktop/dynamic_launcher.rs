@@ -30,7 +30,7 @@
 //!             PrepareInstallOptions::default()
 //!         )
 //!         .await?
-//!         .response()?;
+//!         .response().await?;
 //!     let token = response.token();
 //!
 //!
@@ -220,6 +220,19 @@ impl<'a> DynamicLauncherProxy<'a> {
         Ok(Self(proxy))
     }
 
+    /// Create a new instance of [`DynamicLauncherProxy`] on the given
+    /// connection.
+    pub async fn with_connection(
+        connection: &zbus::Connection,
+    ) -> Result<DynamicLauncherProxy<'a>, Error> {
+        let proxy = Proxy::new_desktop_with_connection(
+            connection,
+            "org.freedesktop.portal.DynamicLauncher",
+        )
+        .await?;
+        Ok(Self(proxy))
+    }
+
     /// *Note* Only `Icon::Bytes` is accepted.
     ///
     ///  # Specifications
@@ -30,7 +30,7 @@ use serde::Serialize;
 use zbus::zvariant::{self, SerializeDict, Type};
 
 use super::{HandleToken, Request};
-use crate::{proxy::Proxy, Error, WindowIdentifier};
+use crate::{blocking, proxy::Proxy, Error, WindowIdentifier};
 
 #[derive(SerializeDict, Type, Debug, Default)]
 #[zvariant(signature = "dict")]
@@ -58,6 +58,13 @@ impl<'a> EmailProxy<'a> {
         Ok(Self(proxy))
     }
 
+    /// Create a new instance of [`EmailProxy`] on the given connection.
+    pub async fn with_connection(connection: &zbus::Connection) -> Result<EmailProxy<'a>, Error> {
+        let proxy =
+            Proxy::new_desktop_with_connection(connection, "org.freedesktop.portal.Email").await?;
+        Ok(Self(proxy))
+    }
+
     /// Presents a window that lets the user compose an email.
     ///
     /// **Note** the default email client for the host will need to support
@@ -103,9 +110,17 @@ impl<'a> std::ops::Deref for EmailProxy<'a> {
 pub struct EmailRequest {
     identifier: WindowIdentifier,
     options: EmailOptions,
+    connection: Option<zbus::Connection>,
 }
 
 impl EmailRequest {
+    #[must_use]
+    /// Sets the connection to send the request on, instead of the session bus.
+    pub fn connection(mut self, connection: impl Into<Option<zbus::Connection>>) -> Self {
+        self.connection = connection.into();
+        self
+    }
+
     /// Sets a window identifier.
     #[must_use]
     pub fn identifier(mut self, identifier: impl Into<Option<WindowIdentifier>>) -> Self {
@@ -199,7 +214,15 @@ impl EmailRequest {
 
     /// Send the request.
     pub async fn send(self) -> Result<Request<()>, Error> {
-        let proxy = EmailProxy::new().await?;
+        let proxy = match self.connection {
+            Some(connection) => EmailProxy::with_connection(&connection).await?,
+            None => EmailProxy::new().await?,
+        };
         proxy.compose(&self.identifier, self.options).await
     }
+
+    /// Blocking version of [`send()`][`Self::send`].
+    pub fn send_blocking(self) -> Result<blocking::desktop::Request<()>, Error> {
+        blocking::block_on(self.send()).map(blocking::desktop::Request::from)
+    }
 }
@@ -27,7 +27,8 @@
 //!         .filter(FileFilter::new("SVG Image").mimetype("image/svg+xml"))
 //!         .send()
 //!         .await?
-//!         .response()?;
+//!         .response()
+//!         .await?;
 //!
 //!     println!("{:#?}", files);
 //!
@@ -49,7 +50,8 @@
 //!         .filter(FileFilter::new("JPEG Image").glob("*.jpg"))
 //!         .send()
 //!         .await?
-//!         .response()?;
+//!         .response()
+//!         .await?;
 //!
 //!     println!("{:#?}", files);
 //!
@@ -71,7 +73,8 @@
 //!         .files(&["test.jpg", "awesome.png"])?
 //!         .send()
 //!         .await?
-//!         .response()?;
+//!         .response()
+//!         .await?;
 //!
 //!     println!("{:#?}", files);
 //!
@@ -86,7 +89,9 @@ use serde_repr::{Deserialize_repr, Serialize_repr};
 use zbus::zvariant::{DeserializeDict, SerializeDict, Type};
 
 use super::{HandleToken, Request};
-use crate::{proxy::Proxy, Error, FilePath, WindowIdentifier};
+use crate::{blocking, proxy::Proxy, Error, FilePath, WindowIdentifier};
+
+mod paths;
 
 #[derive(Clone, Serialize, Deserialize, Type, Debug, PartialEq)]
 /// A file filter, to limit the available file choices to a mimetype or a glob
@@ -263,6 +268,10 @@ struct SaveFilesOptions {
 }
 
 #[derive(Debug, Type, DeserializeDict)]
+#[cfg_attr(
+    any(feature = "backend", feature = "testing"),
+    derive(SerializeDict, Default)
+)]
 /// A response of [`OpenFileRequest`], [`SaveFileRequest`] or
 /// [`SaveFilesRequest`].
 #[zvariant(signature = "dict")]
@@ -296,6 +305,26 @@ impl SelectedFiles {
     pub fn choices(&self) -> &[(String, String)] {
         self.choices.as_deref().unwrap_or_default()
     }
+
+    #[cfg(any(feature = "backend", feature = "testing"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "backend", feature = "testing"))))]
+    /// Adds a selected file uri.
+    #[must_use]
+    pub fn uri(mut self, uri: url::Url) -> Self {
+        self.uris.push(uri);
+        self
+    }
+
+    #[cfg(any(feature = "backend", feature = "testing"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "backend", feature = "testing"))))]
+    /// Adds the selected value of a choice.
+    #[must_use]
+    pub fn choice(mut self, choice_key: &str, choice_value: &str) -> Self {
+        self.choices
+            .get_or_insert_with(Vec::new)
+            .push((choice_key.to_owned(), choice_value.to_owned()));
+        self
+    }
 }
 
 #[doc(alias = "org.freedesktop.portal.FileChooser")]
@@ -308,6 +337,16 @@ impl<'a> FileChooserProxy<'a> {
         Ok(Self(proxy))
     }
 
+    /// Create a new instance of [`FileChooserProxy`] on the given connection.
+    pub async fn with_connection(
+        connection: &zbus::Connection,
+    ) -> Result<FileChooserProxy<'a>, Error> {
+        let proxy =
+            Proxy::new_desktop_with_connection(connection, "org.freedesktop.portal.FileChooser")
+                .await?;
+        Ok(Self(proxy))
+    }
+
     pub async fn open_file(
         &self,
         identifier: &WindowIdentifier,
@@ -371,9 +410,17 @@ pub struct OpenFileRequest {
     identifier: WindowIdentifier,
     title: String,
     options: OpenFileOptions,
+    connection: Option<zbus::Connection>,
 }
 
 impl OpenFileRequest {
+    #[must_use]
+    /// Sets the connection to send the request on, instead of the session bus.
+    pub fn connection(mut self, connection: impl Into<Option<zbus::Connection>>) -> Self {
+        self.connection = connection.into();
+        self
+    }
+
     #[must_use]
     /// Sets a window identifier.
     pub fn identifier(mut self, identifier: impl Into<Option<WindowIdentifier>>) -> Self {
@@ -465,11 +512,19 @@ impl OpenFileRequest {
 
     /// Send the request.
     pub async fn send(self) -> Result<Request<SelectedFiles>, Error> {
-        let proxy = FileChooserProxy::new().await?;
+        let proxy = match self.connection {
+            Some(connection) => FileChooserProxy::with_connection(&connection).await?,
+            None => FileChooserProxy::new().await?,
+        };
         proxy
             .open_file(&self.identifier, &self.title, self.options)
             .await
     }
+
+    /// Blocking version of [`send()`][`Self::send`].
+    pub fn send_blocking(self) -> Result<blocking::desktop::Request<SelectedFiles>, Error> {
+        blocking::block_on(self.send()).map(blocking::desktop::Request::from)
+    }
 }
 
 #[derive(Debug, Default)]
@@ -481,9 +536,17 @@ pub struct SaveFilesRequest {
     identifier: WindowIdentifier,
     title: String,
     options: SaveFilesOptions,
+    connection: Option<zbus::Connection>,
 }
 
 impl SaveFilesRequest {
+    #[must_use]
+    /// Sets the connection to send the request on, instead of the session bus.
+    pub fn connection(mut self, connection: impl Into<Option<zbus::Connection>>) -> Self {
+        self.connection = connection.into();
+        self
+    }
+
     #[must_use]
     /// Sets a window identifier.
     pub fn identifier(mut self, identifier: impl Into<Option<WindowIdentifier>>) -> Self {
@@ -552,11 +615,19 @@ impl SaveFilesRequest {
 
     /// Send the request.
     pub async fn send(self) -> Result<Request<SelectedFiles>, Error> {
-        let proxy = FileChooserProxy::new().await?;
+        let proxy = match self.connection {
+            Some(connection) => FileChooserProxy::with_connection(&connection).await?,
+            None => FileChooserProxy::new().await?,
+        };
         proxy
             .save_files(&self.identifier, &self.title, self.options)
             .await
     }
+
+    /// Blocking version of [`send()`][`Self::send`].
+    pub fn send_blocking(self) -> Result<blocking::desktop::Request<SelectedFiles>, Error> {
+        blocking::block_on(self.send()).map(blocking::desktop::Request::from)
+    }
 }
 
 #[derive(Debug, Default)]
@@ -568,9 +639,17 @@ pub struct SaveFileRequest {
     identifier: WindowIdentifier,
     title: String,
     options: SaveFileOptions,
+    connection: Option<zbus::Connection>,
 }
 
 impl SaveFileRequest {
+    #[must_use]
+    /// Sets the connection to send the request on, instead of the session bus.
+    pub fn connection(mut self, connection: impl Into<Option<zbus::Connection>>) -> Self {
+        self.connection = connection.into();
+        self
+    }
+
     #[must_use]
     /// Sets a window identifier.
     pub fn identifier(mut self, identifier: impl Into<Option<WindowIdentifier>>) -> Self {
@@ -664,9 +743,17 @@ impl SaveFileRequest {
 
     /// Send the request.
     pub async fn send(self) -> Result<Request<SelectedFiles>, Error> {
-        let proxy = FileChooserProxy::new().await?;
+        let proxy = match self.connection {
+            Some(connection) => FileChooserProxy::with_connection(&connection).await?,
+            None => FileChooserProxy::new().await?,
+        };
         proxy
             .save_file(&self.identifier, &self.title, self.options)
             .await
     }
+
+    /// Blocking version of [`send()`][`Self::send`].
+    pub fn send_blocking(self) -> Result<blocking::desktop::Request<SelectedFiles>, Error> {
+        blocking::block_on(self.send()).map(blocking::desktop::Request::from)
+    }
 }
@@ -0,0 +1,288 @@
+use std::path::{Component, Path, PathBuf};
+
+use super::SelectedFiles;
+use crate::{app_id::DocumentID, documents::Documents, Error};
+
+/// The document id of `path` in the document portal mounted at
+/// `mount_point`, with the path relative to the document.
+fn document(mount_point: &Path, path: &Path) -> Option<(DocumentID, PathBuf)> {
+    let mut components = path.strip_prefix(mount_point).ok()?.components();
+    let mut id = components.next()?;
+    // Documents are also exported per application in by-app/<app-id>/.
+    if id == Component::Normal("by-app".as_ref()) {
+        components.next()?;
+        id = components.next()?;
+    }
+    let id = id.as_os_str().to_str()?;
+    Some((DocumentID::from(id), components.as_path().to_owned()))
+}
+
+/// Whether `path` could have been exported by the document portal, whose
+/// mount point is a `doc` directory.
+fn maybe_document(path: &Path) -> bool {
+    path.ancestors()
+        .skip(1)
+        .any(|directory| directory.file_name() == Some("doc".as_ref()))
+}
+
+#[cfg(feature = "tokio")]
+async fn open_tokio_file(path: PathBuf, create: bool) -> std::io::Result<tokio::fs::File> {
+    let mut options = tokio::fs::OpenOptions::new();
+    if create {
+        options.write(true).create(true).truncate(true);
+    } else {
+        options.read(true);
+    }
+    options.open(path).await
+}
+
+#[cfg(feature = "async-std")]
+async fn open_async_std_file(path: PathBuf, create: bool) -> std::io::Result<async_fs::File> {
+    let mut options = async_fs::OpenOptions::new();
+    if create {
+        options.write(true).create(true).truncate(true);
+    } else {
+        options.read(true);
+    }
+    options.open(path).await
+}
+
+impl SelectedFiles {
+    /// The local paths of the selected files, skipping the URIs which aren't
+    /// `file://` ones.
+    ///
+    /// Paths which aren't valid UTF-8 are kept as is. Inside a sandbox, the
+    /// files are exported by the document portal, see
+    /// [`SelectedFiles::host_paths`] for their paths on the host.
+    pub fn paths(&self) -> Vec<PathBuf> {
+        self.uris()
+            .iter()
+            .filter_map(|uri| uri.to_file_path().ok())
+            .collect()
+    }
+
+    /// The paths of the selected files on the host, to show to users.
+    ///
+    /// The files exported by the document portal are mapped back to their
+    /// host path, the other ones are left as in [`SelectedFiles::paths`].
+    /// So are all of them when the document portal is older than version 5.
+    ///
+    /// **Note** the host paths can't be accessed from inside a sandbox.
+    pub async fn host_paths(&self, documents: &Documents<'_>) -> Result<Vec<PathBuf>, Error> {
+        let paths = self.paths();
+        // Not to ask for the mount point outside of a sandbox.
+        if !paths.iter().any(|path| maybe_document(path)) {
+            return Ok(paths);
+        }
+        let mount_point = documents.mount_point().await?;
+        let documents_paths = paths
+            .iter()
+            .map(|path| document(mount_point.as_ref(), path))
+            .collect::<Vec<_>>();
+        let ids = documents_paths
+            .iter()
+            .flatten()
+            .map(|(id, _)| id.clone())
+            .collect::<Vec<_>>();
+        if ids.is_empty() {
+            return Ok(paths);
+        }
+        let host_paths = match documents.host_paths(&ids).await {
+            Ok(host_paths) => host_paths,
+            Err(Error::RequiresVersion(..)) => return Ok(paths),
+            Err(err) => return Err(err),
+        };
+
+        Ok(paths
+            .into_iter()
+            .zip(documents_paths)
+            .map(|(path, document)| {
+                document
+                    .and_then(|(id, relative)| {
+                        // The host path is the one of the exported file or
+                        // directory, the first component of `relative`.
+                        let host_path = host_paths.get(&id)?.as_ref();
+                        let inner = relative.components().skip(1).collect::<PathBuf>();
+                        Some(host_path.join(inner))
+                    })
+                    .unwrap_or(path)
+            })
+            .collect())
+    }
+
+    /// Open the selected files for reading, in the order of
+    /// [`SelectedFiles::paths`].
+    #[cfg(feature = "tokio")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+    pub async fn open_tokio(&self) -> Result<Vec<tokio::fs::File>, Error> {
+        let mut files = Vec::new();
+        for path in self.paths() {
+            files.push(open_tokio_file(path, false).await?);
+        }
+        Ok(files)
+    }
+
+    /// Open the files selected with [`SelectedFiles::save_file`] or
+    /// [`SelectedFiles::save_files`] for writing, creating or truncating
+    /// them, in the order of [`SelectedFiles::paths`].
+    #[cfg(feature = "tokio")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+    pub async fn create_tokio(&self) -> Result<Vec<tokio::fs::File>, Error> {
+        let mut files = Vec::new();
+        for path in self.paths() {
+            files.push(open_tokio_file(path, true).await?);
+        }
+        Ok(files)
+    }
+
+    /// Open the selected files for reading, in the order of
+    /// [`SelectedFiles::paths`].
+    ///
+    /// The files can be used with any executor, e.g. async-std or smol.
+    #[cfg(feature = "async-std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "async-std")))]
+    pub async fn open_async_std(&self) -> Result<Vec<async_fs::File>, Error> {
+        let mut files = Vec::new();
+        for path in self.paths() {
+            files.push(open_async_std_file(path, false).await?);
+        }
+        Ok(files)
+    }
+
+    /// Open the files selected with [`SelectedFiles::save_file`] or
+    /// [`SelectedFiles::save_files`] for writing, creating or truncating
+    /// them, in the order of [`SelectedFiles::paths`].
+    ///
+    /// The files can be used with any executor, e.g. async-std or smol.
+    #[cfg(feature = "async-std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "async-std")))]
+    pub async fn create_async_std(&self) -> Result<Vec<async_fs::File>, Error> {
+        let mut files = Vec::new();
+        for path in self.paths() {
+            files.push(open_async_std_file(path, true).await?);
+        }
+        Ok(files)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn document_paths() {
+        let mount_point = Path::new("/run/user/1000/doc");
+        assert_eq!(
+            document(mount_point, Path::new("/run/user/1000/doc/a1b2/notes.txt")),
+            Some((DocumentID::from("a1b2"), PathBuf::from("notes.txt")))
+        );
+        assert_eq!(
+            document(
+                mount_point,
+                Path::new("/run/user/1000/doc/by-app/org.example.App/a1b2/dir/notes.txt")
+            ),
+            Some((DocumentID::from("a1b2"), PathBuf::from("dir/notes.txt")))
+        );
+        assert_eq!(
+            document(mount_point, Path::new("/home/user/notes.txt")),
+            None
+        );
+        assert!(maybe_document(Path::new(
+            "/run/user/1000/doc/a1b2/notes.txt"
+        )));
+        assert!(!maybe_document(Path::new("/home/user/doc")));
+    }
+
+    #[cfg(all(feature = "async-std", feature = "testing"))]
+    #[test]
+    fn create_and_open() {
+        use futures_util::{AsyncReadExt, AsyncWriteExt};
+
+        let path = std::env::temp_dir().join(format!(
+            "ashpd-selected-file-{}-{}",
+            std::process::id(),
+            rand::Rng::gen::<u32>(&mut rand::thread_rng())
+        ));
+        let files = SelectedFiles::default().uri(url::Url::from_file_path(&path).unwrap());
+
+        zbus::block_on(async {
+            let mut created = files.create_async_std().await.unwrap();
+            created[0].write_all(b"notes").await.unwrap();
+            created[0].flush().await.unwrap();
+
+            let mut opened = files.open_async_std().await.unwrap();
+            let mut content = String::new();
+            opened[0].read_to_string(&mut content).await.unwrap();
+            assert_eq!(content, "notes");
+        });
+        std::fs::remove_file(path).unwrap();
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod portal_tests {
+    use std::{collections::HashMap, ffi::OsStr, os::unix::ffi::OsStrExt};
+
+    use super::*;
+    use crate::{testing::FakePortal, FilePath};
+
+    const INTERFACE: &str = "org.freedesktop.portal.Documents";
+
+    #[test]
+    fn host_paths() {
+        zbus::block_on(async {
+            let portal = FakePortal::new().await.unwrap();
+            portal.set_version(INTERFACE, 5);
+            let documents = Documents::with_connection(portal.connection())
+                .await
+                .unwrap();
+
+            let files = SelectedFiles::default()
+                .uri(
+                    "file:///run/user/1000/doc/a1b2/dir/notes.txt"
+                        .parse()
+                        .unwrap(),
+                )
+                .uri("file:///home/user/caf%E9.txt".parse().unwrap())
+                .uri("https://example.org/remote.txt".parse().unwrap());
+            let latin1 = PathBuf::from(OsStr::from_bytes(b"/home/user/caf\xe9.txt"));
+            assert_eq!(
+                files.paths(),
+                [
+                    PathBuf::from("/run/user/1000/doc/a1b2/dir/notes.txt"),
+                    latin1.clone()
+                ]
+            );
+
+            portal.reply(
+                INTERFACE,
+                "GetMountPoint",
+                FilePath::new("/run/user/1000/doc").unwrap(),
+            );
+            portal.reply(
+                INTERFACE,
+                "GetHostPaths",
+                HashMap::from([(
+                    DocumentID::from("a1b2"),
+                    FilePath::new("/home/user/Documents/dir").unwrap(),
+                )]),
+            );
+            assert_eq!(
+                files.host_paths(&documents).await.unwrap(),
+                [PathBuf::from("/home/user/Documents/dir/notes.txt"), latin1]
+            );
+            let call = &portal.calls(INTERFACE, "GetHostPaths")[0];
+            let (ids,) = call.body().deserialize::<(Vec<String>,)>().unwrap();
+            assert_eq!(ids, ["a1b2"]);
+
+            // None of them can be exported by the document portal.
+            let files =
+                SelectedFiles::default().uri("file:///home/user/notes.txt".parse().unwrap());
+            assert_eq!(
+                files.host_paths(&documents).await.unwrap(),
+                [PathBuf::from("/home/user/notes.txt")]
+            );
+            assert_eq!(portal.calls(INTERFACE, "GetMountPoint").len(), 1);
+        });
+    }
+}
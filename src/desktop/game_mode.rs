@@ -83,6 +83,14 @@ impl<'a> GameMode<'a> {
         Ok(Self(proxy))
     }
 
+    /// Create a new instance of [`GameMode`] on the given connection.
+    pub async fn with_connection(connection: &zbus::Connection) -> Result<GameMode<'a>, Error> {
+        let proxy =
+            Proxy::new_desktop_with_connection(connection, "org.freedesktop.portal.GameMode")
+                .await?;
+        Ok(Self(proxy))
+    }
+
     /// Query the GameMode status for a process.
     /// If the caller is running inside a sandbox with pid namespace isolation,
     /// the pid will be translated to the respective host pid.
@@ -157,9 +165,9 @@ impl<'a> GameMode<'a> {
         let status = self.0.call("RegisterGame", &(pid)).await?;
         match status {
             RegisterStatus::Success => Ok(()),
-            RegisterStatus::Rejected => Err(Error::Portal(PortalError::Failed(format!(
-                "Failed to register game for `{pid}`"
-            )))),
+            RegisterStatus::Rejected => {
+                Err(PortalError::Failed(format!("Failed to register game for `{pid}`")).into())
+            }
         }
     }
 
@@ -189,9 +197,9 @@ impl<'a> GameMode<'a> {
             .await?;
         match status {
             RegisterStatus::Success => Ok(()),
-            RegisterStatus::Rejected => Err(Error::Portal(PortalError::Failed(
-                "Failed to register by pidfd".to_string(),
-            ))),
+            RegisterStatus::Rejected => {
+                Err(PortalError::Failed("Failed to register by pidfd".to_string()).into())
+            }
         }
     }
 
@@ -213,9 +221,10 @@ impl<'a> GameMode<'a> {
             .await?;
         match status {
             RegisterStatus::Success => Ok(()),
-            RegisterStatus::Rejected => Err(Error::Portal(PortalError::Failed(format!(
+            RegisterStatus::Rejected => Err(PortalError::Failed(format!(
                 "Failed to register by pid for target=`{target}` requester=`{requester}`"
-            )))),
+            ))
+            .into()),
         }
     }
 
@@ -237,9 +246,9 @@ impl<'a> GameMode<'a> {
         let status = self.0.call("UnregisterGame", &(pid)).await?;
         match status {
             RegisterStatus::Success => Ok(()),
-            RegisterStatus::Rejected => Err(Error::Portal(PortalError::Failed(format!(
-                "Failed to unregister for `{pid}`"
-            )))),
+            RegisterStatus::Rejected => {
+                Err(PortalError::Failed(format!("Failed to unregister for `{pid}`")).into())
+            }
         }
     }
 
@@ -269,9 +278,9 @@ impl<'a> GameMode<'a> {
             .await?;
         match status {
             RegisterStatus::Success => Ok(()),
-            RegisterStatus::Rejected => Err(Error::Portal(PortalError::Failed(
-                "Failed to unregister by pidfd`".to_string(),
-            ))),
+            RegisterStatus::Rejected => {
+                Err(PortalError::Failed("Failed to unregister by pidfd`".to_string()).into())
+            }
         }
     }
 
@@ -294,9 +303,10 @@ impl<'a> GameMode<'a> {
             .await?;
         match status {
             RegisterStatus::Success => Ok(()),
-            RegisterStatus::Rejected => Err(Error::Portal(PortalError::Failed(format!(
+            RegisterStatus::Rejected => Err(PortalError::Failed(format!(
                 "Failed to unregister by pid for target=`{target}` requester=`{requester}`"
-            )))),
+            ))
+            .into()),
         }
     }
 }
@@ -8,7 +8,7 @@ use zbus::zvariant::{
     DeserializeDict, ObjectPath, OwnedObjectPath, OwnedValue, SerializeDict, Type,
 };
 
-use super::{HandleToken, Request, Session};
+use super::{HandleToken, Request, Session, SessionPortal};
 use crate::{desktop::session::CreateSessionResponse, proxy::Proxy, Error, WindowIdentifier};
 
 #[derive(Clone, SerializeDict, Type, Debug, Default)]
@@ -218,21 +218,37 @@ impl<'a> GlobalShortcuts<'a> {
         Ok(Self(proxy))
     }
 
+    /// Create a new instance of [`GlobalShortcuts`] on the given connection.
+    pub async fn with_connection(
+        connection: &zbus::Connection,
+    ) -> Result<GlobalShortcuts<'a>, Error> {
+        let proxy = Proxy::new_desktop_with_connection(
+            connection,
+            "org.freedesktop.portal.GlobalShortcuts",
+        )
+        .await?;
+        Ok(Self(proxy))
+    }
+
     /// Create a global shortcuts session.
     ///
     /// # Specifications
     ///
     /// See also [`CreateSession`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.portal.GlobalShortcuts.html#org-freedesktop-portal-globalshortcuts-createsession).
     #[doc(alias = "CreateSession")]
-    pub async fn create_session(&self) -> Result<Session<'a>, Error> {
+    pub async fn create_session(&self) -> Result<Session<'a, Self>, Error> {
         let options = CreateSessionOptions::default();
         let (request, proxy) = futures_util::try_join!(
             self.0
                 .request::<CreateSessionResponse>(&options.handle_token, "CreateSession", &options)
                 .into_future(),
-            Session::from_unique_name(&options.session_handle_token).into_future(),
+            Session::from_unique_name(self.0.connection(), &options.session_handle_token)
+                .into_future(),
         )?;
-        assert_eq!(proxy.path(), &request.response()?.session_handle.as_ref());
+        assert_eq!(
+            proxy.path(),
+            &request.response().await?.session_handle.as_ref()
+        );
         Ok(proxy)
     }
 
@@ -244,7 +260,7 @@ impl<'a> GlobalShortcuts<'a> {
     #[doc(alias = "BindShortcuts")]
     pub async fn bind_shortcuts(
         &self,
-        session: &Session<'_>,
+        session: &Session<'_, Self>,
         shortcuts: &[NewShortcut],
         parent_window: &WindowIdentifier,
     ) -> Result<Request<BindShortcuts>, Error> {
@@ -266,7 +282,7 @@ impl<'a> GlobalShortcuts<'a> {
     #[doc(alias = "ListShortcuts")]
     pub async fn list_shortcuts(
         &self,
-        session: &Session<'_>,
+        session: &Session<'_, Self>,
     ) -> Result<Request<ListShortcuts>, Error> {
         let options = ListShortcutsOptions::default();
         self.0
@@ -308,6 +324,17 @@ impl<'a> GlobalShortcuts<'a> {
     }
 }
 
+impl super::session::sealed::Sealed for GlobalShortcuts<'_> {}
+impl SessionPortal for GlobalShortcuts<'_> {}
+
+impl crate::resilient::sealed::Sealed for GlobalShortcuts<'_> {
+    fn proxy(&self) -> &Proxy<'_> {
+        &self.0
+    }
+}
+
+impl crate::PortalProxy for GlobalShortcuts<'_> {}
+
 impl<'a> std::ops::Deref for GlobalShortcuts<'a> {
     type Target = zbus::Proxy<'a>;
 
@@ -15,7 +15,7 @@ use zbus::{names::OwnedMemberName, zvariant::Type};
 ///
 /// A valid object path element must only contain the ASCII characters
 /// `[A-Z][a-z][0-9]_`
-#[derive(Serialize, Type)]
+#[derive(Clone, Serialize, Type)]
 pub struct HandleToken(OwnedMemberName);
 
 impl Display for HandleToken {
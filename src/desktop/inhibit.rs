@@ -45,7 +45,7 @@ use serde::Deserialize;
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use zbus::zvariant::{DeserializeDict, ObjectPath, OwnedObjectPath, SerializeDict, Type};
 
-use super::{HandleToken, Request, Session};
+use super::{HandleToken, Request, Session, SessionPortal};
 use crate::{desktop::session::CreateSessionResponse, proxy::Proxy, Error, WindowIdentifier};
 
 #[derive(SerializeDict, Type, Debug, Default)]
@@ -151,6 +151,14 @@ impl<'a> InhibitProxy<'a> {
         Ok(Self(proxy))
     }
 
+    /// Create a new instance of [`InhibitProxy`] on the given connection.
+    pub async fn with_connection(connection: &zbus::Connection) -> Result<InhibitProxy<'a>, Error> {
+        let proxy =
+            Proxy::new_desktop_with_connection(connection, "org.freedesktop.portal.Inhibit")
+                .await?;
+        Ok(Self(proxy))
+    }
+
     /// Creates a monitoring session.
     /// While this session is active, the caller will receive `state_changed`
     /// signals with updates on the session state.
@@ -167,16 +175,20 @@ impl<'a> InhibitProxy<'a> {
     pub async fn create_monitor(
         &self,
         identifier: &WindowIdentifier,
-    ) -> Result<Session<'a>, Error> {
+    ) -> Result<Session<'a, Self>, Error> {
         let options = CreateMonitorOptions::default();
         let body = &(&identifier, &options);
         let (monitor, proxy) = futures_util::try_join!(
             self.0
                 .request::<CreateSessionResponse>(&options.handle_token, "CreateMonitor", body)
                 .into_future(),
-            Session::from_unique_name(&options.session_handle_token).into_future(),
+            Session::from_unique_name(self.0.connection(), &options.session_handle_token)
+                .into_future(),
         )?;
-        assert_eq!(proxy.path(), &monitor.response()?.session_handle.as_ref());
+        assert_eq!(
+            proxy.path(),
+            &monitor.response().await?.session_handle.as_ref()
+        );
         Ok(proxy)
     }
 
@@ -238,11 +250,22 @@ impl<'a> InhibitProxy<'a> {
     /// See also [`QueryEndResponse`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.portal.Inhibit.html#org-freedesktop-portal-inhibit-queryendresponse).
     #[doc(alias = "QueryEndResponse")]
     #[doc(alias = "xdp_portal_session_monitor_query_end_response")]
-    pub async fn query_end_response(&self, session: &Session<'_>) -> Result<(), Error> {
+    pub async fn query_end_response(&self, session: &Session<'_, Self>) -> Result<(), Error> {
         self.0.call("QueryEndResponse", &(session)).await
     }
 }
 
+impl super::session::sealed::Sealed for InhibitProxy<'_> {}
+impl SessionPortal for InhibitProxy<'_> {}
+
+impl crate::resilient::sealed::Sealed for InhibitProxy<'_> {
+    fn proxy(&self) -> &Proxy<'_> {
+        &self.0
+    }
+}
+
+impl crate::PortalProxy for InhibitProxy<'_> {}
+
 impl<'a> std::ops::Deref for InhibitProxy<'a> {
     type Target = zbus::Proxy<'a>;
 
@@ -99,7 +99,7 @@
 //!         .await?;
 //!
 //!     let pos = Position::Left;
-//!     let zones = input_capture.zones(&session).await?.response()?;
+//!     let zones = input_capture.zones(&session).await?.response().await?;
 //!     eprintln!("zones: {zones:?}");
 //!     let barriers = zones
 //!         .regions()
@@ -124,7 +124,7 @@
 //!     let request = input_capture
 //!         .set_pointer_barriers(&session, &barriers, zones.zone_set())
 //!         .await?;
-//!     let response = request.response()?;
+//!     let response = request.response().await?;
 //!     let failed_barrier_ids = response.failed_barriers();
 //!
 //!     eprintln!("failed barrier ids: {:?}", failed_barrier_ids);
@@ -210,7 +210,7 @@
 //!     let mut event_stream = EiConvertEventStream::new(event_stream, response.serial);
 //!
 //!     let pos = Position::Left;
-//!     let zones = input_capture.zones(&session).await?.response()?;
+//!     let zones = input_capture.zones(&session).await?.response().await?;
 //!     eprintln!("zones: {zones:?}");
 //!     let barriers = zones
 //!         .regions()
@@ -235,7 +235,7 @@
 //!     let request = input_capture
 //!         .set_pointer_barriers(&session, &barriers, zones.zone_set())
 //!         .await?;
-//!     let response = request.response()?;
+//!     let response = request.response().await?;
 //!     let failed_barrier_ids = response.failed_barriers();
 //!
 //!     eprintln!("failed barrier ids: {:?}", failed_barrier_ids);
@@ -300,7 +300,14 @@ use zbus::zvariant::{
     self, DeserializeDict, ObjectPath, OwnedObjectPath, OwnedValue, SerializeDict, Type, Value,
 };
 
-use super::{HandleToken, Request, Session};
+mod barriers;
+#[cfg(feature = "eis")]
+mod events;
+pub use self::barriers::{BarrierLayout, Edge, EdgeSpec, PlacedBarrier};
+#[cfg(feature = "eis")]
+#[cfg_attr(docsrs, doc(cfg(feature = "eis")))]
+pub use self::events::{CapturedEvent, InputEvent};
+use super::{HandleToken, Request, Session, SessionPortal};
 use crate::{proxy::Proxy, Error, WindowIdentifier};
 
 #[derive(Serialize_repr, Deserialize_repr, PartialEq, Eq, Debug, Copy, Clone, Type)]
@@ -504,6 +511,20 @@ impl Zones {
     }
 }
 
+#[cfg(test)]
+impl Zones {
+    /// Zones of `(width, height, x_offset, y_offset)` regions.
+    pub(crate) fn new(regions: &[(u32, u32, i32, i32)], zone_set: u32) -> Self {
+        Self {
+            zones: regions
+                .iter()
+                .map(|&(width, height, x, y)| Region(width, height, x, y))
+                .collect(),
+            zone_set,
+        }
+    }
+}
+
 /// A barrier ID.
 pub type BarrierID = u32;
 
@@ -550,6 +571,14 @@ impl<'a> InputCapture<'a> {
         Ok(Self(proxy))
     }
 
+    /// Create a new instance of [`InputCapture`] on the given connection.
+    pub async fn with_connection(connection: &zbus::Connection) -> Result<InputCapture<'a>, Error> {
+        let proxy =
+            Proxy::new_desktop_with_connection(connection, "org.freedesktop.portal.InputCapture")
+                .await?;
+        Ok(Self(proxy))
+    }
+
     /// Create an input capture session.
     ///
     /// # Specifications
@@ -559,7 +588,7 @@ impl<'a> InputCapture<'a> {
         &self,
         parent_window: &WindowIdentifier,
         capabilities: BitFlags<Capabilities>,
-    ) -> Result<(Session<'_>, BitFlags<Capabilities>), Error> {
+    ) -> Result<(Session<'_, Self>, BitFlags<Capabilities>), Error> {
         let options = CreateSessionOptions {
             handle_token: Default::default(),
             session_handle_token: Default::default(),
@@ -574,9 +603,10 @@ impl<'a> InputCapture<'a> {
                     (parent_window, &options)
                 )
                 .into_future(),
-            Session::from_unique_name(&options.session_handle_token).into_future(),
+            Session::from_unique_name(self.0.connection(), &options.session_handle_token)
+                .into_future(),
         )?;
-        let response = request.response()?;
+        let response = request.response().await?;
         assert_eq!(proxy.path(), &response.session_handle.as_ref());
         Ok((proxy, response.capabilities))
     }
@@ -587,7 +617,7 @@ impl<'a> InputCapture<'a> {
     ///
     /// See also [`GetZones`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.portal.InputCapture.html#org-freedesktop-portal-inputcapture-getzones).
     #[doc(alias = "GetZones")]
-    pub async fn zones(&self, session: &Session<'_>) -> Result<Request<Zones>, Error> {
+    pub async fn zones(&self, session: &Session<'_, Self>) -> Result<Request<Zones>, Error> {
         let options = GetZonesOptions::default();
         self.0
             .request(&options.handle_token, "GetZones", (session, &options))
@@ -602,7 +632,7 @@ impl<'a> InputCapture<'a> {
     #[doc(alias = "SetPointerBarriers")]
     pub async fn set_pointer_barriers(
         &self,
-        session: &Session<'_>,
+        session: &Session<'_, Self>,
         barriers: &[Barrier],
         zone_set: u32,
     ) -> Result<Request<SetPointerBarriersResponse>, Error> {
@@ -621,7 +651,7 @@ impl<'a> InputCapture<'a> {
     /// # Specifications
     ///
     /// See also [`Enable`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.portal.InputCapture.html#org-freedesktop-portal-inputcapture-enable).
-    pub async fn enable(&self, session: &Session<'_>) -> Result<(), Error> {
+    pub async fn enable(&self, session: &Session<'_, Self>) -> Result<(), Error> {
         let options = EnableOptions::default();
         self.0.call("Enable", &(session, &options)).await
     }
@@ -631,7 +661,7 @@ impl<'a> InputCapture<'a> {
     /// # Specifications
     ///
     /// See also [`Disable`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.portal.InputCapture.html#org-freedesktop-portal-inputcapture-disable).
-    pub async fn disable(&self, session: &Session<'_>) -> Result<(), Error> {
+    pub async fn disable(&self, session: &Session<'_, Self>) -> Result<(), Error> {
         let options = DisableOptions::default();
         self.0.call("Disable", &(session, &options)).await
     }
@@ -643,7 +673,7 @@ impl<'a> InputCapture<'a> {
     /// See also [`Release`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.portal.InputCapture.html#org-freedesktop-portal-inputcapture-release).
     pub async fn release(
         &self,
-        session: &Session<'_>,
+        session: &Session<'_, Self>,
         activation_id: u32,
         cursor_position: (f64, f64),
     ) -> Result<(), Error> {
@@ -660,7 +690,7 @@ impl<'a> InputCapture<'a> {
     ///
     /// See also [`ConnectToEIS`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.portal.InputCapture.html#org-freedesktop-portal-inputcapture-connecttoeis).
     #[doc(alias = "ConnectToEIS")]
-    pub async fn connect_to_eis(&self, session: &Session<'_>) -> Result<OwnedFd, Error> {
+    pub async fn connect_to_eis(&self, session: &Session<'_, Self>) -> Result<OwnedFd, Error> {
         // `ConnectToEIS` doesn't take any options for now
         let options: HashMap<&str, Value<'_>> = HashMap::new();
         let fd = self
@@ -724,6 +754,17 @@ impl<'a> InputCapture<'a> {
     }
 }
 
+impl super::session::sealed::Sealed for InputCapture<'_> {}
+impl SessionPortal for InputCapture<'_> {}
+
+impl crate::resilient::sealed::Sealed for InputCapture<'_> {
+    fn proxy(&self) -> &Proxy<'_> {
+        &self.0
+    }
+}
+
+impl crate::PortalProxy for InputCapture<'_> {}
+
 impl<'a> std::ops::Deref for InputCapture<'a> {
     type Target = zbus::Proxy<'a>;
 
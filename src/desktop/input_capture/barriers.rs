@@ -0,0 +1,486 @@
+use std::future::ready;
+
+use futures_util::{stream, Stream, StreamExt};
+
+use super::{Barrier, BarrierID, InputCapture, Region, Zones};
+use crate::{desktop::Session, Error};
+
+/// An edge of a [`Region`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Edge {
+    /// The left edge.
+    Left,
+    /// The right edge.
+    Right,
+    /// The top edge.
+    Top,
+    /// The bottom edge.
+    Bottom,
+}
+
+impl Edge {
+    /// The edge on the other side, e.g. [`Edge::Right`] for [`Edge::Left`].
+    pub fn opposite(self) -> Self {
+        match self {
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+            Self::Top => Self::Bottom,
+            Self::Bottom => Self::Top,
+        }
+    }
+}
+
+/// The edges a [`BarrierLayout`] places barriers on.
+///
+/// Only the parts of the edges that aren't shared with another region get a
+/// barrier, as the compositor denies the barriers between two regions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EdgeSpec {
+    /// The edge of the outermost regions on that side, e.g. the left edge of
+    /// the leftmost monitor with `EdgeSpec::Outermost(Edge::Left)`.
+    Outermost(Edge),
+    /// All the outer edges of the regions.
+    AllOuter,
+    /// The edge of the region at the given index of [`Zones::regions`].
+    Region(usize, Edge),
+}
+
+/// A barrier placed by a [`BarrierLayout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlacedBarrier {
+    id: BarrierID,
+    edge: Edge,
+    region: usize,
+    position: (i32, i32, i32, i32),
+}
+
+impl PlacedBarrier {
+    /// The id of the barrier, as in
+    /// [`Activated::barrier_id`](super::Activated::barrier_id).
+    pub fn id(&self) -> BarrierID {
+        self.id
+    }
+
+    /// The edge the barrier is on.
+    pub fn edge(&self) -> Edge {
+        self.edge
+    }
+
+    /// The index of the region in [`Zones::regions`] the barrier is on the
+    /// edge of.
+    pub fn region(&self) -> usize {
+        self.region
+    }
+
+    /// The position of the barrier, `(x1, y1, x2, y2)` as in [`Barrier::new`].
+    pub fn position(&self) -> (i32, i32, i32, i32) {
+        self.position
+    }
+}
+
+/// A [builder-pattern] type to derive the pointer barriers from [`Zones`].
+///
+/// Barriers get the ids 1, 2 and so on, [`BarrierLayout::barrier`] maps them
+/// back to their edge. Call [`InputCapture::set_barrier_layout`] to set them
+/// up, and [`InputCapture::track_barrier_layout`] to rebuild them whenever the
+/// zones change.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use ashpd::desktop::input_capture::{
+///     BarrierLayout, Capabilities, Edge, EdgeSpec, InputCapture,
+/// };
+///
+/// async fn run() -> ashpd::Result<()> {
+///     let input_capture = InputCapture::new().await?;
+///     let (session, _capabilities) = input_capture
+///         .create_session(
+///             &ashpd::WindowIdentifier::default(),
+///             Capabilities::Pointer.into(),
+///         )
+///         .await?;
+///
+///     let zones = input_capture.zones(&session).await?.response().await?;
+///     let mut layout = BarrierLayout::new(&zones).edges(EdgeSpec::Outermost(Edge::Left));
+///     input_capture
+///         .set_barrier_layout(&session, &mut layout)
+///         .await?;
+///     for barrier in layout.failed_barriers() {
+///         eprintln!("denied barrier: {barrier:?}");
+///     }
+///     Ok(())
+/// }
+/// ```
+///
+/// [builder-pattern]: https://doc.rust-lang.org/1.0.0/style/ownership/builders.html
+#[derive(Debug, Clone)]
+pub struct BarrierLayout {
+    specs: Vec<EdgeSpec>,
+    regions: Vec<Region>,
+    zone_set: u32,
+    barriers: Vec<PlacedBarrier>,
+    failed: Vec<BarrierID>,
+}
+
+impl BarrierLayout {
+    /// Create a layout without barriers for `zones`.
+    pub fn new(zones: &Zones) -> Self {
+        Self {
+            specs: Vec::new(),
+            regions: zones.regions().to_vec(),
+            zone_set: zones.zone_set(),
+            barriers: Vec::new(),
+            failed: Vec::new(),
+        }
+    }
+
+    /// Place barriers on the edges of `spec`.
+    #[must_use]
+    pub fn edges(mut self, spec: EdgeSpec) -> Self {
+        self.specs.push(spec);
+        self.place();
+        self
+    }
+
+    /// Place the barriers again for new `zones`.
+    pub fn rebuild(&mut self, zones: &Zones) {
+        self.regions = zones.regions().to_vec();
+        self.zone_set = zones.zone_set();
+        self.place();
+    }
+
+    /// The zone set the barriers were placed for.
+    pub fn zone_set(&self) -> u32 {
+        self.zone_set
+    }
+
+    /// The barriers, to pass to [`InputCapture::set_pointer_barriers`].
+    pub fn barriers(&self) -> Vec<Barrier> {
+        self.barriers
+            .iter()
+            .map(|barrier| Barrier::new(barrier.id, barrier.position))
+            .collect()
+    }
+
+    /// The placed barriers.
+    pub fn placed_barriers(&self) -> &[PlacedBarrier] {
+        &self.barriers
+    }
+
+    /// The barrier with the given id.
+    pub fn barrier(&self, id: BarrierID) -> Option<&PlacedBarrier> {
+        self.barriers.iter().find(|barrier| barrier.id == id)
+    }
+
+    /// The barriers denied by the compositor the last time the layout was set
+    /// up.
+    pub fn failed_barriers(&self) -> impl Iterator<Item = &PlacedBarrier> {
+        self.barriers
+            .iter()
+            .filter(|barrier| self.failed.contains(&barrier.id))
+    }
+
+    fn place(&mut self) {
+        self.failed.clear();
+        let mut barriers: Vec<PlacedBarrier> = Vec::new();
+        for spec in &self.specs {
+            for (region, edge) in self.spec_edges(*spec) {
+                for position in outer_segments(&self.regions, region, edge) {
+                    // Specs may overlap.
+                    if barriers.iter().any(|barrier| barrier.position == position) {
+                        continue;
+                    }
+                    barriers.push(PlacedBarrier {
+                        id: barriers.len() as BarrierID + 1,
+                        edge,
+                        region,
+                        position,
+                    });
+                }
+            }
+        }
+        self.barriers = barriers;
+    }
+
+    /// The edges of the regions selected by `spec`.
+    fn spec_edges(&self, spec: EdgeSpec) -> Vec<(usize, Edge)> {
+        const EDGES: [Edge; 4] = [Edge::Left, Edge::Right, Edge::Top, Edge::Bottom];
+        let indices = 0..self.regions.len();
+        match spec {
+            EdgeSpec::Outermost(edge) => {
+                let outermost = indices
+                    .clone()
+                    .map(|index| edge_line(self.regions[index], edge))
+                    .reduce(|a, b| match edge {
+                        Edge::Left | Edge::Top => a.min(b),
+                        Edge::Right | Edge::Bottom => a.max(b),
+                    });
+                indices
+                    .filter(|index| Some(edge_line(self.regions[*index], edge)) == outermost)
+                    .map(|index| (index, edge))
+                    .collect()
+            }
+            EdgeSpec::AllOuter => indices
+                .flat_map(|index| EDGES.map(|edge| (index, edge)))
+                .collect(),
+            EdgeSpec::Region(index, edge) if index < self.regions.len() => vec![(index, edge)],
+            EdgeSpec::Region(..) => Vec::new(),
+        }
+    }
+}
+
+/// The coordinate of the line of `edge`: x for vertical edges, y for
+/// horizontal ones.
+fn edge_line(region: Region, edge: Edge) -> i32 {
+    match edge {
+        Edge::Left => region.x_offset(),
+        Edge::Right => region.x_offset() + region.width() as i32,
+        Edge::Top => region.y_offset(),
+        Edge::Bottom => region.y_offset() + region.height() as i32,
+    }
+}
+
+/// The half-open range `edge` spans along its line.
+fn edge_span(region: Region, edge: Edge) -> (i32, i32) {
+    match edge {
+        Edge::Left | Edge::Right => (
+            region.y_offset(),
+            region.y_offset() + region.height() as i32,
+        ),
+        Edge::Top | Edge::Bottom => (region.x_offset(), region.x_offset() + region.width() as i32),
+    }
+}
+
+/// The barriers on the parts of the edge of `regions[index]` that aren't
+/// shared with another region.
+fn outer_segments(regions: &[Region], index: usize, edge: Edge) -> Vec<(i32, i32, i32, i32)> {
+    let region = regions[index];
+    let line = edge_line(region, edge);
+    let mut shared = regions
+        .iter()
+        .enumerate()
+        .filter(|(other, neighbour)| {
+            *other != index && edge_line(**neighbour, edge.opposite()) == line
+        })
+        .map(|(_, neighbour)| edge_span(*neighbour, edge.opposite()))
+        .collect::<Vec<_>>();
+    shared.sort_unstable();
+
+    let (mut start, end) = edge_span(region, edge);
+    let mut segments = Vec::new();
+    for (shared_start, shared_end) in shared {
+        if shared_start >= end {
+            break;
+        }
+        if shared_start > start {
+            segments.push((start, shared_start));
+        }
+        start = start.max(shared_end);
+    }
+    if start < end {
+        segments.push((start, end));
+    }
+
+    // The end points of barriers are inclusive.
+    segments
+        .into_iter()
+        .map(|(start, end)| match edge {
+            Edge::Left | Edge::Right => (line, start, line, end - 1),
+            Edge::Top | Edge::Bottom => (start, line, end - 1, line),
+        })
+        .collect()
+}
+
+impl InputCapture<'_> {
+    /// Set up the barriers of `layout`, recording the ones denied by the
+    /// compositor in [`BarrierLayout::failed_barriers`].
+    ///
+    /// **Note** some compositors, e.g. GNOME, require the session to be
+    /// [disabled](InputCapture::disable) to change its barriers.
+    pub async fn set_barrier_layout(
+        &self,
+        session: &Session<'_, Self>,
+        layout: &mut BarrierLayout,
+    ) -> Result<(), Error> {
+        let response = self
+            .set_pointer_barriers(session, &layout.barriers(), layout.zone_set())
+            .await?
+            .response()
+            .await?;
+        layout.failed = response.failed_barriers().to_vec();
+        Ok(())
+    }
+
+    /// Rebuild and set up the barriers of `layout` whenever the zones of the
+    /// session change, with the new zones and their zone set.
+    ///
+    /// The stream returns the layout each time its barriers were set up.
+    pub async fn track_barrier_layout<'s>(
+        &'s self,
+        session: &'s Session<'_, Self>,
+        layout: BarrierLayout,
+    ) -> Result<impl Stream<Item = Result<BarrierLayout, Error>> + 's, Error> {
+        let session_path = session.path().to_owned();
+        let zones_changed = self
+            .receive_zones_changed()
+            .await?
+            .filter(move |changed| ready(changed.session_handle() == session_path));
+        Ok(stream::unfold(
+            (Box::pin(zones_changed), layout),
+            move |(mut zones_changed, mut layout)| async move {
+                zones_changed.next().await?;
+                let result = async {
+                    let zones = self.zones(session).await?.response().await?;
+                    layout.rebuild(&zones);
+                    self.set_barrier_layout(session, &mut layout).await
+                }
+                .await;
+                let item = result.map(|()| layout.clone());
+                Some((item, (zones_changed, layout)))
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn positions(layout: &BarrierLayout) -> Vec<(i32, i32, i32, i32)> {
+        layout
+            .placed_barriers()
+            .iter()
+            .map(PlacedBarrier::position)
+            .collect()
+    }
+
+    #[test]
+    fn placement() {
+        // A 1920x1080 monitor, and a taller one on its right.
+        let zones = Zones::new(&[(1920, 1080, 0, 0), (1280, 1440, 1920, 0)], 1);
+
+        let layout = BarrierLayout::new(&zones).edges(EdgeSpec::Outermost(Edge::Left));
+        assert_eq!(positions(&layout), [(0, 0, 0, 1079)]);
+        let layout = BarrierLayout::new(&zones).edges(EdgeSpec::Outermost(Edge::Bottom));
+        assert_eq!(positions(&layout), [(1920, 1440, 3199, 1440)]);
+
+        let layout = BarrierLayout::new(&zones)
+            .edges(EdgeSpec::AllOuter)
+            .edges(EdgeSpec::Region(0, Edge::Left));
+        assert_eq!(
+            positions(&layout),
+            [
+                (0, 0, 0, 1079),
+                (0, 0, 1919, 0),
+                (0, 1080, 1919, 1080),
+                // Below the first monitor.
+                (1920, 1080, 1920, 1439),
+                (3200, 0, 3200, 1439),
+                (1920, 0, 3199, 0),
+                (1920, 1440, 3199, 1440),
+            ]
+        );
+        let barrier = layout.barrier(4).unwrap();
+        assert_eq!((barrier.edge(), barrier.region()), (Edge::Left, 1));
+        assert!(layout.barrier(8).is_none());
+
+        // The shared edge has no outer part.
+        let layout = BarrierLayout::new(&zones).edges(EdgeSpec::Region(0, Edge::Right));
+        assert!(layout.barriers().is_empty());
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod portal_tests {
+    use std::collections::HashMap;
+
+    use zbus::zvariant::{ObjectPath, OwnedValue, Value};
+
+    use super::*;
+    use crate::{desktop::Response, testing::FakePortal};
+
+    const INTERFACE: &str = "org.freedesktop.portal.InputCapture";
+
+    fn zones_response(
+        regions: Vec<(u32, u32, i32, i32)>,
+        zone_set: u32,
+    ) -> HashMap<String, OwnedValue> {
+        HashMap::from([
+            ("zones".to_owned(), Value::from(regions).try_into().unwrap()),
+            ("zone_set".to_owned(), OwnedValue::from(zone_set)),
+        ])
+    }
+
+    #[test]
+    fn tracked_layout() {
+        zbus::block_on(async {
+            let portal = FakePortal::new().await.unwrap();
+            let proxy = InputCapture::with_connection(portal.connection())
+                .await
+                .unwrap();
+            let session = portal.session().await.unwrap();
+
+            let zones = Zones::new(&[(1920, 1080, 0, 0)], 1);
+            let layout = BarrierLayout::new(&zones).edges(EdgeSpec::Outermost(Edge::Right));
+            let layouts = proxy.track_barrier_layout(&session, layout).await.unwrap();
+            futures_util::pin_mut!(layouts);
+
+            portal.respond(
+                INTERFACE,
+                "GetZones",
+                Response::ok(zones_response(
+                    vec![(1920, 1080, 0, 0), (1920, 1080, 1920, 0)],
+                    2,
+                )),
+            );
+            portal.respond(
+                INTERFACE,
+                "SetPointerBarriers",
+                Response::ok(HashMap::from([(
+                    "failed_barriers".to_owned(),
+                    OwnedValue::try_from(Value::from(vec![1u32])).unwrap(),
+                )])),
+            );
+            let details = HashMap::from([("zone_set", Value::from(1u32))]);
+            portal
+                .emit_signal(
+                    INTERFACE,
+                    "ZonesChanged",
+                    &(
+                        ObjectPath::try_from(FakePortal::SESSION_PATH).unwrap(),
+                        details,
+                    ),
+                )
+                .await
+                .unwrap();
+
+            let layout = layouts.next().await.unwrap().unwrap();
+            assert_eq!(layout.zone_set(), 2);
+            assert_eq!(
+                layout.placed_barriers()[0].position(),
+                (3840, 0, 3840, 1079)
+            );
+            assert_eq!(
+                layout
+                    .failed_barriers()
+                    .map(PlacedBarrier::id)
+                    .collect::<Vec<_>>(),
+                [1]
+            );
+
+            let calls = portal.calls(INTERFACE, "SetPointerBarriers");
+            let (_, _, barriers, zone_set) = calls[0]
+                .body()
+                .deserialize::<(
+                    ObjectPath<'_>,
+                    HashMap<String, OwnedValue>,
+                    Vec<HashMap<String, OwnedValue>>,
+                    u32,
+                )>()
+                .unwrap();
+            assert_eq!(zone_set, 2);
+            assert_eq!(barriers.len(), 1);
+        });
+    }
+}
@@ -0,0 +1,550 @@
+use std::{collections::HashMap, future::ready};
+
+use futures_util::{
+    future::Either,
+    stream::{self, PollNext},
+    Stream, StreamExt,
+};
+use reis::{
+    ei,
+    event::{Device, EiEvent, Keymap},
+};
+
+use super::{Activated, BarrierID, InputCapture};
+use crate::{
+    desktop::{remote_desktop::KeyState, Session},
+    eis::{EiConnection, EisError, CAPABILITIES, DEFAULT_NAME},
+    Error,
+};
+
+/// An input event captured by an [`InputCapture`] session.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum InputEvent {
+    /// The pointer moved by (`dx`, `dy`) logical pixels.
+    PointerMotion {
+        /// The horizontal motion.
+        dx: f32,
+        /// The vertical motion.
+        dy: f32,
+    },
+    /// The pointer moved to (`x`, `y`), in logical pixels within one of the
+    /// regions of the device.
+    PointerMotionAbsolute {
+        /// The horizontal position.
+        x: f32,
+        /// The vertical position.
+        y: f32,
+    },
+    /// A button was pressed or released.
+    Button {
+        /// The button code of `linux/input-event-codes.h`, e.g. 272 for
+        /// `BTN_LEFT`.
+        button: u32,
+        /// Whether the button was pressed or released.
+        state: KeyState,
+    },
+    /// Scrolled by (`dx`, `dy`) logical pixels.
+    Scroll {
+        /// The horizontal scrolling.
+        dx: f32,
+        /// The vertical scrolling.
+        dy: f32,
+    },
+    /// Scrolled by discrete steps, in fractions of 120 for a mouse wheel
+    /// click.
+    ScrollDiscrete {
+        /// The horizontal steps.
+        dx: i32,
+        /// The vertical steps.
+        dy: i32,
+    },
+    /// A scroll sequence ended on the given axes, e.g. the fingers were lifted
+    /// off a touchpad.
+    ScrollStop {
+        /// Whether the horizontal scrolling stopped.
+        x: bool,
+        /// Whether the vertical scrolling stopped.
+        y: bool,
+    },
+    /// A scroll sequence was cancelled on the given axes.
+    ScrollCancel {
+        /// Whether the horizontal scrolling was cancelled.
+        x: bool,
+        /// Whether the vertical scrolling was cancelled.
+        y: bool,
+    },
+    /// A key was pressed or released.
+    Key {
+        /// The key code of `linux/input-event-codes.h`, e.g. 30 for `KEY_A`.
+        /// See [`CapturedEvent::keymap`] to translate it.
+        key: u32,
+        /// Whether the key was pressed or released.
+        state: KeyState,
+    },
+    /// The touch point `id` was put down at (`x`, `y`), in logical pixels.
+    TouchDown {
+        /// The touch point.
+        id: u32,
+        /// The horizontal position.
+        x: f32,
+        /// The vertical position.
+        y: f32,
+    },
+    /// The touch point `id` moved to (`x`, `y`), in logical pixels.
+    TouchMotion {
+        /// The touch point.
+        id: u32,
+        /// The horizontal position.
+        x: f32,
+        /// The vertical position.
+        y: f32,
+    },
+    /// The touch point `id` was lifted.
+    TouchUp {
+        /// The touch point.
+        id: u32,
+    },
+}
+
+/// An [`InputEvent`] along with the activation it was captured in, returned
+/// by [`InputCapture::events`].
+#[derive(Debug, Clone)]
+pub struct CapturedEvent {
+    event: InputEvent,
+    device: Device,
+    time: u64,
+    activation_id: u32,
+    cursor_position: Option<(f32, f32)>,
+    barrier_id: Option<BarrierID>,
+}
+
+impl CapturedEvent {
+    /// The input event.
+    pub fn event(&self) -> InputEvent {
+        self.event
+    }
+
+    /// The device the event was captured from.
+    pub fn device(&self) -> &Device {
+        &self.device
+    }
+
+    /// The keymap of the device, to translate the key codes of
+    /// [`InputEvent::Key`].
+    pub fn keymap(&self) -> Option<&Keymap> {
+        self.device.keymap()
+    }
+
+    /// The time of the event in microseconds of `CLOCK_MONOTONIC`.
+    pub fn time(&self) -> u64 {
+        self.time
+    }
+
+    /// The id of the activation the event was captured in, same as
+    /// [`Activated::activation_id`].
+    pub fn activation_id(&self) -> u32 {
+        self.activation_id
+    }
+
+    /// The cursor position when the capture was activated, see
+    /// [`Activated::cursor_position`].
+    ///
+    /// `None` if the [`Activated`] signal of the activation wasn't received
+    /// before the event.
+    pub fn cursor_position(&self) -> Option<(f32, f32)> {
+        self.cursor_position
+    }
+
+    /// The barrier that triggered the activation, see
+    /// [`Activated::barrier_id`].
+    ///
+    /// `None` if the [`Activated`] signal of the activation wasn't received
+    /// before the event.
+    pub fn barrier_id(&self) -> Option<BarrierID> {
+        self.barrier_id
+    }
+}
+
+/// The state of the [`InputCapture::events`] stream.
+#[derive(Debug, Default)]
+struct Capture {
+    /// The last activation of the session.
+    activated: Option<Activated>,
+    /// The activation id of the devices between their start and stop of
+    /// emulation.
+    emulating: HashMap<Device, u32>,
+    done: bool,
+}
+
+impl Capture {
+    /// `None` ends the stream, `Some(None)` skips the item.
+    fn handle(
+        &mut self,
+        item: Either<Activated, Result<EiEvent, Error>>,
+    ) -> Option<Option<Result<CapturedEvent, Error>>> {
+        if self.done {
+            return None;
+        }
+        let result = match item {
+            Either::Left(activated) => {
+                self.activated = Some(activated);
+                return Some(None);
+            }
+            Either::Right(event) => event.and_then(|event| self.handle_event(event)),
+        };
+        self.done = result.is_err();
+        Some(result.transpose())
+    }
+
+    fn handle_event(&mut self, event: EiEvent) -> Result<Option<CapturedEvent>, Error> {
+        let (device, time, event) = match event {
+            EiEvent::Disconnected(event) => {
+                return Err(EisError::Disconnected(event.explanation).into())
+            }
+            // The sequence of the emulation is the activation id of the
+            // portal.
+            EiEvent::DeviceStartEmulating(event) => {
+                self.emulating.insert(event.device, event.sequence);
+                return Ok(None);
+            }
+            EiEvent::DeviceStopEmulating(event) => {
+                self.emulating.remove(&event.device);
+                return Ok(None);
+            }
+            EiEvent::DeviceRemoved(event) => {
+                self.emulating.remove(&event.device);
+                return Ok(None);
+            }
+            EiEvent::PointerMotion(event) => (
+                event.device,
+                event.time,
+                InputEvent::PointerMotion {
+                    dx: event.dx,
+                    dy: event.dy,
+                },
+            ),
+            EiEvent::PointerMotionAbsolute(event) => (
+                event.device,
+                event.time,
+                InputEvent::PointerMotionAbsolute {
+                    x: event.dx_absolute,
+                    y: event.dy_absolute,
+                },
+            ),
+            EiEvent::Button(event) => (
+                event.device,
+                event.time,
+                InputEvent::Button {
+                    button: event.button,
+                    state: match event.state {
+                        ei::button::ButtonState::Press => KeyState::Pressed,
+                        ei::button::ButtonState::Released => KeyState::Released,
+                    },
+                },
+            ),
+            EiEvent::ScrollDelta(event) => (
+                event.device,
+                event.time,
+                InputEvent::Scroll {
+                    dx: event.dx,
+                    dy: event.dy,
+                },
+            ),
+            EiEvent::ScrollDiscrete(event) => (
+                event.device,
+                event.time,
+                InputEvent::ScrollDiscrete {
+                    dx: event.discrete_dx,
+                    dy: event.discrete_dy,
+                },
+            ),
+            EiEvent::ScrollStop(event) => (
+                event.device,
+                event.time,
+                InputEvent::ScrollStop {
+                    x: event.x,
+                    y: event.y,
+                },
+            ),
+            EiEvent::ScrollCancel(event) => (
+                event.device,
+                event.time,
+                InputEvent::ScrollCancel {
+                    x: event.x,
+                    y: event.y,
+                },
+            ),
+            EiEvent::KeyboardKey(event) => (
+                event.device,
+                event.time,
+                InputEvent::Key {
+                    key: event.key,
+                    state: match event.state {
+                        ei::keyboard::KeyState::Press => KeyState::Pressed,
+                        ei::keyboard::KeyState::Released => KeyState::Released,
+                    },
+                },
+            ),
+            EiEvent::TouchDown(event) => (
+                event.device,
+                event.time,
+                InputEvent::TouchDown {
+                    id: event.touch_id,
+                    x: event.x,
+                    y: event.y,
+                },
+            ),
+            EiEvent::TouchMotion(event) => (
+                event.device,
+                event.time,
+                InputEvent::TouchMotion {
+                    id: event.touch_id,
+                    x: event.x,
+                    y: event.y,
+                },
+            ),
+            EiEvent::TouchUp(event) => (
+                event.device,
+                event.time,
+                InputEvent::TouchUp { id: event.touch_id },
+            ),
+            _ => return Ok(None),
+        };
+        // Events are only sent while emulating.
+        let Some(&activation_id) = self.emulating.get(&device) else {
+            return Ok(None);
+        };
+        let activated = self
+            .activated
+            .as_ref()
+            .filter(|activated| activated.activation_id() == activation_id);
+        Ok(Some(CapturedEvent {
+            event,
+            device,
+            time,
+            activation_id,
+            cursor_position: activated.map(Activated::cursor_position),
+            barrier_id: activated.map(Activated::barrier_id),
+        }))
+    }
+}
+
+impl InputCapture<'_> {
+    /// The input events captured by the session.
+    ///
+    /// Connects to the EIS socket of the session, binds every capability of
+    /// its seats and decodes the events of the devices. Each event carries
+    /// the id of the activation it was captured in, along with the cursor
+    /// position and the barrier of the corresponding [`Activated`] signal.
+    ///
+    /// The stream ends after the first error, e.g. when the EIS server
+    /// disconnects.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use ashpd::desktop::{
+    ///     input_capture::{InputCapture, InputEvent},
+    ///     Session,
+    /// };
+    /// use futures_util::StreamExt;
+    ///
+    /// async fn run(
+    ///     proxy: &InputCapture<'_>,
+    ///     session: &Session<'_, InputCapture<'_>>,
+    /// ) -> ashpd::Result<()> {
+    ///     let events = proxy.events(session).await?;
+    ///     proxy.enable(session).await?;
+    ///     futures_util::pin_mut!(events);
+    ///     while let Some(event) = events.next().await {
+    ///         let event = event?;
+    ///         if let InputEvent::PointerMotion { dx, dy } = event.event() {
+    ///             println!(
+    ///                 "Moved by ({dx}, {dy}) since crossing barrier {:?} at {:?}",
+    ///                 event.barrier_id(),
+    ///                 event.cursor_position()
+    ///             );
+    ///         }
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn events(
+        &self,
+        session: &Session<'_, Self>,
+    ) -> Result<impl Stream<Item = Result<CapturedEvent, Error>>, Error> {
+        let session_path = session.path().to_owned();
+        let activated = self
+            .receive_activated()
+            .await?
+            .filter(move |activated| ready(activated.session_handle() == session_path));
+        let fd = self.connect_to_eis(session).await?;
+        let connection =
+            EiConnection::new(fd, DEFAULT_NAME, ei::handshake::ContextType::Receiver).await?;
+        let events = stream::try_unfold(connection, |mut connection| async move {
+            let event = connection.next_event().await?;
+            if let EiEvent::SeatAdded(event) = &event {
+                event.seat.bind_capabilities(CAPABILITIES);
+                connection.flush()?;
+            }
+            Ok(Some((event, connection)))
+        });
+        // The signals are handled first, so that the activation is known by
+        // the time its events are read.
+        Ok(stream::select_with_strategy(
+            activated.map(Either::Left),
+            events.map(Either::Right),
+            |_: &mut ()| PollNext::Left,
+        )
+        .scan(Capture::default(), |capture, item| {
+            ready(capture.handle(item))
+        })
+        .filter_map(ready))
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use std::sync::mpsc;
+
+    use reis::{eis, request::EisRequest};
+    use zbus::zvariant::{self, ObjectPath, Value};
+
+    use super::*;
+    use crate::{eis::tests::Server, testing::FakePortal};
+
+    const INTERFACE: &str = "org.freedesktop.portal.InputCapture";
+
+    fn activated(
+        path: &str,
+        activation_id: u32,
+        cursor_position: (f64, f64),
+    ) -> (ObjectPath<'_>, HashMap<&str, Value<'_>>) {
+        (
+            ObjectPath::try_from(path).unwrap(),
+            HashMap::from([
+                ("activation_id", Value::from(activation_id)),
+                ("cursor_position", Value::from(cursor_position)),
+                ("barrier_id", Value::from(3u32)),
+            ]),
+        )
+    }
+
+    #[test]
+    fn captured_events() {
+        let (ready_tx, ready_rx) = mpsc::channel();
+        let (fd, server) = Server::spawn(move |mut server| {
+            let EisRequest::Bind(_) = server.next_request() else {
+                panic!("Expected the seat to be bound");
+            };
+            ready_rx.recv().unwrap();
+
+            let serial = server.converter.next_serial();
+            let device = server.seat.eis_seat().device(1);
+            device.name("pointer");
+            device.device_type(eis::device::DeviceType::Virtual);
+            let pointer = device.interface::<eis::Pointer>(1);
+            let button = device.interface::<eis::Button>(1);
+            device.done();
+            device.resumed(serial);
+            device.start_emulating(serial, 7);
+            pointer.motion_relative(1.0, 2.0);
+            device.frame(serial, 1000);
+            button.button(272, eis::button::ButtonState::Press);
+            device.frame(serial, 2000);
+            device.stop_emulating(serial);
+            // Outside of an activation.
+            pointer.motion_relative(5.0, 5.0);
+            device.frame(serial, 3000);
+            device.start_emulating(serial, 8);
+            pointer.motion_relative(3.0, 4.0);
+            device.frame(serial, 4000);
+            server.flush();
+            // Keep the connection open until the client is done.
+            server
+        });
+
+        zbus::block_on(async {
+            let portal = FakePortal::new().await.unwrap();
+            let proxy = InputCapture::with_connection(portal.connection())
+                .await
+                .unwrap();
+            let session = portal.session().await.unwrap();
+
+            portal.reply(INTERFACE, "ConnectToEIS", zvariant::OwnedFd::from(fd));
+            let events = proxy.events(&session).await.unwrap();
+            futures_util::pin_mut!(events);
+
+            let other = "/org/freedesktop/portal/desktop/session/1_2/other";
+            portal
+                .emit_signal(INTERFACE, "Activated", &activated(other, 7, (0.0, 0.0)))
+                .await
+                .unwrap();
+            portal
+                .emit_signal(
+                    INTERFACE,
+                    "Activated",
+                    &activated(FakePortal::SESSION_PATH, 7, (10.0, 20.0)),
+                )
+                .await
+                .unwrap();
+            // The signals are received once the reply to a later call is.
+            portal.reply(INTERFACE, "Enable", ());
+            proxy.enable(&session).await.unwrap();
+            ready_tx.send(()).unwrap();
+
+            let event = events.next().await.unwrap().unwrap();
+            assert_eq!(
+                event.event(),
+                InputEvent::PointerMotion { dx: 1.0, dy: 2.0 }
+            );
+            assert_eq!(event.device().name(), Some("pointer"));
+            assert_eq!(event.time(), 1000);
+            assert_eq!(event.activation_id(), 7);
+            assert_eq!(event.cursor_position(), Some((10.0, 20.0)));
+            assert_eq!(event.barrier_id(), Some(3));
+            assert!(event.keymap().is_none());
+
+            let event = events.next().await.unwrap().unwrap();
+            assert_eq!(
+                event.event(),
+                InputEvent::Button {
+                    button: 272,
+                    state: KeyState::Pressed
+                }
+            );
+            assert_eq!(event.time(), 2000);
+
+            // No Activated signal for this activation.
+            let event = events.next().await.unwrap().unwrap();
+            assert_eq!(
+                event.event(),
+                InputEvent::PointerMotion { dx: 3.0, dy: 4.0 }
+            );
+            assert_eq!(event.activation_id(), 8);
+            assert_eq!(event.cursor_position(), None);
+            assert_eq!(event.barrier_id(), None);
+        });
+
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn disconnection_ends_stream() {
+        let mut capture = Capture::default();
+        let disconnected = reis::event::Disconnected {
+            last_serial: 0,
+            reason: ei::connection::DisconnectReason::Disconnected,
+            explanation: "Bye".to_owned(),
+        };
+        let item = capture.handle(Either::Right(Ok(EiEvent::Disconnected(disconnected))));
+        assert!(matches!(
+            item,
+            Some(Some(Err(Error::Eis(EisError::Disconnected(explanation))))) if explanation == "Bye"
+        ));
+        assert!(capture
+            .handle(Either::Right(Err(Error::NoResponse)))
+            .is_none());
+    }
+}
@@ -35,7 +35,7 @@ use serde::Deserialize;
 use serde_repr::Serialize_repr;
 use zbus::zvariant::{DeserializeDict, ObjectPath, OwnedObjectPath, SerializeDict, Type};
 
-use super::{HandleToken, Request, Session};
+use super::{HandleToken, Request, Session, SessionPortal};
 use crate::{proxy::Proxy, Error, WindowIdentifier};
 
 #[cfg_attr(feature = "glib", derive(glib::Enum))]
@@ -208,6 +208,16 @@ impl<'a> LocationProxy<'a> {
         Ok(Self(proxy))
     }
 
+    /// Create a new instance of [`LocationProxy`] on the given connection.
+    pub async fn with_connection(
+        connection: &zbus::Connection,
+    ) -> Result<LocationProxy<'a>, Error> {
+        let proxy =
+            Proxy::new_desktop_with_connection(connection, "org.freedesktop.portal.Location")
+                .await?;
+        Ok(Self(proxy))
+    }
+
     /// Signal emitted when the user location is updated.
     ///
     /// # Specifications
@@ -238,7 +248,7 @@ impl<'a> LocationProxy<'a> {
         distance_threshold: Option<u32>,
         time_threshold: Option<u32>,
         accuracy: Option<Accuracy>,
-    ) -> Result<Session<'a>, Error> {
+    ) -> Result<Session<'a, Self>, Error> {
         let options = CreateSessionOptions {
             distance_threshold,
             time_threshold,
@@ -249,7 +259,8 @@ impl<'a> LocationProxy<'a> {
             self.0
                 .call::<OwnedObjectPath>("CreateSession", &(options))
                 .into_future(),
-            Session::from_unique_name(&options.session_handle_token).into_future(),
+            Session::from_unique_name(self.0.connection(), &options.session_handle_token)
+                .into_future(),
         )?;
         assert_eq!(proxy.path(), &path.into_inner());
         Ok(proxy)
@@ -271,7 +282,7 @@ impl<'a> LocationProxy<'a> {
     #[doc(alias = "xdp_portal_location_monitor_start")]
     pub async fn start(
         &self,
-        session: &Session<'_>,
+        session: &Session<'_, Self>,
         identifier: &WindowIdentifier,
     ) -> Result<Request<()>, Error> {
         let options = SessionStartOptions::default();
@@ -285,6 +296,17 @@ impl<'a> LocationProxy<'a> {
     }
 }
 
+impl super::session::sealed::Sealed for LocationProxy<'_> {}
+impl SessionPortal for LocationProxy<'_> {}
+
+impl crate::resilient::sealed::Sealed for LocationProxy<'_> {
+    fn proxy(&self) -> &Proxy<'_> {
+        &self.0
+    }
+}
+
+impl crate::PortalProxy for LocationProxy<'_> {}
+
 impl<'a> std::ops::Deref for LocationProxy<'a> {
     type Target = zbus::Proxy<'a>;
 
@@ -39,6 +39,16 @@ impl<'a> MemoryMonitor<'a> {
         Ok(Self(proxy))
     }
 
+    /// Create a new instance of [`MemoryMonitor`] on the given connection.
+    pub async fn with_connection(
+        connection: &zbus::Connection,
+    ) -> Result<MemoryMonitor<'a>, Error> {
+        let proxy =
+            Proxy::new_desktop_with_connection(connection, "org.freedesktop.portal.MemoryMonitor")
+                .await?;
+        Ok(Self(proxy))
+    }
+
     /// Signal emitted when a particular low memory situation happens
     /// with 0 being the lowest level of memory availability warning, and 255
     /// being the highest.
@@ -52,6 +62,14 @@ impl<'a> MemoryMonitor<'a> {
     }
 }
 
+impl crate::resilient::sealed::Sealed for MemoryMonitor<'_> {
+    fn proxy(&self) -> &Proxy<'_> {
+        &self.0
+    }
+}
+
+impl crate::PortalProxy for MemoryMonitor<'_> {}
+
 impl<'a> std::ops::Deref for MemoryMonitor<'a> {
     type Target = zbus::Proxy<'a>;
 
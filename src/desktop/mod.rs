@@ -1,10 +1,15 @@
 mod handle_token;
 pub(crate) mod request;
 mod session;
+#[cfg(feature = "backend")]
+pub use self::handle_token::HandleToken;
+#[cfg(not(feature = "backend"))]
 pub(crate) use self::handle_token::HandleToken;
+mod restore_token;
 pub use self::{
     request::{Request, Response, ResponseError},
-    session::Session,
+    restore_token::{FileRestoreTokenStore, RestoreTokenStore},
+    session::{HasScreencast, Session, SessionDetails, SessionPortal},
 };
 mod color;
 pub use color::Color;
@@ -60,7 +65,15 @@ pub mod wallpaper;
 #[cfg_attr(feature = "glib", derive(glib::Enum))]
 #[cfg_attr(feature = "glib", enum_type(name = "AshpdPersistMode"))]
 #[derive(
-    Default, serde_repr::Serialize_repr, PartialEq, Eq, Debug, Copy, Clone, zbus::zvariant::Type,
+    Default,
+    serde_repr::Serialize_repr,
+    serde_repr::Deserialize_repr,
+    PartialEq,
+    Eq,
+    Debug,
+    Copy,
+    Clone,
+    zbus::zvariant::Type,
 )]
 #[doc(alias = "XdpPersistMode")]
 #[repr(u32)]
@@ -100,6 +100,16 @@ impl<'a> NetworkMonitor<'a> {
         Ok(Self(proxy))
     }
 
+    /// Create a new instance of [`NetworkMonitor`] on the given connection.
+    pub async fn with_connection(
+        connection: &zbus::Connection,
+    ) -> Result<NetworkMonitor<'a>, Error> {
+        let proxy =
+            Proxy::new_desktop_with_connection(connection, "org.freedesktop.portal.NetworkMonitor")
+                .await?;
+        Ok(Self(proxy))
+    }
+
     /// Returns whether the given hostname is believed to be reachable.
     ///
     /// # Arguments
@@ -200,6 +210,14 @@ impl<'a> NetworkMonitor<'a> {
     }
 }
 
+impl crate::resilient::sealed::Sealed for NetworkMonitor<'_> {
+    fn proxy(&self) -> &Proxy<'_> {
+        &self.0
+    }
+}
+
+impl crate::PortalProxy for NetworkMonitor<'_> {}
+
 impl<'a> std::ops::Deref for NetworkMonitor<'a> {
     type Target = zbus::Proxy<'a>;
 
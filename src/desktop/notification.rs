@@ -304,6 +304,16 @@ impl<'a> NotificationProxy<'a> {
         Ok(Self(proxy))
     }
 
+    /// Create a new instance of [`NotificationProxy`] on the given connection.
+    pub async fn with_connection(
+        connection: &zbus::Connection,
+    ) -> Result<NotificationProxy<'a>, Error> {
+        let proxy =
+            Proxy::new_desktop_with_connection(connection, "org.freedesktop.portal.Notification")
+                .await?;
+        Ok(Self(proxy))
+    }
+
     /// Signal emitted when a particular action is invoked.
     ///
     /// # Specifications
@@ -355,6 +365,14 @@ impl<'a> NotificationProxy<'a> {
     }
 }
 
+impl crate::resilient::sealed::Sealed for NotificationProxy<'_> {
+    fn proxy(&self) -> &Proxy<'_> {
+        &self.0
+    }
+}
+
+impl crate::PortalProxy for NotificationProxy<'_> {}
+
 impl<'a> std::ops::Deref for NotificationProxy<'a> {
     type Target = zbus::Proxy<'a>;
 
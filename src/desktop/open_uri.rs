@@ -57,7 +57,7 @@ use url::Url;
 use zbus::zvariant::{Fd, SerializeDict, Type};
 
 use super::{HandleToken, Request};
-use crate::{proxy::Proxy, Error, WindowIdentifier};
+use crate::{blocking, proxy::Proxy, Error, WindowIdentifier};
 
 #[derive(SerializeDict, Type, Debug, Default)]
 #[zvariant(signature = "dict")]
@@ -84,6 +84,13 @@ impl<'a> OpenURIProxy<'a> {
         Ok(Self(proxy))
     }
 
+    pub async fn with_connection(connection: &zbus::Connection) -> Result<OpenURIProxy<'a>, Error> {
+        let proxy =
+            Proxy::new_desktop_with_connection(connection, "org.freedesktop.portal.OpenURI")
+                .await?;
+        Ok(Self(proxy))
+    }
+
     pub async fn open_directory(
         &self,
         identifier: &WindowIdentifier,
@@ -147,9 +154,17 @@ impl<'a> std::ops::Deref for OpenURIProxy<'a> {
 pub struct OpenFileRequest {
     identifier: WindowIdentifier,
     options: OpenFileOptions,
+    connection: Option<zbus::Connection>,
 }
 
 impl OpenFileRequest {
+    #[must_use]
+    /// Sets the connection to send the request on, instead of the session bus.
+    pub fn connection(mut self, connection: impl Into<Option<zbus::Connection>>) -> Self {
+        self.connection = connection.into();
+        self
+    }
+
     #[must_use]
     /// Sets a window identifier.
     pub fn identifier(mut self, identifier: impl Into<Option<WindowIdentifier>>) -> Self {
@@ -173,15 +188,34 @@ impl OpenFileRequest {
 
     /// Send the request for a file.
     pub async fn send_file(self, file: &BorrowedFd<'_>) -> Result<Request<()>, Error> {
-        let proxy = OpenURIProxy::new().await?;
+        let proxy = match self.connection {
+            Some(connection) => OpenURIProxy::with_connection(&connection).await?,
+            None => OpenURIProxy::new().await?,
+        };
         proxy.open_file(&self.identifier, file, self.options).await
     }
 
+    /// Blocking version of [`send_file()`][`Self::send_file`].
+    pub fn send_file_blocking(
+        self,
+        file: &BorrowedFd<'_>,
+    ) -> Result<blocking::desktop::Request<()>, Error> {
+        blocking::block_on(self.send_file(file)).map(blocking::desktop::Request::from)
+    }
+
     /// Send the request for a URI.
     pub async fn send_uri(self, uri: &Url) -> Result<Request<()>, Error> {
-        let proxy = OpenURIProxy::new().await?;
+        let proxy = match self.connection {
+            Some(connection) => OpenURIProxy::with_connection(&connection).await?,
+            None => OpenURIProxy::new().await?,
+        };
         proxy.open_uri(&self.identifier, uri, self.options).await
     }
+
+    /// Blocking version of [`send_uri()`][`Self::send_uri`].
+    pub fn send_uri_blocking(self, uri: &Url) -> Result<blocking::desktop::Request<()>, Error> {
+        blocking::block_on(self.send_uri(uri)).map(blocking::desktop::Request::from)
+    }
 }
 
 #[derive(Debug, Default)]
@@ -193,9 +227,17 @@ impl OpenFileRequest {
 pub struct OpenDirectoryRequest {
     identifier: WindowIdentifier,
     options: OpenDirOptions,
+    connection: Option<zbus::Connection>,
 }
 
 impl OpenDirectoryRequest {
+    #[must_use]
+    /// Sets the connection to send the request on, instead of the session bus.
+    pub fn connection(mut self, connection: impl Into<Option<zbus::Connection>>) -> Self {
+        self.connection = connection.into();
+        self
+    }
+
     #[must_use]
     /// Sets a window identifier.
     pub fn identifier(mut self, identifier: impl Into<Option<WindowIdentifier>>) -> Self {
@@ -205,9 +247,20 @@ impl OpenDirectoryRequest {
 
     /// Send the request.
     pub async fn send(self, directory: &BorrowedFd<'_>) -> Result<Request<()>, Error> {
-        let proxy = OpenURIProxy::new().await?;
+        let proxy = match self.connection {
+            Some(connection) => OpenURIProxy::with_connection(&connection).await?,
+            None => OpenURIProxy::new().await?,
+        };
         proxy
             .open_directory(&self.identifier, directory, self.options)
             .await
     }
+
+    /// Blocking version of [`send()`][`Self::send`].
+    pub fn send_blocking(
+        self,
+        directory: &BorrowedFd<'_>,
+    ) -> Result<blocking::desktop::Request<()>, Error> {
+        blocking::block_on(self.send(directory)).map(blocking::desktop::Request::from)
+    }
 }
@@ -21,6 +21,19 @@ impl<'a> PowerProfileMonitor<'a> {
         Ok(Self(proxy))
     }
 
+    /// Create a new instance of [`PowerProfileMonitor`] on the given
+    /// connection.
+    pub async fn with_connection(
+        connection: &zbus::Connection,
+    ) -> Result<PowerProfileMonitor<'a>, Error> {
+        let proxy = Proxy::new_desktop_with_connection(
+            connection,
+            "org.freedesktop.portal.PowerProfileMonitor",
+        )
+        .await?;
+        Ok(Self(proxy))
+    }
+
     /// Whether the power saver is enabled.
     ///
     /// # Specifications
@@ -23,7 +23,8 @@
 //!             true,
 //!         )
 //!         .await?
-//!         .response()?;
+//!         .response()
+//!         .await?;
 //!     proxy
 //!         .print(
 //!             &identifier,
@@ -641,6 +642,13 @@ impl<'a> PrintProxy<'a> {
         Ok(Self(proxy))
     }
 
+    /// Create a new instance of [`PrintProxy`] on the given connection.
+    pub async fn with_connection(connection: &zbus::Connection) -> Result<PrintProxy<'a>, Error> {
+        let proxy =
+            Proxy::new_desktop_with_connection(connection, "org.freedesktop.portal.Print").await?;
+        Ok(Self(proxy))
+    }
+
     // TODO accept_label: Added in version 2 of the interface.
     /// Presents a print dialog to the user and returns print settings and page
     /// setup.
@@ -34,6 +34,16 @@ impl<'a> ProxyResolver<'a> {
         Ok(Self(proxy))
     }
 
+    /// Create a new instance of [`ProxyResolver`] on the given connection.
+    pub async fn with_connection(
+        connection: &zbus::Connection,
+    ) -> Result<ProxyResolver<'a>, Error> {
+        let proxy =
+            Proxy::new_desktop_with_connection(connection, "org.freedesktop.portal.ProxyResolver")
+                .await?;
+        Ok(Self(proxy))
+    }
+
     /// Looks up which proxy to use to connect to `uri`.
     ///
     /// # Returns
@@ -18,6 +18,14 @@ impl<'a> Realtime<'a> {
         Ok(Self(proxy))
     }
 
+    /// Create a new instance of [`Realtime`] on the given connection.
+    pub async fn with_connection(connection: &zbus::Connection) -> Result<Realtime<'a>, Error> {
+        let proxy =
+            Proxy::new_desktop_with_connection(connection, "org.freedesktop.portal.Realtime")
+                .await?;
+        Ok(Self(proxy))
+    }
+
     #[doc(alias = "MakeThreadRealtimeWithPID")]
     #[allow(missing_docs)]
     pub async fn max_thread_realtime_with_pid(
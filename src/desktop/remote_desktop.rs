@@ -19,12 +19,15 @@
 //!             None,
 //!             PersistMode::DoNot,
 //!         )
+//!         .await?
+//!         .response()
 //!         .await?;
 //!
 //!     let response = proxy
 //!         .start(&session, &WindowIdentifier::default())
 //!         .await?
-//!         .response()?;
+//!         .response()
+//!         .await?;
 //!     println!("{:#?}", response.devices());
 //!
 //!     // 13 for Enter key code
@@ -65,6 +68,8 @@
 //!             None,
 //!             PersistMode::DoNot,
 //!         )
+//!         .await?
+//!         .response()
 //!         .await?;
 //!     screencast
 //!         .select_sources(
@@ -75,12 +80,15 @@
 //!             None,
 //!             PersistMode::DoNot,
 //!         )
+//!         .await?
+//!         .response()
 //!         .await?;
 //!
 //!     let response = remote_desktop
 //!         .start(&session, &identifier)
 //!         .await?
-//!         .response()?;
+//!         .response()
+//!         .await?;
 //!     println!("{:#?}", response.devices());
 //!     println!("{:#?}", response.streams());
 //!
@@ -102,9 +110,23 @@ use futures_util::TryFutureExt;
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use zbus::zvariant::{self, DeserializeDict, SerializeDict, Type, Value};
 
-use super::{screencast::Stream, HandleToken, PersistMode, Request, Session};
+use super::{
+    screencast::Stream, HandleToken, HasScreencast, PersistMode, Request, RestoreTokenStore,
+    Session, SessionPortal,
+};
 use crate::{desktop::session::CreateSessionResponse, proxy::Proxy, Error, WindowIdentifier};
 
+mod gestures;
+mod keyboard;
+mod script;
+#[cfg(feature = "eis")]
+pub use self::script::ScriptRecorder;
+pub use self::{
+    gestures::Gesture,
+    keyboard::Key,
+    script::{Action, Script, ScriptPlayer, Step},
+};
+
 #[cfg_attr(feature = "glib", derive(glib::Enum))]
 #[cfg_attr(feature = "glib", enum_type(name = "AshpdKeyState"))]
 #[derive(Serialize_repr, Deserialize_repr, Copy, Clone, PartialEq, Eq, Debug, Type)]
@@ -240,6 +262,16 @@ impl<'a> RemoteDesktop<'a> {
         Ok(Self(proxy))
     }
 
+    /// Create a new instance of [`RemoteDesktop`] on the given connection.
+    pub async fn with_connection(
+        connection: &zbus::Connection,
+    ) -> Result<RemoteDesktop<'a>, Error> {
+        let proxy =
+            Proxy::new_desktop_with_connection(connection, "org.freedesktop.portal.RemoteDesktop")
+                .await?;
+        Ok(Self(proxy))
+    }
+
     /// Create a remote desktop session.
     /// A remote desktop session is used to allow remote controlling a desktop
     /// session. It can also be used together with a screen cast session.
@@ -249,15 +281,19 @@ impl<'a> RemoteDesktop<'a> {
     /// See also [`CreateSession`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.portal.RemoteDesktop.html#org-freedesktop-portal-remotedesktop-createsession).
     #[doc(alias = "CreateSession")]
     #[doc(alias = "xdp_portal_create_remote_desktop_session")]
-    pub async fn create_session(&self) -> Result<Session<'a>, Error> {
+    pub async fn create_session(&self) -> Result<Session<'a, Self>, Error> {
         let options = CreateRemoteOptions::default();
         let (request, proxy) = futures_util::try_join!(
             self.0
                 .request::<CreateSessionResponse>(&options.handle_token, "CreateSession", &options)
                 .into_future(),
-            Session::from_unique_name(&options.session_handle_token).into_future()
+            Session::from_unique_name(self.0.connection(), &options.session_handle_token)
+                .into_future()
         )?;
-        assert_eq!(proxy.path(), &request.response()?.session_handle.as_ref());
+        assert_eq!(
+            proxy.path(),
+            &request.response().await?.session_handle.as_ref()
+        );
         Ok(proxy)
     }
 
@@ -275,7 +311,7 @@ impl<'a> RemoteDesktop<'a> {
     #[doc(alias = "SelectDevices")]
     pub async fn select_devices(
         &self,
-        session: &Session<'_>,
+        session: &Session<'_, Self>,
         types: BitFlags<DeviceType>,
         restore_token: Option<&str>,
         persist_mode: PersistMode,
@@ -289,6 +325,24 @@ impl<'a> RemoteDesktop<'a> {
             .await
     }
 
+    /// Same as [`RemoteDesktop::select_devices`], restoring the session from
+    /// the token stored for `profile` in `store`, if any.
+    ///
+    /// Use [`RemoteDesktop::start_with_store`] to start the session and store
+    /// the new token.
+    pub async fn select_devices_with_store(
+        &self,
+        session: &Session<'_, Self>,
+        types: BitFlags<DeviceType>,
+        persist_mode: PersistMode,
+        store: &impl RestoreTokenStore,
+        profile: &str,
+    ) -> Result<Request<()>, Error> {
+        let restore_token = store.load(profile).await?;
+        self.select_devices(session, types, restore_token.as_deref(), persist_mode)
+            .await
+    }
+
     ///  Start the remote desktop session.
     ///
     /// This will typically result in the portal presenting a dialog letting
@@ -307,7 +361,7 @@ impl<'a> RemoteDesktop<'a> {
     #[doc(alias = "Start")]
     pub async fn start(
         &self,
-        session: &Session<'_>,
+        session: &Session<'_, Self>,
         identifier: &WindowIdentifier,
     ) -> Result<Request<SelectedDevices>, Error> {
         let options = StartRemoteOptions::default();
@@ -320,6 +374,24 @@ impl<'a> RemoteDesktop<'a> {
             .await
     }
 
+    /// Same as [`RemoteDesktop::start`], waiting for the response and
+    /// replacing the token stored for `profile` in `store` with the one the
+    /// portal returned.
+    ///
+    /// Restore tokens can only be used once, the stored token is removed if
+    /// the portal didn't return a new one.
+    pub async fn start_with_store(
+        &self,
+        session: &Session<'_, Self>,
+        identifier: &WindowIdentifier,
+        store: &impl RestoreTokenStore,
+        profile: &str,
+    ) -> Result<SelectedDevices, Error> {
+        let devices = self.start(session, identifier).await?.response().await?;
+        store.store(profile, devices.restore_token()).await?;
+        Ok(devices)
+    }
+
     /// Notify keyboard code.
     ///
     /// **Note** only works if [`DeviceType::Keyboard`] access was provided
@@ -338,7 +410,7 @@ impl<'a> RemoteDesktop<'a> {
     #[doc(alias = "NotifyKeyboardKeycode")]
     pub async fn notify_keyboard_keycode(
         &self,
-        session: &Session<'_>,
+        session: &Session<'_, Self>,
         keycode: i32,
         state: KeyState,
     ) -> Result<(), Error> {
@@ -368,7 +440,7 @@ impl<'a> RemoteDesktop<'a> {
     #[doc(alias = "NotifyKeyboardKeysym")]
     pub async fn notify_keyboard_keysym(
         &self,
-        session: &Session<'_>,
+        session: &Session<'_, Self>,
         keysym: i32,
         state: KeyState,
     ) -> Result<(), Error> {
@@ -395,7 +467,11 @@ impl<'a> RemoteDesktop<'a> {
     ///
     /// See also [`NotifyTouchUp`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.portal.RemoteDesktop.html#org-freedesktop-portal-remotedesktop-notifytouchup).
     #[doc(alias = "NotifyTouchUp")]
-    pub async fn notify_touch_up(&self, session: &Session<'_>, slot: u32) -> Result<(), Error> {
+    pub async fn notify_touch_up(
+        &self,
+        session: &Session<'_, Self>,
+        slot: u32,
+    ) -> Result<(), Error> {
         // The `notify` methods don't take any options for now
         // see https://github.com/flatpak/xdg-desktop-portal/blob/master/src/remote-desktop.c#L723
         let options: HashMap<&str, Value<'_>> = HashMap::new();
@@ -426,7 +502,7 @@ impl<'a> RemoteDesktop<'a> {
     #[doc(alias = "NotifyTouchDown")]
     pub async fn notify_touch_down(
         &self,
-        session: &Session<'_>,
+        session: &Session<'_, Self>,
         stream: u32,
         slot: u32,
         x: f64,
@@ -462,7 +538,7 @@ impl<'a> RemoteDesktop<'a> {
     #[doc(alias = "NotifyTouchMotion")]
     pub async fn notify_touch_motion(
         &self,
-        session: &Session<'_>,
+        session: &Session<'_, Self>,
         stream: u32,
         slot: u32,
         x: f64,
@@ -494,7 +570,7 @@ impl<'a> RemoteDesktop<'a> {
     #[doc(alias = "NotifyPointerMotionAbsolute")]
     pub async fn notify_pointer_motion_absolute(
         &self,
-        session: &Session<'_>,
+        session: &Session<'_, Self>,
         stream: u32,
         x: f64,
         y: f64,
@@ -527,7 +603,7 @@ impl<'a> RemoteDesktop<'a> {
     #[doc(alias = "NotifyPointerMotion")]
     pub async fn notify_pointer_motion(
         &self,
-        session: &Session<'_>,
+        session: &Session<'_, Self>,
         dx: f64,
         dy: f64,
     ) -> Result<(), Error> {
@@ -559,7 +635,7 @@ impl<'a> RemoteDesktop<'a> {
     #[doc(alias = "NotifyPointerButton")]
     pub async fn notify_pointer_button(
         &self,
-        session: &Session<'_>,
+        session: &Session<'_, Self>,
         button: i32,
         state: KeyState,
     ) -> Result<(), Error> {
@@ -588,7 +664,7 @@ impl<'a> RemoteDesktop<'a> {
     #[doc(alias = "NotifyPointerAxisDiscrete")]
     pub async fn notify_pointer_axis_discrete(
         &self,
-        session: &Session<'_>,
+        session: &Session<'_, Self>,
         axis: Axis,
         steps: i32,
     ) -> Result<(), Error> {
@@ -626,7 +702,7 @@ impl<'a> RemoteDesktop<'a> {
     #[doc(alias = "NotifyPointerAxis")]
     pub async fn notify_pointer_axis(
         &self,
-        session: &Session<'_>,
+        session: &Session<'_, Self>,
         dx: f64,
         dy: f64,
         finish: bool,
@@ -643,6 +719,9 @@ impl<'a> RemoteDesktop<'a> {
     ///
     /// **Note** only succeeds if called after [`RemoteDesktop::start`].
     ///
+    /// With the `eis` feature, `ashpd::eis::EiSender` emulates input devices
+    /// on the returned socket.
+    ///
     /// Requires RemoteDesktop version 2.
     ///
     /// # Arguments
@@ -659,7 +738,7 @@ impl<'a> RemoteDesktop<'a> {
     ///
     /// See also [`ConnectToEIS`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.portal.RemoteDesktop.html#org-freedesktop-portal-remotedesktop-connecttoeis).
     #[doc(alias = "ConnectToEIS")]
-    pub async fn connect_to_eis(&self, session: &Session<'_>) -> Result<OwnedFd, Error> {
+    pub async fn connect_to_eis(&self, session: &Session<'_, Self>) -> Result<OwnedFd, Error> {
         // `ConnectToEIS` doesn't take any options for now
         // see https://github.com/flatpak/xdg-desktop-portal/blob/master/src/remote-desktop.c#L1464
         let options: HashMap<&str, Value<'_>> = HashMap::new();
@@ -681,6 +760,10 @@ impl<'a> RemoteDesktop<'a> {
     }
 }
 
+impl super::session::sealed::Sealed for RemoteDesktop<'_> {}
+impl SessionPortal for RemoteDesktop<'_> {}
+impl HasScreencast for RemoteDesktop<'_> {}
+
 impl<'a> std::ops::Deref for RemoteDesktop<'a> {
     type Target = zbus::Proxy<'a>;
 
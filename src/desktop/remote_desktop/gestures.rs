@@ -0,0 +1,494 @@
+use std::{collections::BTreeSet, f64::consts::PI, sync::Mutex, time::Duration};
+
+use super::RemoteDesktop;
+use crate::{
+    desktop::{screencast::Stream, Session},
+    helpers::sleep,
+    Error,
+};
+
+/// The time a finger stays down during a tap.
+const TAP_DURATION: Duration = Duration::from_millis(50);
+/// The time between two motion events of a moving gesture.
+const FRAME_INTERVAL: Duration = Duration::from_millis(16);
+/// The distance between two adjacent fingers of a swipe, in logical pixels.
+const FINGER_SPACING: f64 = 40.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Press {
+        position: (f64, f64),
+    },
+    Swipe {
+        from: (f64, f64),
+        to: (f64, f64),
+        fingers: u32,
+    },
+    Pinch {
+        center: (f64, f64),
+        from: f64,
+        to: f64,
+    },
+    Rotate {
+        center: (f64, f64),
+        distance: f64,
+        angle: f64,
+    },
+}
+
+/// A touch gesture, performed with [`RemoteDesktop::perform_gesture`].
+///
+/// Positions are in logical pixels relative to the stream the gesture is
+/// performed on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gesture {
+    kind: Kind,
+    duration: Duration,
+}
+
+impl Gesture {
+    fn new(kind: Kind, duration: Duration) -> Self {
+        Self { kind, duration }
+    }
+
+    /// A short tap of one finger at `position`.
+    pub fn tap(position: (f64, f64)) -> Self {
+        Self::new(Kind::Press { position }, TAP_DURATION)
+    }
+
+    /// One finger held down at `position` for `duration`.
+    pub fn long_press(position: (f64, f64), duration: Duration) -> Self {
+        Self::new(Kind::Press { position }, duration)
+    }
+
+    /// One finger moving from `from` to `to` in `duration`.
+    pub fn swipe(from: (f64, f64), to: (f64, f64), duration: Duration) -> Self {
+        Self::new(
+            Kind::Swipe {
+                from,
+                to,
+                fingers: 1,
+            },
+            duration,
+        )
+    }
+
+    /// Two fingers on a horizontal line through `center`, moving from `from`
+    /// to `to` pixels apart in `duration`. The fingers come closer when
+    /// `to` is less than `from`, zooming out.
+    pub fn pinch(center: (f64, f64), from: f64, to: f64, duration: Duration) -> Self {
+        Self::new(Kind::Pinch { center, from, to }, duration)
+    }
+
+    /// Two fingers `distance` pixels apart turning around `center` by `angle`
+    /// degrees in `duration`, clockwise on screen for a positive angle. The
+    /// fingers start on a horizontal line.
+    pub fn rotate(center: (f64, f64), distance: f64, angle: f64, duration: Duration) -> Self {
+        Self::new(
+            Kind::Rotate {
+                center,
+                distance,
+                angle,
+            },
+            duration,
+        )
+    }
+
+    /// The number of fingers of a swipe, spread side by side across its
+    /// direction, defaults to one. Other gestures ignore it.
+    #[must_use]
+    pub fn fingers(mut self, count: u32) -> Self {
+        if let Kind::Swipe { fingers, .. } = &mut self.kind {
+            *fingers = count.max(1);
+        }
+        self
+    }
+
+    /// The time the gesture takes.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// The number of fingers of the gesture.
+    fn finger_count(&self) -> usize {
+        match self.kind {
+            Kind::Press { .. } => 1,
+            Kind::Swipe { fingers, .. } => fingers as usize,
+            Kind::Pinch { .. } | Kind::Rotate { .. } => 2,
+        }
+    }
+
+    /// The positions of the fingers at `progress`, from 0 to 1.
+    fn positions(&self, progress: f64) -> Vec<(f64, f64)> {
+        let lerp = |from: f64, to: f64| from + (to - from) * progress;
+        match self.kind {
+            Kind::Press { position } => vec![position],
+            Kind::Swipe { from, to, fingers } => {
+                let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+                let length = dx.hypot(dy);
+                // The unit vector across the direction of the swipe.
+                let (nx, ny) = if length > 0.0 {
+                    (-dy / length, dx / length)
+                } else {
+                    (1.0, 0.0)
+                };
+                let center = (lerp(from.0, to.0), lerp(from.1, to.1));
+                (0..fingers)
+                    .map(|finger| {
+                        let offset = (finger as f64 - (fingers - 1) as f64 / 2.0) * FINGER_SPACING;
+                        (center.0 + nx * offset, center.1 + ny * offset)
+                    })
+                    .collect()
+            }
+            Kind::Pinch { center, from, to } => {
+                let half = lerp(from, to) / 2.0;
+                vec![(center.0 - half, center.1), (center.0 + half, center.1)]
+            }
+            Kind::Rotate {
+                center,
+                distance,
+                angle,
+            } => {
+                let angle = lerp(0.0, angle) * PI / 180.0;
+                let (dx, dy) = (angle.cos() * distance / 2.0, angle.sin() * distance / 2.0);
+                vec![
+                    (center.0 - dx, center.1 - dy),
+                    (center.0 + dx, center.1 + dy),
+                ]
+            }
+        }
+    }
+
+    /// The number of motion events of each finger.
+    fn frames(&self) -> u32 {
+        match self.kind {
+            Kind::Press { .. } => 0,
+            _ => (self.duration.as_millis() / FRAME_INTERVAL.as_millis()).max(1) as u32,
+        }
+    }
+}
+
+/// The touch slots of a gesture, given back to the session once dropped.
+struct Slots<'s> {
+    held: &'s Mutex<BTreeSet<u32>>,
+    slots: Vec<u32>,
+}
+
+impl<'s> Slots<'s> {
+    /// Hold the `count` lowest slots not held by another gesture.
+    fn hold(held: &'s Mutex<BTreeSet<u32>>, count: usize) -> Self {
+        let mut held_slots = held.lock().unwrap();
+        let slots = (0..)
+            .filter(|slot| !held_slots.contains(slot))
+            .take(count)
+            .collect::<Vec<_>>();
+        held_slots.extend(&slots);
+        drop(held_slots);
+        Self { held, slots }
+    }
+}
+
+impl Drop for Slots<'_> {
+    fn drop(&mut self) {
+        let mut held = self.held.lock().unwrap();
+        for slot in &self.slots {
+            held.remove(slot);
+        }
+    }
+}
+
+/// Keep `position` within the bounds of `stream`, when it has a size.
+fn clamp(stream: &Stream, (x, y): (f64, f64)) -> (f64, f64) {
+    match stream.size() {
+        Some((width, height)) if width > 0 && height > 0 => (
+            x.clamp(0.0, (width - 1) as f64),
+            y.clamp(0.0, (height - 1) as f64),
+        ),
+        _ => (x, y),
+    }
+}
+
+impl RemoteDesktop<'_> {
+    /// Perform `gesture` with touch events on `stream`, returning once all
+    /// the fingers are lifted.
+    ///
+    /// Positions outside of the stream are moved to its edges. The fingers
+    /// put down are lifted even if an event fails, and the first error is
+    /// returned.
+    ///
+    /// Each finger uses the lowest touch slot not used by the other gestures
+    /// performed on the session at the same time. The slots of the touch
+    /// events sent directly, e.g. with [`RemoteDesktop::notify_touch_down`],
+    /// aren't taken into account.
+    ///
+    /// **Note** only works if
+    /// [`DeviceType::Touchscreen`](super::DeviceType::Touchscreen) access
+    /// was provided after starting the session.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// use ashpd::desktop::{
+    ///     remote_desktop::{Gesture, RemoteDesktop},
+    ///     screencast::Stream,
+    ///     Session,
+    /// };
+    ///
+    /// async fn run(
+    ///     proxy: &RemoteDesktop<'_>,
+    ///     session: &Session<'_, RemoteDesktop<'_>>,
+    ///     stream: &Stream,
+    /// ) -> ashpd::Result<()> {
+    ///     let zoom_in = Gesture::pinch((400.0, 300.0), 50.0, 250.0, Duration::from_millis(300));
+    ///     proxy.perform_gesture(session, stream, &zoom_in).await
+    /// }
+    /// ```
+    pub async fn perform_gesture(
+        &self,
+        session: &Session<'_, Self>,
+        stream: &Stream,
+        gesture: &Gesture,
+    ) -> Result<(), Error> {
+        let node_id = stream.pipe_wire_node_id();
+        let slots = Slots::hold(session.touch_slots(), gesture.finger_count());
+        let slots = slots.slots.iter().copied();
+        let mut result = Ok(());
+        let mut down = 0;
+        for (slot, (x, y)) in slots.clone().zip(gesture.positions(0.0)) {
+            let (x, y) = clamp(stream, (x, y));
+            result = self.notify_touch_down(session, node_id, slot, x, y).await;
+            if result.is_err() {
+                break;
+            }
+            down += 1;
+        }
+
+        if result.is_ok() {
+            let frames = gesture.frames();
+            if frames == 0 {
+                sleep(gesture.duration).await;
+            }
+            'frames: for frame in 1..=frames {
+                sleep(gesture.duration / frames).await;
+                let progress = frame as f64 / frames as f64;
+                for (slot, position) in slots.clone().zip(gesture.positions(progress)) {
+                    let (x, y) = clamp(stream, position);
+                    result = self.notify_touch_motion(session, node_id, slot, x, y).await;
+                    if result.is_err() {
+                        break 'frames;
+                    }
+                }
+            }
+        }
+
+        for slot in slots.take(down) {
+            let lifted = self.notify_touch_up(session, slot).await;
+            result = result.and(lifted);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rounded(positions: Vec<(f64, f64)>) -> Vec<(f64, f64)> {
+        positions
+            .into_iter()
+            .map(|(x, y)| (x.round(), y.round()))
+            .collect()
+    }
+
+    #[test]
+    fn finger_positions() {
+        let second = Duration::from_secs(1);
+
+        let tap = Gesture::tap((10.0, 20.0));
+        assert_eq!(tap.finger_count(), 1);
+        assert_eq!(tap.frames(), 0);
+        assert_eq!(tap.positions(0.5), [(10.0, 20.0)]);
+
+        let swipe = Gesture::swipe((0.0, 100.0), (200.0, 100.0), second).fingers(2);
+        assert_eq!(swipe.finger_count(), 2);
+        assert_eq!(swipe.frames(), 62);
+        assert_eq!(swipe.positions(0.0), [(0.0, 80.0), (0.0, 120.0)]);
+        assert_eq!(swipe.positions(0.5), [(100.0, 80.0), (100.0, 120.0)]);
+
+        let pinch = Gesture::pinch((100.0, 100.0), 20.0, 100.0, second);
+        assert_eq!(pinch.positions(0.0), [(90.0, 100.0), (110.0, 100.0)]);
+        assert_eq!(pinch.positions(1.0), [(50.0, 100.0), (150.0, 100.0)]);
+
+        let rotate = Gesture::rotate((100.0, 100.0), 100.0, 90.0, second);
+        assert_eq!(
+            rounded(rotate.positions(1.0)),
+            [(100.0, 50.0), (100.0, 150.0)]
+        );
+    }
+
+    #[test]
+    fn held_slots() {
+        let held = Mutex::new(BTreeSet::new());
+        let first = Slots::hold(&held, 2);
+        let second = Slots::hold(&held, 1);
+        assert_eq!(
+            (first.slots.as_slice(), second.slots.as_slice()),
+            (&[0, 1][..], &[2][..])
+        );
+        drop(first);
+        assert_eq!(Slots::hold(&held, 3).slots, [0, 1, 3]);
+        drop(second);
+        assert!(held.lock().unwrap().is_empty());
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod portal_tests {
+    use std::collections::HashMap;
+
+    use zbus::zvariant::{ObjectPath, OwnedValue};
+
+    use super::*;
+    use crate::testing::FakePortal;
+
+    const INTERFACE: &str = "org.freedesktop.portal.RemoteDesktop";
+
+    type Touch<'a> = (
+        ObjectPath<'a>,
+        HashMap<String, OwnedValue>,
+        u32,
+        u32,
+        f64,
+        f64,
+    );
+
+    fn touches(portal: &FakePortal, method: &str) -> Vec<(u32, f64, f64)> {
+        portal
+            .calls(INTERFACE, method)
+            .iter()
+            .map(|call| {
+                let (_, _, _, slot, x, y) = call.body().deserialize::<Touch<'_>>().unwrap();
+                (slot, x, y)
+            })
+            .collect()
+    }
+
+    fn lifted(portal: &FakePortal) -> Vec<u32> {
+        portal
+            .calls(INTERFACE, "NotifyTouchUp")
+            .iter()
+            .map(|call| {
+                let (_, _, slot) = call
+                    .body()
+                    .deserialize::<(ObjectPath<'_>, HashMap<String, OwnedValue>, u32)>()
+                    .unwrap();
+                slot
+            })
+            .collect()
+    }
+
+    #[test]
+    fn perform_pinch() {
+        zbus::block_on(async {
+            let portal = FakePortal::new().await.unwrap();
+            let proxy = RemoteDesktop::with_connection(portal.connection())
+                .await
+                .unwrap();
+            let session = portal.session().await.unwrap();
+            let stream = Stream::builder(42).size((200, 200)).build();
+
+            // Zooming in beyond the stream, in two frames.
+            let pinch = Gesture::pinch((100.0, 100.0), 100.0, 300.0, Duration::from_millis(32));
+            for _ in 0..2 {
+                portal.reply(INTERFACE, "NotifyTouchDown", ());
+                portal.reply(INTERFACE, "NotifyTouchUp", ());
+            }
+            for _ in 0..4 {
+                portal.reply(INTERFACE, "NotifyTouchMotion", ());
+            }
+            proxy
+                .perform_gesture(&session, &stream, &pinch)
+                .await
+                .unwrap();
+            assert_eq!(
+                touches(&portal, "NotifyTouchDown"),
+                [(0, 50.0, 100.0), (1, 150.0, 100.0)]
+            );
+            assert_eq!(
+                touches(&portal, "NotifyTouchMotion"),
+                [
+                    (0, 0.0, 100.0),
+                    (1, 199.0, 100.0),
+                    (0, 0.0, 100.0),
+                    (1, 199.0, 100.0)
+                ]
+            );
+            assert_eq!(lifted(&portal), [0, 1]);
+        });
+    }
+
+    #[test]
+    fn fingers_lifted_on_error() {
+        zbus::block_on(async {
+            let portal = FakePortal::new().await.unwrap();
+            let proxy = RemoteDesktop::with_connection(portal.connection())
+                .await
+                .unwrap();
+            let session = portal.session().await.unwrap();
+            let stream = Stream::builder(42).build();
+
+            let swipe =
+                Gesture::swipe((0.0, 0.0), (0.0, 100.0), Duration::from_millis(16)).fingers(3);
+            portal.reply(INTERFACE, "NotifyTouchDown", ());
+            portal.reply(INTERFACE, "NotifyTouchDown", ());
+            portal.reply_error(
+                INTERFACE,
+                "NotifyTouchDown",
+                "org.freedesktop.portal.Error.Failed",
+                "Failed",
+            );
+            let err = proxy
+                .perform_gesture(&session, &stream, &swipe)
+                .await
+                .unwrap_err();
+            assert!(matches!(err, Error::Portal(..)));
+            assert!(portal.calls(INTERFACE, "NotifyTouchMotion").is_empty());
+            assert_eq!(lifted(&portal), [0, 1]);
+        });
+    }
+
+    #[test]
+    fn concurrent_gestures() {
+        zbus::block_on(async {
+            let portal = FakePortal::new().await.unwrap();
+            let proxy = RemoteDesktop::with_connection(portal.connection())
+                .await
+                .unwrap();
+            let session = portal.session().await.unwrap();
+            let stream = Stream::builder(42).build();
+
+            for _ in 0..3 {
+                portal.reply(INTERFACE, "NotifyTouchDown", ());
+                portal.reply(INTERFACE, "NotifyTouchUp", ());
+            }
+            let (first, second) = (Gesture::tap((10.0, 10.0)), Gesture::tap((20.0, 20.0)));
+            let (first, second) = futures_util::join!(
+                proxy.perform_gesture(&session, &stream, &first),
+                proxy.perform_gesture(&session, &stream, &second),
+            );
+            first.unwrap();
+            second.unwrap();
+            // The slots are free again once the fingers are lifted.
+            proxy
+                .perform_gesture(&session, &stream, &Gesture::tap((30.0, 30.0)))
+                .await
+                .unwrap();
+            assert_eq!(
+                touches(&portal, "NotifyTouchDown"),
+                [(0, 10.0, 10.0), (1, 20.0, 20.0), (0, 30.0, 30.0)]
+            );
+        });
+    }
+}
@@ -0,0 +1,390 @@
+use super::{KeyState, RemoteDesktop};
+use crate::{desktop::Session, Error};
+
+const SHIFT_L: i32 = 0xffe1;
+const CONTROL_L: i32 = 0xffe3;
+const ALT_L: i32 = 0xffe9;
+const SUPER_L: i32 = 0xffeb;
+const ISO_LEVEL3_SHIFT: i32 = 0xfe03;
+const RETURN: i32 = 0xff0d;
+const TAB: i32 = 0xff09;
+const ESCAPE: i32 = 0xff1b;
+const BACKSPACE: i32 = 0xff08;
+const DELETE: i32 = 0xffff;
+/// Offset of the keysyms of Unicode characters outside of Latin-1.
+const UNICODE_OFFSET: i32 = 0x0100_0000;
+
+/// The ASCII characters typed with Shift on a US layout, besides the upper
+/// case letters.
+const SHIFTED_SYMBOLS: &str = "~!@#$%^&*()_+{}|:\"<>?";
+
+/// The Latin-1 and Latin Extended-A characters composed of a dead key and an
+/// ASCII letter: the keysym of the dead key, the composed characters and their
+/// base letters.
+const DEAD_KEYS: &[(i32, &str, &str)] = &[
+    // dead_grave
+    (0xfe50, "ÀàÈèÌìÒòÙù", "AaEeIiOoUu"),
+    // dead_acute
+    (
+        0xfe51,
+        "ÁáÉéÍíÓóÚúÝýĆćĹĺŃńŔŕŚśŹź",
+        "AaEeIiOoUuYyCcLlNnRrSsZz",
+    ),
+    // dead_circumflex
+    (
+        0xfe52,
+        "ÂâÊêÎîÔôÛûĈĉĜĝĤĥĴĵŜŝŴŵŶŷ",
+        "AaEeIiOoUuCcGgHhJjSsWwYy",
+    ),
+    // dead_tilde
+    (0xfe53, "ÃãÑñÕõĨĩŨũ", "AaNnOoIiUu"),
+    // dead_macron
+    (0xfe54, "ĀāĒēĪīŌōŪū", "AaEeIiOoUu"),
+    // dead_breve
+    (0xfe55, "ĂăĔĕĞğĬĭŎŏŬŭ", "AaEeGgIiOoUu"),
+    // dead_abovedot
+    (0xfe56, "ĊċĖėĠġİŻż", "CcEeGgIZz"),
+    // dead_diaeresis
+    (0xfe57, "ÄäËëÏïÖöÜüÿŸ", "AaEeIiOoUuyY"),
+    // dead_abovering
+    (0xfe58, "ÅåŮů", "AaUu"),
+    // dead_doubleacute
+    (0xfe59, "ŐőŰű", "OoUu"),
+    // dead_caron
+    (0xfe5a, "ČčĎďĚěĽľŇňŘřŠšŤťŽž", "CcDdEeLlNnRrSsTtZz"),
+    // dead_cedilla
+    (0xfe5b, "ÇçĢģĶķĻļŅņŖŗŞşŢţ", "CcGgKkLlNnRrSsTt"),
+    // dead_ogonek
+    (0xfe5c, "ĄąĘęĮįŲų", "AaEeIiUu"),
+];
+
+/// A key of a [`RemoteDesktop::press_combo`] chord.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Key {
+    /// The left Shift key.
+    Shift,
+    /// The left Control key.
+    Control,
+    /// The left Alt key.
+    Alt,
+    /// The left Super, or Windows, key.
+    Super,
+    /// The AltGr key, `ISO_Level3_Shift`.
+    AltGr,
+    /// The Return key.
+    Return,
+    /// The Tab key.
+    Tab,
+    /// The Escape key.
+    Escape,
+    /// The Backspace key.
+    Backspace,
+    /// The Delete key.
+    Delete,
+    /// The key of a character, e.g. `Key::Char('t')`. Upper case letters are
+    /// different keysyms than lower case ones.
+    Char(char),
+    /// A raw keysym.
+    Keysym(i32),
+}
+
+impl Key {
+    /// The keysym of the key.
+    pub fn keysym(self) -> i32 {
+        match self {
+            Self::Shift => SHIFT_L,
+            Self::Control => CONTROL_L,
+            Self::Alt => ALT_L,
+            Self::Super => SUPER_L,
+            Self::AltGr => ISO_LEVEL3_SHIFT,
+            Self::Return => RETURN,
+            Self::Tab => TAB,
+            Self::Escape => ESCAPE,
+            Self::Backspace => BACKSPACE,
+            Self::Delete => DELETE,
+            Self::Char(c) => char_keysym(c),
+            Self::Keysym(keysym) => keysym,
+        }
+    }
+}
+
+impl From<char> for Key {
+    fn from(c: char) -> Self {
+        Self::Char(c)
+    }
+}
+
+/// A keysym to tap, with Shift held down or not.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Stroke {
+    keysym: i32,
+    shift: bool,
+}
+
+fn char_keysym(c: char) -> i32 {
+    match c {
+        '\n' | '\r' => RETURN,
+        '\t' => TAB,
+        '\u{8}' => BACKSPACE,
+        '\u{1b}' => ESCAPE,
+        '\u{7f}' => DELETE,
+        // Latin-1 keysyms are the code points.
+        ' '..='~' | '\u{a0}'..='\u{ff}' => c as i32,
+        _ => UNICODE_OFFSET + c as i32,
+    }
+}
+
+fn is_shifted(c: char) -> bool {
+    c.is_uppercase() || SHIFTED_SYMBOLS.contains(c)
+}
+
+/// The stroke typing `c` with its own keysym.
+fn stroke(c: char) -> Stroke {
+    Stroke {
+        keysym: char_keysym(c),
+        shift: is_shifted(c),
+    }
+}
+
+/// The strokes typing `c`, as a dead key followed by its base letter if
+/// `dead_keys` and it is composed of them.
+fn strokes(c: char, dead_keys: bool) -> Vec<Stroke> {
+    let dead_key =
+        DEAD_KEYS
+            .iter()
+            .filter(|_| dead_keys)
+            .find_map(|(dead_key, composed, bases)| {
+                let index = composed.chars().position(|composed| composed == c)?;
+                Some((*dead_key, bases.chars().nth(index)?))
+            });
+    match dead_key {
+        Some((dead_key, base)) => vec![
+            Stroke {
+                keysym: dead_key,
+                shift: false,
+            },
+            stroke(base),
+        ],
+        None => vec![stroke(c)],
+    }
+}
+
+impl RemoteDesktop<'_> {
+    /// Type `text` with keysym events.
+    ///
+    /// Each character is typed with its own keysym, line breaks with Return.
+    /// Upper case letters and the symbols typed with Shift on a US layout are
+    /// typed with Shift held down. Characters outside of Latin-1 are sent as
+    /// Unicode keysyms, left to the compositor to map to a key of the layout.
+    /// See [`RemoteDesktop::type_text_with_dead_keys`] for layouts which only
+    /// have dead keys for the accented letters.
+    ///
+    /// **Note** the symbols are assumed to be on a US layout. With other
+    /// layouts, some are typed with the wrong Shift state, e.g. `@` on a
+    /// German one; use [`RemoteDesktop::press_combo`] to type them there.
+    ///
+    /// Shift is released even if typing a character fails.
+    ///
+    /// **Note** only works if
+    /// [`DeviceType::Keyboard`](super::DeviceType::Keyboard) access was
+    /// provided after starting the session.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use ashpd::desktop::{remote_desktop::RemoteDesktop, Session};
+    ///
+    /// async fn run(
+    ///     proxy: &RemoteDesktop<'_>,
+    ///     session: &Session<'_, RemoteDesktop<'_>>,
+    /// ) -> ashpd::Result<()> {
+    ///     proxy.type_text(session, "Hello, Wörld\n").await
+    /// }
+    /// ```
+    pub async fn type_text(&self, session: &Session<'_, Self>, text: &str) -> Result<(), Error> {
+        self.type_strokes(session, text, false).await
+    }
+
+    /// Type `text` like [`RemoteDesktop::type_text`], but with the accented
+    /// letters of Latin-1 and Latin Extended-A, e.g. `é`, `ő` or `č`, typed as
+    /// a dead key followed by their base letter.
+    ///
+    /// **Note** the compositor has to map the dead keysyms to keys, so the
+    /// dead keys must be on the keyboard layout, e.g. `dead_acute` isn't on
+    /// a French AZERTY one, where `é` has its own key.
+    pub async fn type_text_with_dead_keys(
+        &self,
+        session: &Session<'_, Self>,
+        text: &str,
+    ) -> Result<(), Error> {
+        self.type_strokes(session, text, true).await
+    }
+
+    async fn type_strokes(
+        &self,
+        session: &Session<'_, Self>,
+        text: &str,
+        dead_keys: bool,
+    ) -> Result<(), Error> {
+        for stroke in text.chars().flat_map(|c| strokes(c, dead_keys)) {
+            if stroke.shift {
+                self.press_combo(session, &[Key::Shift, Key::Keysym(stroke.keysym)])
+                    .await?;
+            } else {
+                self.press_combo(session, &[Key::Keysym(stroke.keysym)])
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Press the `keys` in order, e.g. `&[Key::Control, Key::Alt,
+    /// Key::Char('t')]`, then release them in reverse order.
+    ///
+    /// The keys pressed so far are released if pressing one of them fails, and
+    /// all of them are released even if releasing one fails. The first error
+    /// is returned.
+    ///
+    /// **Note** only works if
+    /// [`DeviceType::Keyboard`](super::DeviceType::Keyboard) access was
+    /// provided after starting the session.
+    pub async fn press_combo(
+        &self,
+        session: &Session<'_, Self>,
+        keys: &[Key],
+    ) -> Result<(), Error> {
+        let mut result = Ok(());
+        let mut pressed = 0;
+        for key in keys {
+            result = self
+                .notify_keyboard_keysym(session, key.keysym(), KeyState::Pressed)
+                .await;
+            if result.is_err() {
+                break;
+            }
+            pressed += 1;
+        }
+        for key in keys[..pressed].iter().rev() {
+            let released = self
+                .notify_keyboard_keysym(session, key.keysym(), KeyState::Released)
+                .await;
+            result = result.and(released);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keysym(keysym: i32, shift: bool) -> Stroke {
+        Stroke { keysym, shift }
+    }
+
+    #[test]
+    fn char_strokes() {
+        assert_eq!(strokes('a', false), [keysym(0x61, false)]);
+        assert_eq!(strokes('A', false), [keysym(0x41, true)]);
+        assert_eq!(strokes('?', false), [keysym(0x3f, true)]);
+        assert_eq!(strokes('\n', false), [keysym(RETURN, false)]);
+        assert_eq!(strokes('ö', false), [keysym(0xf6, false)]);
+        assert_eq!(strokes('é', false), [keysym(0xe9, false)]);
+        assert_eq!(strokes('Ø', false), [keysym(0xd8, true)]);
+        assert_eq!(strokes('×', false), [keysym(0xd7, false)]);
+        assert_eq!(strokes('ő', false), [keysym(0x0100_0151, false)]);
+        assert_eq!(strokes('Č', false), [keysym(0x0100_010c, true)]);
+        assert_eq!(strokes('€', false), [keysym(0x0100_20ac, false)]);
+
+        // Only with dead keys.
+        assert_eq!(
+            strokes('ö', true),
+            [keysym(0xfe57, false), keysym(0x6f, false)]
+        );
+        assert_eq!(
+            strokes('Ö', true),
+            [keysym(0xfe57, false), keysym(0x4f, true)]
+        );
+        assert_eq!(
+            strokes('ç', true),
+            [keysym(0xfe5b, false), keysym(0x63, false)]
+        );
+        assert_eq!(
+            strokes('ő', true),
+            [keysym(0xfe59, false), keysym(0x6f, false)]
+        );
+        assert_eq!(
+            strokes('Č', true),
+            [keysym(0xfe5a, false), keysym(0x43, true)]
+        );
+        assert_eq!(strokes('Ø', true), [keysym(0xd8, true)]);
+        assert_eq!(strokes('a', true), [keysym(0x61, false)]);
+        for (_, composed, bases) in DEAD_KEYS {
+            assert_eq!(composed.chars().count(), bases.chars().count());
+        }
+
+        assert_eq!(Key::Char('t').keysym(), 0x74);
+        assert_eq!(Key::from('€').keysym(), 0x0100_20ac);
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod portal_tests {
+    use std::collections::HashMap;
+
+    use zbus::zvariant::{ObjectPath, OwnedValue};
+
+    use super::*;
+    use crate::testing::FakePortal;
+
+    const INTERFACE: &str = "org.freedesktop.portal.RemoteDesktop";
+
+    fn notified(portal: &FakePortal) -> Vec<(i32, u32)> {
+        portal
+            .calls(INTERFACE, "NotifyKeyboardKeysym")
+            .iter()
+            .map(|call| {
+                let (_, _, keysym, state) = call
+                    .body()
+                    .deserialize::<(ObjectPath<'_>, HashMap<String, OwnedValue>, i32, u32)>()
+                    .unwrap();
+                (keysym, state)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn combo_released_on_error() {
+        zbus::block_on(async {
+            let portal = FakePortal::new().await.unwrap();
+            let proxy = RemoteDesktop::with_connection(portal.connection())
+                .await
+                .unwrap();
+            let session = portal.session().await.unwrap();
+
+            portal.reply(INTERFACE, "NotifyKeyboardKeysym", ());
+            portal.reply(INTERFACE, "NotifyKeyboardKeysym", ());
+            portal.reply_error(
+                INTERFACE,
+                "NotifyKeyboardKeysym",
+                "org.freedesktop.portal.Error.Failed",
+                "Failed",
+            );
+            let err = proxy
+                .press_combo(&session, &[Key::Control, Key::Alt, Key::Char('t')])
+                .await
+                .unwrap_err();
+            assert!(matches!(err, Error::Portal(..)));
+            assert_eq!(
+                notified(&portal),
+                [
+                    (CONTROL_L, 1),
+                    (ALT_L, 1),
+                    (0x74, 1),
+                    (ALT_L, 0),
+                    (CONTROL_L, 0)
+                ]
+            );
+        });
+    }
+}
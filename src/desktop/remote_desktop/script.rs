@@ -0,0 +1,862 @@
+use std::{collections::BTreeSet, fmt, time::Duration};
+
+use serde::{de, Deserialize, Deserializer, Serialize};
+
+use super::{Axis, KeyState, RemoteDesktop};
+#[cfg(feature = "eis")]
+use crate::desktop::{
+    input_capture::{CapturedEvent, InputEvent},
+    screencast::ScreencastLayout,
+};
+use crate::{desktop::Session, helpers::sleep, Error};
+
+/// The version of the [`Script`] format.
+const SCRIPT_VERSION: u32 = 1;
+
+/// Scroll wheel clicks are reported in fractions of 120 by input capture.
+#[cfg(feature = "eis")]
+const WHEEL_CLICK: i32 = 120;
+
+/// An input event of a [`Script`], one of the `notify_*` calls of
+/// [`RemoteDesktop`].
+///
+/// Absolute positions are in logical pixels relative to the stream of
+/// [`pipe_wire_node_id`](crate::desktop::screencast::Stream::pipe_wire_node_id)
+/// `stream`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum Action {
+    /// See [`RemoteDesktop::notify_pointer_motion`].
+    PointerMotion {
+        /// The horizontal motion.
+        dx: f64,
+        /// The vertical motion.
+        dy: f64,
+    },
+    /// See [`RemoteDesktop::notify_pointer_motion_absolute`].
+    PointerMotionAbsolute {
+        /// The stream the position is on.
+        stream: u32,
+        /// The horizontal position.
+        x: f64,
+        /// The vertical position.
+        y: f64,
+    },
+    /// See [`RemoteDesktop::notify_pointer_button`].
+    PointerButton {
+        /// The evdev button code.
+        button: i32,
+        /// Whether the button is pressed or released.
+        state: KeyState,
+    },
+    /// See [`RemoteDesktop::notify_pointer_axis`].
+    PointerAxis {
+        /// The horizontal scroll distance.
+        dx: f64,
+        /// The vertical scroll distance.
+        dy: f64,
+        /// Whether the scroll motion is finished.
+        finish: bool,
+    },
+    /// See [`RemoteDesktop::notify_pointer_axis_discrete`].
+    PointerAxisDiscrete {
+        /// The scrolled axis.
+        axis: Axis,
+        /// The number of scroll wheel clicks.
+        steps: i32,
+    },
+    /// See [`RemoteDesktop::notify_keyboard_keycode`].
+    KeyboardKeycode {
+        /// The evdev key code.
+        keycode: i32,
+        /// Whether the key is pressed or released.
+        state: KeyState,
+    },
+    /// See [`RemoteDesktop::notify_keyboard_keysym`].
+    KeyboardKeysym {
+        /// The keysym.
+        keysym: i32,
+        /// Whether the key is pressed or released.
+        state: KeyState,
+    },
+    /// See [`RemoteDesktop::notify_touch_down`].
+    TouchDown {
+        /// The stream the position is on.
+        stream: u32,
+        /// The touch slot.
+        slot: u32,
+        /// The horizontal position.
+        x: f64,
+        /// The vertical position.
+        y: f64,
+    },
+    /// See [`RemoteDesktop::notify_touch_motion`].
+    TouchMotion {
+        /// The stream the position is on.
+        stream: u32,
+        /// The touch slot.
+        slot: u32,
+        /// The horizontal position.
+        x: f64,
+        /// The vertical position.
+        y: f64,
+    },
+    /// See [`RemoteDesktop::notify_touch_up`].
+    TouchUp {
+        /// The touch slot.
+        slot: u32,
+    },
+}
+
+/// An [`Action`] of a [`Script`] with the time since the previous one.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Step {
+    delay_ms: u64,
+    #[serde(flatten)]
+    action: Action,
+}
+
+impl Step {
+    /// The time since the previous step.
+    pub fn delay(&self) -> Duration {
+        Duration::from_millis(self.delay_ms)
+    }
+
+    /// The input event of the step.
+    pub fn action(&self) -> Action {
+        self.action
+    }
+}
+
+/// A sequence of input events with their relative timing, replayed with a
+/// [`ScriptPlayer`].
+///
+/// Scripts can be stored with any serde format, e.g. as JSON:
+///
+/// ```json
+/// {
+///   "version": 1,
+///   "steps": [
+///     { "delay_ms": 0, "type": "pointer_motion_absolute", "stream": 42, "x": 10.0, "y": 20.0 },
+///     { "delay_ms": 100, "type": "pointer_button", "button": 272, "state": 1 },
+///     { "delay_ms": 50, "type": "pointer_button", "button": 272, "state": 0 }
+///   ]
+/// }
+/// ```
+///
+/// Scripts of a newer version of the format fail to deserialize.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Script {
+    #[serde(deserialize_with = "deserialize_version")]
+    version: u32,
+    steps: Vec<Step>,
+}
+
+fn deserialize_version<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+    let version = u32::deserialize(deserializer)?;
+    if version == 0 || version > SCRIPT_VERSION {
+        return Err(de::Error::custom(format!(
+            "unsupported script version {version}"
+        )));
+    }
+    Ok(version)
+}
+
+impl Default for Script {
+    fn default() -> Self {
+        Self {
+            version: SCRIPT_VERSION,
+            steps: Vec::new(),
+        }
+    }
+}
+
+impl Script {
+    /// Create an empty [`Script`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The version of the format of the script.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Append `action`, `delay` after the previous step. The delay is
+    /// truncated to milliseconds.
+    pub fn push(&mut self, delay: Duration, action: Action) {
+        self.steps.push(Step {
+            delay_ms: delay.as_millis() as u64,
+            action,
+        });
+    }
+
+    /// Append `action`, `delay` after the previous step.
+    #[must_use]
+    pub fn then(mut self, delay: Duration, action: Action) -> Self {
+        self.push(delay, action);
+        self
+    }
+
+    /// The steps of the script.
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    /// The time the script takes at normal speed.
+    pub fn duration(&self) -> Duration {
+        self.steps.iter().map(Step::delay).sum()
+    }
+}
+
+/// `delay` at `speed`, saturating for tiny speeds.
+fn scaled(delay: Duration, speed: f64) -> Duration {
+    Duration::try_from_secs_f64(delay.as_secs_f64() / speed).unwrap_or(Duration::MAX)
+}
+
+/// The input held down by a script, released once it ends.
+#[derive(Debug, Default)]
+struct Held {
+    keycodes: BTreeSet<i32>,
+    keysyms: BTreeSet<i32>,
+    buttons: BTreeSet<i32>,
+    slots: BTreeSet<u32>,
+}
+
+impl Held {
+    fn track(&mut self, action: &Action) {
+        let update = |set: &mut BTreeSet<i32>, value, state| match state {
+            KeyState::Pressed => set.insert(value),
+            KeyState::Released => set.remove(&value),
+        };
+        match *action {
+            Action::KeyboardKeycode { keycode, state } => {
+                update(&mut self.keycodes, keycode, state);
+            }
+            Action::KeyboardKeysym { keysym, state } => {
+                update(&mut self.keysyms, keysym, state);
+            }
+            Action::PointerButton { button, state } => {
+                update(&mut self.buttons, button, state);
+            }
+            Action::TouchDown { slot, .. } => {
+                self.slots.insert(slot);
+            }
+            Action::TouchUp { slot } => {
+                self.slots.remove(&slot);
+            }
+            _ => {}
+        }
+    }
+
+    /// The actions releasing the held input.
+    fn releases(self) -> Vec<Action> {
+        let state = KeyState::Released;
+        let keycodes = self
+            .keycodes
+            .into_iter()
+            .map(|keycode| Action::KeyboardKeycode { keycode, state });
+        let keysyms = self
+            .keysyms
+            .into_iter()
+            .map(|keysym| Action::KeyboardKeysym { keysym, state });
+        let buttons = self
+            .buttons
+            .into_iter()
+            .map(|button| Action::PointerButton { button, state });
+        let slots = self.slots.into_iter().map(|slot| Action::TouchUp { slot });
+        keycodes
+            .chain(keysyms)
+            .chain(buttons)
+            .chain(slots)
+            .collect()
+    }
+}
+
+/// Replay a [`Script`] on a [`RemoteDesktop`] session.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use ashpd::desktop::{
+///     remote_desktop::{RemoteDesktop, Script, ScriptPlayer},
+///     Session,
+/// };
+///
+/// async fn run(
+///     proxy: &RemoteDesktop<'_>,
+///     session: &Session<'_, RemoteDesktop<'_>>,
+///     stream: u32,
+/// ) -> Result<(), Box<dyn std::error::Error>> {
+///     let script: Script = serde_json::from_str(&std::fs::read_to_string("login.json")?)?;
+///     ScriptPlayer::new(proxy, session)
+///         .stream(stream)
+///         .speed(2.0)
+///         .play(&script)
+///         .await?;
+///     Ok(())
+/// }
+/// ```
+pub struct ScriptPlayer<'a> {
+    proxy: &'a RemoteDesktop<'a>,
+    session: &'a Session<'a, RemoteDesktop<'a>>,
+    speed: f64,
+    stream: Option<u32>,
+}
+
+impl<'a> ScriptPlayer<'a> {
+    /// Create a new [`ScriptPlayer`] replaying on `session`.
+    pub fn new(proxy: &'a RemoteDesktop<'a>, session: &'a Session<'a, RemoteDesktop<'a>>) -> Self {
+        Self {
+            proxy,
+            session,
+            speed: 1.0,
+            stream: None,
+        }
+    }
+
+    /// The speed of the replay, defaults to `1.0`. A speed of `2.0` replays
+    /// twice as fast, [`f64::INFINITY`] without waiting between steps.
+    ///
+    /// Replaying fails with an [`std::io::ErrorKind::InvalidInput`] error
+    /// if the speed isn't positive.
+    #[must_use]
+    pub fn speed(mut self, speed: f64) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// The stream to replay the absolute positions on, instead of the
+    /// recorded one, as the PipeWire node ids differ between sessions.
+    #[must_use]
+    pub fn stream(mut self, stream: impl Into<Option<u32>>) -> Self {
+        self.stream = stream.into();
+        self
+    }
+
+    /// Replay `script`, returning once its last step is performed.
+    ///
+    /// The keys, buttons and touch points still held when the script ends or
+    /// fails are released, and the first error is returned.
+    pub async fn play(&self, script: &Script) -> Result<(), Error> {
+        if self.speed.is_nan() || self.speed <= 0.0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("Invalid replay speed: {}", self.speed),
+            )
+            .into());
+        }
+        let start = std::time::Instant::now();
+        let mut elapsed = Duration::ZERO;
+        let mut held = Held::default();
+        let mut result = Ok(());
+        for step in script.steps() {
+            // Waiting for the scaled time since the start doesn't accumulate
+            // the latency of the calls.
+            elapsed = elapsed.saturating_add(scaled(step.delay(), self.speed));
+            if let Some(delay) = elapsed.checked_sub(start.elapsed()) {
+                sleep(delay).await;
+            }
+            result = self.perform(step.action()).await;
+            if result.is_err() {
+                break;
+            }
+            held.track(&step.action());
+        }
+        for release in held.releases() {
+            let released = self.perform(release).await;
+            result = result.and(released);
+        }
+        result
+    }
+
+    async fn perform(&self, action: Action) -> Result<(), Error> {
+        let (proxy, session) = (self.proxy, self.session);
+        let stream = |recorded| self.stream.unwrap_or(recorded);
+        match action {
+            Action::PointerMotion { dx, dy } => proxy.notify_pointer_motion(session, dx, dy).await,
+            Action::PointerMotionAbsolute { stream: s, x, y } => {
+                proxy
+                    .notify_pointer_motion_absolute(session, stream(s), x, y)
+                    .await
+            }
+            Action::PointerButton { button, state } => {
+                proxy.notify_pointer_button(session, button, state).await
+            }
+            Action::PointerAxis { dx, dy, finish } => {
+                proxy.notify_pointer_axis(session, dx, dy, finish).await
+            }
+            Action::PointerAxisDiscrete { axis, steps } => {
+                proxy
+                    .notify_pointer_axis_discrete(session, axis, steps)
+                    .await
+            }
+            Action::KeyboardKeycode { keycode, state } => {
+                proxy.notify_keyboard_keycode(session, keycode, state).await
+            }
+            Action::KeyboardKeysym { keysym, state } => {
+                proxy.notify_keyboard_keysym(session, keysym, state).await
+            }
+            Action::TouchDown {
+                stream: s,
+                slot,
+                x,
+                y,
+            } => {
+                proxy
+                    .notify_touch_down(session, stream(s), slot, x, y)
+                    .await
+            }
+            Action::TouchMotion {
+                stream: s,
+                slot,
+                x,
+                y,
+            } => {
+                proxy
+                    .notify_touch_motion(session, stream(s), slot, x, y)
+                    .await
+            }
+            Action::TouchUp { slot } => proxy.notify_touch_up(session, slot).await,
+        }
+    }
+}
+
+impl fmt::Debug for ScriptPlayer<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScriptPlayer")
+            .field("session", &self.session)
+            .field("speed", &self.speed)
+            .field("stream", &self.stream)
+            .finish()
+    }
+}
+
+/// Build a [`Script`] from the events captured by an
+/// [`InputCapture`](crate::desktop::input_capture::InputCapture) session.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use ashpd::desktop::{
+///     input_capture::InputCapture,
+///     remote_desktop::{Script, ScriptRecorder},
+///     Session,
+/// };
+/// use futures_util::{StreamExt, TryStreamExt};
+///
+/// async fn run(
+///     proxy: &InputCapture<'_>,
+///     session: &Session<'_, InputCapture<'_>>,
+/// ) -> ashpd::Result<Script> {
+///     let mut recorder = ScriptRecorder::new();
+///     let mut events = std::pin::pin!(proxy.events(session).await?.take(1000));
+///     while let Some(event) = events.try_next().await? {
+///         recorder.record(&event);
+///     }
+///     Ok(recorder.finish())
+/// }
+/// ```
+#[cfg(feature = "eis")]
+#[cfg_attr(docsrs, doc(cfg(feature = "eis")))]
+#[derive(Debug, Default)]
+pub struct ScriptRecorder {
+    script: Script,
+    stream: u32,
+    layout: Option<ScreencastLayout>,
+    /// The time of the last step, in microseconds.
+    time: Option<u64>,
+    /// The discrete scrolling not recorded yet, less than a wheel click on
+    /// each axis.
+    scroll: (i32, i32),
+}
+
+#[cfg(feature = "eis")]
+impl ScriptRecorder {
+    /// Create a new [`ScriptRecorder`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The stream recorded for the absolute positions, defaults to zero. It
+    /// can be replaced at replay with [`ScriptPlayer::stream`].
+    ///
+    /// **Note** the positions are captured in the logical coordinates of the
+    /// whole desktop. They are only relative to the stream if it covers a
+    /// single monitor at the origin, see [`ScriptRecorder::layout`] otherwise.
+    #[must_use]
+    pub fn stream(mut self, stream: u32) -> Self {
+        self.stream = stream;
+        self
+    }
+
+    /// Sets the layout of the screen cast streams, to record the absolute
+    /// positions relative to the stream containing them, instead of
+    /// [`ScriptRecorder::stream`]. The positions outside of the streams are
+    /// dropped.
+    #[must_use]
+    pub fn layout(mut self, layout: ScreencastLayout) -> Self {
+        self.layout = Some(layout);
+        self
+    }
+
+    /// Append the input of `event`, timed relatively to the previous one.
+    pub fn record(&mut self, event: &CapturedEvent) {
+        self.record_at(event.time(), event.event());
+    }
+
+    fn record_at(&mut self, time: u64, event: InputEvent) {
+        let previous = *self.time.get_or_insert(time);
+        // The delays are in whole milliseconds, the remainder is carried over
+        // to the next step so that they don't drift.
+        let delay = Duration::from_millis(time.saturating_sub(previous) / 1000);
+        self.time = Some(previous + delay.as_micros() as u64);
+        for (index, action) in self.actions(event).into_iter().enumerate() {
+            let delay = if index == 0 { delay } else { Duration::ZERO };
+            self.script.push(delay, action);
+        }
+    }
+
+    /// The stream containing the captured position `(x, y)`, and the position
+    /// relative to it.
+    fn stream_position(&self, x: f32, y: f32) -> Option<(u32, f64, f64)> {
+        let (x, y) = (f64::from(x), f64::from(y));
+        match &self.layout {
+            Some(layout) => layout.logical_to_stream(x, y),
+            None => Some((self.stream, x, y)),
+        }
+    }
+
+    fn actions(&mut self, event: InputEvent) -> Vec<Action> {
+        let float = f64::from;
+        match event {
+            InputEvent::PointerMotion { dx, dy } => vec![Action::PointerMotion {
+                dx: float(dx),
+                dy: float(dy),
+            }],
+            InputEvent::PointerMotionAbsolute { x, y } => self
+                .stream_position(x, y)
+                .map(|(stream, x, y)| Action::PointerMotionAbsolute { stream, x, y })
+                .into_iter()
+                .collect(),
+            InputEvent::Button { button, state } => vec![Action::PointerButton {
+                button: button as i32,
+                state,
+            }],
+            InputEvent::Scroll { dx, dy } => vec![Action::PointerAxis {
+                dx: float(dx),
+                dy: float(dy),
+                finish: false,
+            }],
+            InputEvent::ScrollDiscrete { dx, dy } => [
+                (Axis::Horizontal, &mut self.scroll.0, dx),
+                (Axis::Vertical, &mut self.scroll.1, dy),
+            ]
+            .into_iter()
+            .filter_map(|(axis, remainder, delta)| {
+                // High-resolution wheels scroll by fractions of a click.
+                *remainder += delta;
+                let steps = *remainder / WHEEL_CLICK;
+                *remainder %= WHEEL_CLICK;
+                (steps != 0).then_some(Action::PointerAxisDiscrete { axis, steps })
+            })
+            .collect(),
+            InputEvent::ScrollStop { .. } | InputEvent::ScrollCancel { .. } => {
+                vec![Action::PointerAxis {
+                    dx: 0.0,
+                    dy: 0.0,
+                    finish: true,
+                }]
+            }
+            InputEvent::Key { key, state } => vec![Action::KeyboardKeycode {
+                keycode: key as i32,
+                state,
+            }],
+            InputEvent::TouchDown { id, x, y } => self
+                .stream_position(x, y)
+                .map(|(stream, x, y)| Action::TouchDown {
+                    stream,
+                    slot: id,
+                    x,
+                    y,
+                })
+                .into_iter()
+                .collect(),
+            InputEvent::TouchMotion { id, x, y } => self
+                .stream_position(x, y)
+                .map(|(stream, x, y)| Action::TouchMotion {
+                    stream,
+                    slot: id,
+                    x,
+                    y,
+                })
+                .into_iter()
+                .collect(),
+            InputEvent::TouchUp { id } => vec![Action::TouchUp { slot: id }],
+        }
+    }
+
+    /// The script recorded so far.
+    pub fn script(&self) -> &Script {
+        &self.script
+    }
+
+    /// Stop recording, returning the script.
+    pub fn finish(self) -> Script {
+        self.script
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_format() {
+        let script = Script::new()
+            .then(
+                Duration::ZERO,
+                Action::PointerMotionAbsolute {
+                    stream: 42,
+                    x: 10.0,
+                    y: 20.0,
+                },
+            )
+            .then(
+                Duration::from_millis(100),
+                Action::PointerButton {
+                    button: 272,
+                    state: KeyState::Pressed,
+                },
+            );
+        let json = serde_json::to_string(&script).unwrap();
+        assert_eq!(
+            json,
+            r#"{"version":1,"steps":[{"delay_ms":0,"type":"pointer_motion_absolute","stream":42,"x":10.0,"y":20.0},{"delay_ms":100,"type":"pointer_button","button":272,"state":1}]}"#
+        );
+        assert_eq!(serde_json::from_str::<Script>(&json).unwrap(), script);
+        assert_eq!(script.duration(), Duration::from_millis(100));
+        assert!(serde_json::from_str::<Script>(r#"{"version":2,"steps":[]}"#).is_err());
+        assert!(serde_json::from_str::<Script>(r#"{"steps":[]}"#).is_err());
+
+        assert_eq!(
+            scaled(Duration::from_millis(100), 2.0),
+            Duration::from_millis(50)
+        );
+        assert_eq!(
+            scaled(Duration::from_millis(100), f64::INFINITY),
+            Duration::ZERO
+        );
+        assert_eq!(scaled(Duration::from_secs(1), 1e-300), Duration::MAX);
+    }
+
+    #[cfg(feature = "eis")]
+    #[test]
+    fn recorded_timing() {
+        let mut recorder = ScriptRecorder::new().stream(7);
+        recorder.record_at(
+            1_000_400,
+            InputEvent::PointerMotionAbsolute { x: 1.0, y: 2.0 },
+        );
+        recorder.record_at(1_001_900, InputEvent::ScrollDiscrete { dx: 120, dy: -240 });
+        recorder.record_at(1_003_500, InputEvent::TouchUp { id: 3 });
+        let steps: Vec<_> = recorder
+            .finish()
+            .steps()
+            .iter()
+            .map(|step| (step.delay_ms, step.action()))
+            .collect();
+        // The remaining 0.5 ms of the first delay is carried over to the next.
+        assert_eq!(
+            steps,
+            [
+                (
+                    0,
+                    Action::PointerMotionAbsolute {
+                        stream: 7,
+                        x: 1.0,
+                        y: 2.0
+                    }
+                ),
+                (
+                    1,
+                    Action::PointerAxisDiscrete {
+                        axis: Axis::Horizontal,
+                        steps: 1
+                    }
+                ),
+                (
+                    0,
+                    Action::PointerAxisDiscrete {
+                        axis: Axis::Vertical,
+                        steps: -2
+                    }
+                ),
+                (2, Action::TouchUp { slot: 3 }),
+            ]
+        );
+    }
+
+    #[cfg(all(feature = "eis", any(feature = "backend", feature = "testing")))]
+    #[test]
+    fn recorded_positions_and_scrolling() {
+        use crate::desktop::screencast::Stream;
+
+        let streams = [
+            Stream::builder(40)
+                .position((0, 0))
+                .size((1920, 1080))
+                .build(),
+            Stream::builder(41)
+                .position((1920, 0))
+                .size((1280, 1024))
+                .build(),
+        ];
+        let mut recorder = ScriptRecorder::new().layout(ScreencastLayout::new(&streams));
+        recorder.record_at(
+            0,
+            InputEvent::PointerMotionAbsolute {
+                x: 2000.0,
+                y: 100.0,
+            },
+        );
+        // Outside of the streams.
+        recorder.record_at(
+            0,
+            InputEvent::TouchDown {
+                id: 1,
+                x: 2000.0,
+                y: 1050.0,
+            },
+        );
+        // High-resolution scrolling adds up to whole clicks.
+        recorder.record_at(0, InputEvent::ScrollDiscrete { dx: 0, dy: 60 });
+        recorder.record_at(0, InputEvent::ScrollDiscrete { dx: 0, dy: 90 });
+        recorder.record_at(0, InputEvent::ScrollDiscrete { dx: 0, dy: -150 });
+        let actions: Vec<_> = recorder.finish().steps().iter().map(Step::action).collect();
+        assert_eq!(
+            actions,
+            [
+                Action::PointerMotionAbsolute {
+                    stream: 41,
+                    x: 80.0,
+                    y: 100.0
+                },
+                Action::PointerAxisDiscrete {
+                    axis: Axis::Vertical,
+                    steps: 1
+                },
+                Action::PointerAxisDiscrete {
+                    axis: Axis::Vertical,
+                    steps: -1
+                },
+            ]
+        );
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod portal_tests {
+    use std::collections::HashMap;
+
+    use zbus::zvariant::{ObjectPath, OwnedValue};
+
+    use super::*;
+    use crate::testing::FakePortal;
+
+    const INTERFACE: &str = "org.freedesktop.portal.RemoteDesktop";
+
+    #[test]
+    fn held_input_released() {
+        zbus::block_on(async {
+            let portal = FakePortal::new().await.unwrap();
+            let proxy = RemoteDesktop::with_connection(portal.connection())
+                .await
+                .unwrap();
+            let session = portal.session().await.unwrap();
+
+            let pressed = KeyState::Pressed;
+            let script = Script::new()
+                .then(
+                    Duration::ZERO,
+                    Action::KeyboardKeycode {
+                        keycode: 29,
+                        state: pressed,
+                    },
+                )
+                .then(
+                    Duration::from_millis(20),
+                    Action::PointerButton {
+                        button: 272,
+                        state: pressed,
+                    },
+                )
+                .then(
+                    Duration::from_millis(20),
+                    Action::TouchDown {
+                        stream: 1,
+                        slot: 0,
+                        x: 5.0,
+                        y: 5.0,
+                    },
+                );
+            portal.reply(INTERFACE, "NotifyKeyboardKeycode", ());
+            portal.reply(INTERFACE, "NotifyPointerButton", ());
+            portal.reply_error(
+                INTERFACE,
+                "NotifyTouchDown",
+                "org.freedesktop.portal.Error.Failed",
+                "Failed",
+            );
+            let err = ScriptPlayer::new(&proxy, &session)
+                .speed(0.0)
+                .play(&script)
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(err, Error::IO(ref err) if err.kind() == std::io::ErrorKind::InvalidInput)
+            );
+
+            let start = std::time::Instant::now();
+            let err = ScriptPlayer::new(&proxy, &session)
+                .speed(2.0)
+                .stream(42)
+                .play(&script)
+                .await
+                .unwrap_err();
+            assert!(matches!(err, Error::Portal(..)));
+            assert!(start.elapsed() >= Duration::from_millis(20));
+
+            let (_, _, stream, ..) = portal.calls(INTERFACE, "NotifyTouchDown")[0]
+                .body()
+                .deserialize::<(
+                    ObjectPath<'_>,
+                    HashMap<String, OwnedValue>,
+                    u32,
+                    u32,
+                    f64,
+                    f64,
+                )>()
+                .unwrap();
+            assert_eq!(stream, 42);
+            let states = |method| {
+                portal
+                    .calls(INTERFACE, method)
+                    .iter()
+                    .map(|call| {
+                        let (_, _, code, state) = call
+                            .body()
+                            .deserialize::<(ObjectPath<'_>, HashMap<String, OwnedValue>, i32, u32)>(
+                            )
+                            .unwrap();
+                        (code, state)
+                    })
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(states("NotifyKeyboardKeycode"), [(29, 1), (29, 0)]);
+            assert_eq!(states("NotifyPointerButton"), [(272, 1), (272, 0)]);
+            assert!(portal.calls(INTERFACE, "NotifyTouchUp").is_empty());
+        });
+    }
+}
@@ -1,8 +1,11 @@
 use std::{
     collections::HashMap,
     fmt::{self, Debug},
+    future::Future,
     marker::PhantomData,
-    sync::Mutex,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
 };
 
 use futures_util::StreamExt;
@@ -13,10 +16,10 @@ use serde::{
 };
 use zbus::{
     proxy::SignalStream,
-    zvariant::{ObjectPath, Type, Value},
+    zvariant::{ObjectPath, OwnedValue, Type, Value},
 };
 
-use crate::{desktop::HandleToken, proxy::Proxy, Error};
+use crate::{desktop::HandleToken, proxy::Proxy, Error, ErrorDetails};
 
 /// A typical response returned by the [`Request::response`].
 /// of a [`Request`].
@@ -32,19 +35,23 @@ where
     Err(ResponseError),
 }
 
-#[cfg(feature = "backend")]
+#[cfg(any(feature = "backend", feature = "testing"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "backend", feature = "testing"))))]
 impl<T> Response<T>
 where
     T: for<'de> Deserialize<'de> + Type,
 {
+    /// Create a successful response carrying the results.
     pub fn ok(inner: T) -> Self {
         Self::Ok(inner)
     }
 
+    /// Create a response for a request that was cancelled by the user.
     pub fn cancelled() -> Self {
         Self::Err(ResponseError::Cancelled)
     }
 
+    /// Create a response for a request that was ended in some other way.
     pub fn other() -> Self {
         Self::Err(ResponseError::Other)
     }
@@ -184,69 +191,107 @@ impl From<ResponseError> for ResponseType {
 /// user interaction related to the method call.
 ///
 /// The portal indicates that a portal request interaction is over by emitting
-/// the "Response" signal on the Request object.
+/// the "Response" signal on the Request object. A [`Request`] is a future that
+/// resolves to the content of that signal, it is returned as soon as the
+/// portal created the request handle.
 ///
 /// The application can abort the interaction calling
-/// [`close()`][`Request::close`] on the Request object.
+/// [`close()`][`Request::close`] on the Request object, or ask for it to be
+/// closed once dropped with [`close_on_drop()`][`Request::close_on_drop`].
+///
+/// Like other futures, the request panics if it is polled again after it
+/// resolved to the response.
 ///
 /// Wrapper of the DBus interface: [`org.freedesktop.portal.Request`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.portal.Request.html).
 #[doc(alias = "org.freedesktop.portal.Request")]
-pub struct Request<T>(
-    Proxy<'static>,
-    SignalStream<'static>,
-    Mutex<Option<Result<T, Error>>>,
-    PhantomData<T>,
-)
+pub struct Request<T>
 where
-    T: for<'de> Deserialize<'de> + Type + Debug;
+    T: for<'de> Deserialize<'de> + Type + Debug,
+{
+    proxy: Proxy<'static>,
+    stream: SignalStream<'static>,
+    timeout: Option<Pin<Box<dyn Future<Output = ()> + Send + Sync>>>,
+    close_on_drop: bool,
+    responded: bool,
+    call: Option<(String, String)>,
+    _phantom: PhantomData<fn() -> T>,
+}
 
 impl<T> Request<T>
 where
     T: for<'de> Deserialize<'de> + Type + Debug,
 {
-    pub(crate) async fn new<P>(path: P) -> Result<Request<T>, Error>
+    pub(crate) async fn new<P>(connection: &zbus::Connection, path: P) -> Result<Request<T>, Error>
     where
         P: TryInto<ObjectPath<'static>>,
         P::Error: Into<zbus::Error>,
     {
-        let proxy = Proxy::new_desktop_with_path("org.freedesktop.portal.Request", path).await?;
+        let proxy =
+            Proxy::new_desktop_with_path(connection, "org.freedesktop.portal.Request", path)
+                .await?;
         // Start listening for a response signal the moment request is created
         let stream = proxy.receive_signal("Response").await?;
-        Ok(Self(proxy, stream, Default::default(), PhantomData))
+        Ok(Self {
+            proxy,
+            stream,
+            timeout: None,
+            close_on_drop: false,
+            responded: false,
+            call: None,
+            _phantom: PhantomData,
+        })
     }
 
-    pub(crate) async fn from_unique_name(handle_token: &HandleToken) -> Result<Request<T>, Error> {
-        let path =
-            Proxy::unique_name("/org/freedesktop/portal/desktop/request", handle_token).await?;
+    pub(crate) async fn from_unique_name(
+        connection: &zbus::Connection,
+        handle_token: &HandleToken,
+    ) -> Result<Request<T>, Error> {
+        let path = Proxy::unique_name(
+            connection,
+            "/org/freedesktop/portal/desktop/request",
+            handle_token,
+        )?;
         #[cfg(feature = "tracing")]
         tracing::info!("Creating a org.freedesktop.portal.Request {}", path);
-        Self::new(path).await
+        Self::new(connection, path).await
     }
 
-    pub(crate) async fn prepare_response(&mut self) -> Result<(), Error> {
-        let message = self.1.next().await.ok_or(Error::NoResponse)?;
-        #[cfg(feature = "tracing")]
-        tracing::info!("Received signal 'Response' on '{}'", self.0.interface());
-        let response = match message.body().deserialize::<Response<T>>()? {
-            Response::Err(e) => Err(e.into()),
-            Response::Ok(r) => Ok(r),
-        };
-        #[cfg(feature = "tracing")]
-        tracing::debug!("Received response {:#?}", response);
-        let r = response as Result<T, Error>;
-        *self.2.get_mut().unwrap() = Some(r);
-        Ok(())
+    /// The portal method call that created the request, reported in the
+    /// [`ErrorDetails`] of a failed response.
+    pub(crate) fn called_by(mut self, interface: &str, method: &str) -> Self {
+        self.call = Some((interface.to_owned(), method.to_owned()));
+        self
+    }
+
+    /// Fail with [`Error::Timeout`] if the portal doesn't respond within
+    /// `timeout`.
+    ///
+    /// The delay starts counting down when this method is called. The user
+    /// interaction is not ended when the delay expires, use
+    /// [`close_on_drop()`][`Request::close_on_drop`] for that.
+    #[must_use]
+    pub fn response_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(Box::pin(crate::helpers::sleep(timeout)));
+        self
+    }
+
+    /// Whether to [`close()`][`Request::close`] the request when it is
+    /// dropped before the portal responded, defaults to `false`.
+    #[must_use]
+    pub fn close_on_drop(mut self, close_on_drop: bool) -> Self {
+        self.close_on_drop = close_on_drop;
+        self
     }
 
-    /// The corresponding response if the request was successful.
+    /// Wait for the response of the portal.
+    ///
+    /// Same as awaiting the request.
     ///
     /// # Specifications
     ///
     /// See also [`Response`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.portal.Request.html#org-freedesktop-portal-request-response).
-    pub fn response(&self) -> Result<T, Error> {
-        // It should be safe to unwrap here as we are sure we have received a response
-        // by the time the user calls response
-        self.2.lock().unwrap().take().unwrap()
+    pub async fn response(self) -> Result<T, Error> {
+        self.await
     }
 
     /// Closes the portal request to which this object refers and ends all
@@ -258,11 +303,86 @@ where
     /// See also [`Close`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.portal.Request.html#org-freedesktop-portal-request-close).
     #[doc(alias = "Close")]
     pub async fn close(&self) -> Result<(), Error> {
-        self.0.call("Close", &()).await
+        self.proxy.call("Close", &()).await
     }
 
     pub(crate) fn path(&self) -> &ObjectPath<'_> {
-        self.0.path()
+        self.proxy.path()
+    }
+
+    fn parse_response(&self, message: Option<zbus::Message>) -> Result<T, Error> {
+        let message = message.ok_or(Error::NoResponse)?;
+        #[cfg(feature = "tracing")]
+        tracing::info!(
+            "Received signal 'Response' on '{}'",
+            message.header().path().unwrap()
+        );
+        let response = match message.body().deserialize::<Response<T>>()? {
+            Response::Err(e) => {
+                let (_, results) = message
+                    .body()
+                    .deserialize::<(u32, HashMap<String, OwnedValue>)>()?;
+                let call = self
+                    .call
+                    .as_ref()
+                    .map(|(interface, method)| (interface.as_str(), method.as_str()));
+                Err(Error::Response(
+                    e,
+                    Box::new(ErrorDetails::request(call, self.path(), results)),
+                ))
+            }
+            Response::Ok(r) => Ok(r),
+        };
+        #[cfg(feature = "tracing")]
+        tracing::debug!("Received response {:#?}", response);
+        response
+    }
+}
+
+impl<T> Future for Request<T>
+where
+    T: for<'de> Deserialize<'de> + Type + Debug,
+{
+    type Output = Result<T, Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        assert!(!self.responded, "`Request` polled after completion");
+        if let Poll::Ready(message) = self.stream.poll_next_unpin(cx) {
+            self.responded = true;
+            return Poll::Ready(self.parse_response(message));
+        }
+        if let Some(timeout) = self.timeout.as_mut() {
+            if timeout.as_mut().poll(cx).is_ready() {
+                self.timeout = None;
+                return Poll::Ready(Err(Error::Timeout));
+            }
+        }
+        Poll::Pending
+    }
+}
+
+impl<T> Drop for Request<T>
+where
+    T: for<'de> Deserialize<'de> + Type + Debug,
+{
+    fn drop(&mut self) {
+        if !self.close_on_drop || self.responded {
+            return;
+        }
+        let proxy = zbus::Proxy::clone(&self.proxy);
+        #[cfg(feature = "tracing")]
+        tracing::info!("Closing dropped request {}", proxy.path());
+        proxy
+            .connection()
+            .clone()
+            .executor()
+            .spawn(
+                async move {
+                    let _ = proxy.call_noreply("Close", &()).await;
+                },
+                "ashpd request close",
+            )
+            .detach();
     }
 }
 
@@ -296,4 +416,78 @@ mod tests {
         );
         assert_eq!(Response::<()>::signature(), "(ua{sv})");
     }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn failed_response_details() {
+        use crate::{helpers::sleep, testing::FakePortal, ErrorKind};
+
+        const PATH: &str = "/org/freedesktop/portal/desktop/request/1_2/ashpd_test";
+
+        zbus::block_on(async {
+            let portal = FakePortal::new().await.unwrap();
+            let request = Request::<()>::new(portal.connection(), PATH)
+                .await
+                .unwrap()
+                .called_by("org.freedesktop.portal.Screenshot", "Screenshot");
+
+            let results = HashMap::from([("reason", Value::from("No screen"))]);
+            let (response, emitted) = futures_util::join!(request.response(), async {
+                sleep(Duration::from_millis(50)).await;
+                portal
+                    .emit_signal_at(
+                        PATH,
+                        "org.freedesktop.portal.Request",
+                        "Response",
+                        &(2u32, results),
+                    )
+                    .await
+            });
+            emitted.unwrap();
+
+            let err = response.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::Other);
+            let details = err.details().unwrap();
+            assert_eq!(
+                details.interface(),
+                Some("org.freedesktop.portal.Screenshot")
+            );
+            assert_eq!(details.method(), Some("Screenshot"));
+            assert_eq!(details.request_path().unwrap().as_str(), PATH);
+            assert_eq!(
+                details.results()["reason"].downcast_ref::<&str>().unwrap(),
+                "No screen"
+            );
+        });
+    }
+    #[cfg(feature = "testing")]
+    #[test]
+    #[should_panic = "`Request` polled after completion"]
+    fn polled_after_response() {
+        use crate::{helpers::sleep, testing::FakePortal};
+
+        const PATH: &str = "/org/freedesktop/portal/desktop/request/1_2/ashpd_test";
+
+        zbus::block_on(async {
+            let portal = FakePortal::new().await.unwrap();
+            let request = Request::<()>::new(portal.connection(), PATH).await.unwrap();
+            let mut request = std::pin::pin!(request);
+
+            let (response, emitted) = futures_util::join!(request.as_mut(), async {
+                sleep(Duration::from_millis(50)).await;
+                portal
+                    .emit_signal_at(
+                        PATH,
+                        "org.freedesktop.portal.Request",
+                        "Response",
+                        &(0u32, HashMap::<&str, Value<'_>>::new()),
+                    )
+                    .await
+            });
+            emitted.unwrap();
+            response.unwrap();
+
+            let _ = futures_util::poll!(request.as_mut());
+        });
+    }
 }
@@ -0,0 +1,255 @@
+use std::{
+    fs,
+    future::Future,
+    io::{self, Write},
+    os::unix::fs::{DirBuilderExt, OpenOptionsExt},
+    path::{Path, PathBuf},
+};
+
+use crate::{helpers::unblock, Error};
+
+/// A storage for the restore tokens of screen cast and remote desktop
+/// sessions.
+///
+/// The portal hands out a new restore token every time a session is started
+/// with a [`PersistMode`](super::PersistMode) other than
+/// [`DoNot`](super::PersistMode::DoNot), and a token can only be used once.
+/// The store keeps the latest token of each profile, a name chosen by the
+/// application for every kind of session it wants to restore, e.g. `"main"`
+/// or `"recording"`.
+///
+/// See [`Screencast::select_sources_with_store`](super::screencast::Screencast::select_sources_with_store)
+/// and [`RemoteDesktop::select_devices_with_store`](super::remote_desktop::RemoteDesktop::select_devices_with_store)
+/// for the methods making use of it.
+///
+/// Implementations doing blocking I/O should run it off the executor, like
+/// [`FileRestoreTokenStore`] does.
+pub trait RestoreTokenStore: Send + Sync {
+    /// The token stored for `profile`, if any.
+    fn load(&self, profile: &str) -> impl Future<Output = Result<Option<String>, Error>> + Send;
+
+    /// Replace the token stored for `profile`, or remove it if `token` is
+    /// `None`.
+    fn store(
+        &self,
+        profile: &str,
+        token: Option<&str>,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+}
+
+impl<S: RestoreTokenStore + ?Sized> RestoreTokenStore for &S {
+    fn load(&self, profile: &str) -> impl Future<Output = Result<Option<String>, Error>> + Send {
+        (**self).load(profile)
+    }
+
+    fn store(
+        &self,
+        profile: &str,
+        token: Option<&str>,
+    ) -> impl Future<Output = Result<(), Error>> + Send {
+        (**self).store(profile, token)
+    }
+}
+
+/// A [`RestoreTokenStore`] keeping a file per profile in a directory.
+///
+/// The files are read and written on a separate thread.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use ashpd::{
+///     desktop::{
+///         screencast::{CursorMode, Screencast, SourceType},
+///         FileRestoreTokenStore, PersistMode,
+///     },
+///     WindowIdentifier,
+/// };
+///
+/// async fn run() -> ashpd::Result<()> {
+///     let store = FileRestoreTokenStore::new()?;
+///     let proxy = Screencast::new().await?;
+///     let session = proxy.create_session().await?;
+///     proxy
+///         .select_sources_with_store(
+///             &session,
+///             CursorMode::Embedded,
+///             SourceType::Monitor.into(),
+///             false,
+///             PersistMode::ExplicitlyRevoked,
+///             &store,
+///             "main",
+///         )
+///         .await?
+///         .response()
+///         .await?;
+///     let streams = proxy
+///         .start_with_store(&session, &WindowIdentifier::default(), &store, "main")
+///         .await?;
+///     println!("{:#?}", streams.streams());
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileRestoreTokenStore {
+    directory: PathBuf,
+}
+
+impl FileRestoreTokenStore {
+    /// A store in the `ashpd/restore-tokens` directory of the XDG state
+    /// directory, `$XDG_STATE_HOME` or `~/.local/state`.
+    ///
+    /// Sandboxed applications get a state directory of their own. Others share
+    /// it and should prefix their profile names with their application ID, or
+    /// use [`FileRestoreTokenStore::with_directory`].
+    pub fn new() -> Result<Self, Error> {
+        let state_dir = std::env::var_os("XDG_STATE_HOME")
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+            .or_else(|| {
+                std::env::var_os("HOME")
+                    .map(PathBuf::from)
+                    .filter(|path| path.is_absolute())
+                    .map(|home| home.join(".local").join("state"))
+            })
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "Neither XDG_STATE_HOME nor HOME is set",
+                )
+            })?;
+        Ok(Self::with_directory(
+            state_dir.join("ashpd").join("restore-tokens"),
+        ))
+    }
+
+    /// A store in `directory`, created on the first stored token.
+    pub fn with_directory(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+
+    /// The directory the tokens are stored in.
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    fn path(&self, profile: &str) -> Result<PathBuf, Error> {
+        if profile.is_empty() || profile.starts_with('.') || profile.contains(['/', '\0']) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Invalid restore token profile name: {profile:?}"),
+            )
+            .into());
+        }
+        Ok(self.directory.join(profile))
+    }
+}
+
+impl RestoreTokenStore for FileRestoreTokenStore {
+    async fn load(&self, profile: &str) -> Result<Option<String>, Error> {
+        let path = self.path(profile)?;
+        unblock(move || match fs::read_to_string(path) {
+            Ok(token) => Ok(Some(token.trim_end().to_owned()).filter(|t| !t.is_empty())),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        })
+        .await
+    }
+
+    async fn store(&self, profile: &str, token: Option<&str>) -> Result<(), Error> {
+        let path = self.path(profile)?;
+        let directory = self.directory.clone();
+        // Unique, so that concurrent stores don't write to the same file.
+        let tmp_path = directory.join(format!(
+            ".{profile}.{}.{:08x}.tmp",
+            std::process::id(),
+            rand::Rng::gen::<u32>(&mut rand::thread_rng())
+        ));
+        let token = token.map(ToOwned::to_owned);
+        unblock(move || write_token(&directory, &path, &tmp_path, token.as_deref())).await
+    }
+}
+
+fn write_token(
+    directory: &Path,
+    path: &Path,
+    tmp_path: &Path,
+    token: Option<&str>,
+) -> Result<(), Error> {
+    let Some(token) = token else {
+        return match fs::remove_file(path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        };
+    };
+
+    fs::DirBuilder::new()
+        .recursive(true)
+        .mode(0o700)
+        .create(directory)?;
+    // Write to a temporary file first so a crash never leaves a truncated
+    // token behind.
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(tmp_path)?;
+    let written = file
+        .write_all(token.as_bytes())
+        .and_then(|_| file.sync_all())
+        .and_then(|_| fs::rename(tmp_path, path));
+    if let Err(err) = written {
+        let _ = fs::remove_file(tmp_path);
+        return Err(err.into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_store_round_trip() {
+        let directory = std::env::temp_dir().join(format!(
+            "ashpd-restore-tokens-{}-{}",
+            std::process::id(),
+            rand::Rng::gen::<u32>(&mut rand::thread_rng())
+        ));
+        let store = FileRestoreTokenStore::with_directory(&directory);
+
+        zbus::block_on(async {
+            assert_eq!(store.load("main").await.unwrap(), None);
+            store.store("main", Some("token-1")).await.unwrap();
+            store.store("main", Some("token-2")).await.unwrap();
+            assert_eq!(
+                store.load("main").await.unwrap().as_deref(),
+                Some("token-2")
+            );
+            assert_eq!(store.load("other").await.unwrap(), None);
+
+            // Concurrent stores don't share a temporary file.
+            let other_store = FileRestoreTokenStore::with_directory(&directory);
+            let (stored, other_stored) = futures_util::join!(
+                store.store("main", Some("token-3")),
+                other_store.store("main", Some("token-4")),
+            );
+            stored.unwrap();
+            other_stored.unwrap();
+            let token = store.load("main").await.unwrap().unwrap();
+            assert!(token == "token-3" || token == "token-4");
+            assert_eq!(fs::read_dir(&directory).unwrap().count(), 1);
+
+            store.store("main", None).await.unwrap();
+            store.store("main", None).await.unwrap();
+            assert_eq!(store.load("main").await.unwrap(), None);
+
+            assert!(store.load("../main").await.is_err());
+            assert!(store.store("", Some("token")).await.is_err());
+        });
+
+        fs::remove_dir_all(directory).unwrap();
+    }
+}
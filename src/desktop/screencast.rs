@@ -26,12 +26,15 @@
 //!             None,
 //!             PersistMode::DoNot,
 //!         )
+//!         .await?
+//!         .response()
 //!         .await?;
 //!
 //!     let response = proxy
 //!         .start(&session, &WindowIdentifier::default())
 //!         .await?
-//!         .response()?;
+//!         .response()
+//!         .await?;
 //!     response.streams().iter().for_each(|stream| {
 //!         println!("node id: {}", stream.pipe_wire_node_id());
 //!         println!("size: {:?}", stream.size());
@@ -49,7 +52,21 @@ use serde::Deserialize;
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use zbus::zvariant::{self, DeserializeDict, SerializeDict, Type, Value};
 
-use super::{HandleToken, PersistMode, Request, Session};
+#[cfg(feature = "pipewire")]
+mod frames;
+mod layout;
+#[cfg(feature = "pipewire")]
+mod still;
+#[cfg(feature = "pipewire")]
+#[cfg_attr(docsrs, doc(cfg(feature = "pipewire")))]
+pub use self::frames::{CursorBitmap, CursorUpdate, Frame, FrameStream, PixelFormat};
+pub use self::layout::ScreencastLayout;
+#[cfg(feature = "pipewire")]
+#[cfg_attr(docsrs, doc(cfg(feature = "pipewire")))]
+pub use self::still::{capture_still, CaptureStillRequest, Still};
+use super::{
+    HandleToken, HasScreencast, PersistMode, Request, RestoreTokenStore, Session, SessionPortal,
+};
 use crate::{desktop::session::CreateSessionResponse, proxy::Proxy, Error, WindowIdentifier};
 
 #[bitflags]
@@ -157,6 +174,7 @@ struct StartCastOptions {
 }
 
 #[derive(DeserializeDict, Type)]
+#[cfg_attr(any(feature = "backend", feature = "testing"), derive(SerializeDict))]
 /// A response to a [`Screencast::start`] request.
 #[zvariant(signature = "dict")]
 pub struct Streams {
@@ -165,6 +183,17 @@ pub struct Streams {
 }
 
 impl Streams {
+    #[cfg(any(feature = "backend", feature = "testing"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "backend", feature = "testing"))))]
+    /// Create a new response from a list of streams and an optional restore
+    /// token.
+    pub fn new(streams: Vec<Stream>, restore_token: Option<&str>) -> Self {
+        Self {
+            streams,
+            restore_token: restore_token.map(ToOwned::to_owned),
+        }
+    }
+
     /// The session restore token.
     pub fn restore_token(&self) -> Option<&str> {
         self.restore_token.as_deref()
@@ -186,10 +215,21 @@ impl Debug for Streams {
 }
 
 #[derive(Clone, Deserialize, Type)]
+#[cfg_attr(
+    any(feature = "backend", feature = "testing"),
+    derive(serde::Serialize)
+)]
 /// A PipeWire stream.
 pub struct Stream(u32, StreamProperties);
 
 impl Stream {
+    #[cfg(any(feature = "backend", feature = "testing"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "backend", feature = "testing"))))]
+    /// Create a [`StreamBuilder`] for the given PipeWire node.
+    pub fn builder(pipe_wire_node_id: u32) -> StreamBuilder {
+        StreamBuilder(Self(pipe_wire_node_id, StreamProperties::default()))
+    }
+
     /// The PipeWire stream Node ID
     pub fn pipe_wire_node_id(&self) -> u32 {
         self.0
@@ -242,7 +282,8 @@ impl Debug for Stream {
             .finish()
     }
 }
-#[derive(Clone, DeserializeDict, Type, Debug)]
+#[derive(Clone, DeserializeDict, Type, Debug, Default)]
+#[cfg_attr(any(feature = "backend", feature = "testing"), derive(SerializeDict))]
 /// The stream properties.
 #[zvariant(signature = "dict")]
 struct StreamProperties {
@@ -253,6 +294,57 @@ struct StreamProperties {
     mapping_id: Option<String>,
 }
 
+#[cfg(any(feature = "backend", feature = "testing"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "backend", feature = "testing"))))]
+#[derive(Debug)]
+/// A [builder-pattern] type to construct a [`Stream`].
+///
+/// [builder-pattern]: https://doc.rust-lang.org/1.0.0/style/ownership/builders.html
+pub struct StreamBuilder(Stream);
+
+#[cfg(any(feature = "backend", feature = "testing"))]
+impl StreamBuilder {
+    /// Sets the position of the stream in the compositor coordinate space.
+    #[must_use]
+    pub fn position(mut self, position: impl Into<Option<(i32, i32)>>) -> Self {
+        self.0 .1.position = position.into();
+        self
+    }
+
+    /// Sets the size of the stream in the compositor coordinate space.
+    #[must_use]
+    pub fn size(mut self, size: impl Into<Option<(i32, i32)>>) -> Self {
+        self.0 .1.size = size.into();
+        self
+    }
+
+    /// Sets the source type of the stream.
+    #[must_use]
+    pub fn source_type(mut self, source_type: impl Into<Option<SourceType>>) -> Self {
+        self.0 .1.source_type = source_type.into();
+        self
+    }
+
+    /// Sets the stream identifier.
+    #[must_use]
+    pub fn id<'a>(mut self, id: impl Into<Option<&'a str>>) -> Self {
+        self.0 .1.id = id.into().map(ToOwned::to_owned);
+        self
+    }
+
+    /// Sets the stream mapping id.
+    #[must_use]
+    pub fn mapping_id<'a>(mut self, mapping_id: impl Into<Option<&'a str>>) -> Self {
+        self.0 .1.mapping_id = mapping_id.into().map(ToOwned::to_owned);
+        self
+    }
+
+    /// Build the [`Stream`].
+    pub fn build(self) -> Stream {
+        self.0
+    }
+}
+
 /// The interface lets sandboxed applications create screen cast sessions.
 ///
 /// Wrapper of the DBus interface: [`org.freedesktop.portal.ScreenCast`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.portal.ScreenCast.html).
@@ -267,6 +359,14 @@ impl<'a> Screencast<'a> {
         Ok(Self(proxy))
     }
 
+    /// Create a new instance of [`Screencast`] on the given connection.
+    pub async fn with_connection(connection: &zbus::Connection) -> Result<Screencast<'a>, Error> {
+        let proxy =
+            Proxy::new_desktop_with_connection(connection, "org.freedesktop.portal.ScreenCast")
+                .await?;
+        Ok(Self(proxy))
+    }
+
     /// Create a screen cast session.
     ///
     /// # Specifications
@@ -274,15 +374,19 @@ impl<'a> Screencast<'a> {
     /// See also [`CreateSession`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.portal.ScreenCast.html#org-freedesktop-portal-screencast-createsession).
     #[doc(alias = "CreateSession")]
     #[doc(alias = "xdp_portal_create_screencast_session")]
-    pub async fn create_session(&self) -> Result<Session<'a>, Error> {
+    pub async fn create_session(&self) -> Result<Session<'a, Self>, Error> {
         let options = CreateSessionOptions::default();
         let (request, proxy) = futures_util::try_join!(
             self.0
                 .request::<CreateSessionResponse>(&options.handle_token, "CreateSession", &options)
                 .into_future(),
-            Session::from_unique_name(&options.session_handle_token).into_future(),
+            Session::from_unique_name(self.0.connection(), &options.session_handle_token)
+                .into_future(),
         )?;
-        assert_eq!(proxy.path(), &request.response()?.session_handle.as_ref());
+        assert_eq!(
+            proxy.path(),
+            &request.response().await?.session_handle.as_ref()
+        );
         Ok(proxy)
     }
 
@@ -292,7 +396,8 @@ impl<'a> Screencast<'a> {
     /// # Arguments
     ///
     /// * `session` - A [`Session`], created with
-    ///   [`create_session()`][`Screencast::create_session`].
+    ///   [`create_session()`][`Screencast::create_session`] or
+    ///   [`RemoteDesktop::create_session()`][`crate::desktop::remote_desktop::RemoteDesktop::create_session`].
     ///
     /// # Returns
     ///
@@ -302,7 +407,10 @@ impl<'a> Screencast<'a> {
     ///
     /// See also [`OpenPipeWireRemote`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.portal.ScreenCast.html#org-freedesktop-portal-screencast-openpipewireremote).
     #[doc(alias = "OpenPipeWireRemote")]
-    pub async fn open_pipe_wire_remote(&self, session: &Session<'_>) -> Result<OwnedFd, Error> {
+    pub async fn open_pipe_wire_remote(
+        &self,
+        session: &Session<'_, impl HasScreencast>,
+    ) -> Result<OwnedFd, Error> {
         // `options` parameter doesn't seems to be used yet
         // see https://github.com/flatpak/xdg-desktop-portal/blob/master/src/screen-cast.c#L812
         let options: HashMap<&str, Value<'_>> = HashMap::new();
@@ -323,7 +431,8 @@ impl<'a> Screencast<'a> {
     /// # Arguments
     ///
     /// * `session` - A [`Session`], created with
-    ///   [`create_session()`][`Screencast::create_session`].
+    ///   [`create_session()`][`Screencast::create_session`] or
+    ///   [`RemoteDesktop::create_session()`][`crate::desktop::remote_desktop::RemoteDesktop::create_session`].
     /// * `cursor_mode` - Sets how the cursor will be drawn on the screen cast
     ///   stream.
     /// * `types` - Sets the types of content to record.
@@ -335,7 +444,7 @@ impl<'a> Screencast<'a> {
     #[doc(alias = "SelectSources")]
     pub async fn select_sources(
         &self,
-        session: &Session<'_>,
+        session: &Session<'_, impl HasScreencast>,
         cursor_mode: CursorMode,
         types: BitFlags<SourceType>,
         multiple: bool,
@@ -353,6 +462,34 @@ impl<'a> Screencast<'a> {
             .await
     }
 
+    /// Same as [`Screencast::select_sources`], restoring the session from the
+    /// token stored for `profile` in `store`, if any.
+    ///
+    /// Use [`Screencast::start_with_store`] to start the session and store
+    /// the new token.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn select_sources_with_store(
+        &self,
+        session: &Session<'_, impl HasScreencast>,
+        cursor_mode: CursorMode,
+        types: BitFlags<SourceType>,
+        multiple: bool,
+        persist_mode: PersistMode,
+        store: &impl RestoreTokenStore,
+        profile: &str,
+    ) -> Result<Request<()>, Error> {
+        let restore_token = store.load(profile).await?;
+        self.select_sources(
+            session,
+            cursor_mode,
+            types,
+            multiple,
+            restore_token.as_deref(),
+            persist_mode,
+        )
+        .await
+    }
+
     /// Start the screen cast session.
     ///
     /// This will typically result the portal presenting a dialog letting the
@@ -376,7 +513,7 @@ impl<'a> Screencast<'a> {
     #[doc(alias = "Start")]
     pub async fn start(
         &self,
-        session: &Session<'_>,
+        session: &Session<'_, Self>,
         identifier: &WindowIdentifier,
     ) -> Result<Request<Streams>, Error> {
         let options = StartCastOptions::default();
@@ -389,6 +526,24 @@ impl<'a> Screencast<'a> {
             .await
     }
 
+    /// Same as [`Screencast::start`], waiting for the response and replacing
+    /// the token stored for `profile` in `store` with the one the portal
+    /// returned.
+    ///
+    /// Restore tokens can only be used once, the stored token is removed if
+    /// the portal didn't return a new one.
+    pub async fn start_with_store(
+        &self,
+        session: &Session<'_, Self>,
+        identifier: &WindowIdentifier,
+        store: &impl RestoreTokenStore,
+        profile: &str,
+    ) -> Result<Streams, Error> {
+        let streams = self.start(session, identifier).await?.response().await?;
+        store.store(profile, streams.restore_token()).await?;
+        Ok(streams)
+    }
+
     /// Available cursor mode.
     ///
     /// # Specifications
@@ -410,6 +565,10 @@ impl<'a> Screencast<'a> {
     }
 }
 
+impl super::session::sealed::Sealed for Screencast<'_> {}
+impl SessionPortal for Screencast<'_> {}
+impl HasScreencast for Screencast<'_> {}
+
 impl<'a> std::ops::Deref for Screencast<'a> {
     type Target = zbus::Proxy<'a>;
 
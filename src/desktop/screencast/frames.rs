@@ -0,0 +1,824 @@
+use std::{
+    fmt::Debug,
+    io::Cursor,
+    os::fd::OwnedFd,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures_channel::{mpsc, oneshot};
+use pipewire::{
+    context::Context as PwContext,
+    main_loop::MainLoop,
+    properties::properties,
+    spa::{
+        self,
+        buffer::{ChunkFlags, Data},
+        param::{
+            format::{FormatProperties, MediaSubtype, MediaType},
+            format_utils,
+            video::{VideoFormat, VideoInfoRaw},
+            ParamType,
+        },
+        pod::{serialize::PodSerializer, ChoiceValue, Object, Pod, Property, Value},
+        utils::{Choice, ChoiceEnum, ChoiceFlags, Direction, Fraction, Id, Rectangle, SpaTypes},
+    },
+    stream::{StreamFlags, StreamRef, StreamState},
+};
+
+/// The number of frames kept while the consumer of a [`FrameStream`] is busy.
+/// Newer frames are dropped until it catches up.
+const FRAMES_QUEUE_SIZE: usize = 2;
+
+/// The pixel layout of a [`Frame`].
+///
+/// The names follow the order of the bytes in memory, e.g. the first byte of
+/// a [`PixelFormat::Bgrx`] pixel is the blue component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum PixelFormat {
+    /// Blue, green, red and an unused byte.
+    Bgrx,
+    /// Red, green, blue and an unused byte.
+    Rgbx,
+    /// An unused byte, red, green and blue.
+    Xrgb,
+    /// An unused byte, blue, green and red.
+    Xbgr,
+    /// Blue, green, red and alpha.
+    Bgra,
+    /// Red, green, blue and alpha.
+    Rgba,
+    /// Alpha, red, green and blue.
+    Argb,
+    /// Alpha, blue, green and red.
+    Abgr,
+    /// Red, green and blue.
+    Rgb,
+    /// Blue, green and red.
+    Bgr,
+}
+
+impl PixelFormat {
+    const ALL: [Self; 10] = [
+        Self::Bgrx,
+        Self::Rgbx,
+        Self::Xrgb,
+        Self::Xbgr,
+        Self::Bgra,
+        Self::Rgba,
+        Self::Argb,
+        Self::Abgr,
+        Self::Rgb,
+        Self::Bgr,
+    ];
+
+    /// The number of bytes of a pixel.
+    pub fn bytes_per_pixel(self) -> usize {
+        match self {
+            Self::Rgb | Self::Bgr => 3,
+            _ => 4,
+        }
+    }
+
+    /// Whether the format has an alpha channel.
+    pub fn has_alpha(self) -> bool {
+        matches!(self, Self::Bgra | Self::Rgba | Self::Argb | Self::Abgr)
+    }
+
+    fn to_spa(self) -> VideoFormat {
+        match self {
+            Self::Bgrx => VideoFormat::BGRx,
+            Self::Rgbx => VideoFormat::RGBx,
+            Self::Xrgb => VideoFormat::xRGB,
+            Self::Xbgr => VideoFormat::xBGR,
+            Self::Bgra => VideoFormat::BGRA,
+            Self::Rgba => VideoFormat::RGBA,
+            Self::Argb => VideoFormat::ARGB,
+            Self::Abgr => VideoFormat::ABGR,
+            Self::Rgb => VideoFormat::RGB,
+            Self::Bgr => VideoFormat::BGR,
+        }
+    }
+
+    fn from_spa(format: VideoFormat) -> Option<Self> {
+        Self::ALL.into_iter().find(|f| f.to_spa() == format)
+    }
+}
+
+/// The cursor of a screen cast stream, sent along the frames when the
+/// session was set up with
+/// [`CursorMode::Metadata`](super::CursorMode::Metadata).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CursorUpdate {
+    position: (i32, i32),
+    hotspot: (i32, i32),
+    bitmap: Option<CursorBitmap>,
+}
+
+impl CursorUpdate {
+    /// The position of the cursor in the stream, in pixels.
+    pub fn position(&self) -> (i32, i32) {
+        self.position
+    }
+
+    /// The position of the pointing spot in the [`bitmap`](Self::bitmap).
+    ///
+    /// The bitmap is to be drawn at [`position`](Self::position) minus the
+    /// hotspot.
+    pub fn hotspot(&self) -> (i32, i32) {
+        self.hotspot
+    }
+
+    /// The image of the cursor.
+    ///
+    /// It is only sent when the cursor image changes, the previous one is to
+    /// be used otherwise.
+    pub fn bitmap(&self) -> Option<&CursorBitmap> {
+        self.bitmap.as_ref()
+    }
+}
+
+/// The image of a [`CursorUpdate`].
+#[derive(Clone, PartialEq, Eq)]
+pub struct CursorBitmap {
+    data: Vec<u8>,
+    width: u32,
+    height: u32,
+    stride: usize,
+    format: PixelFormat,
+}
+
+impl CursorBitmap {
+    /// The pixels, `height` rows of [`stride`](CursorBitmap::stride) bytes.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// The width and height of the bitmap, in pixels.
+    pub fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// The number of bytes between the start of two rows.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// The layout of the pixels.
+    pub fn format(&self) -> PixelFormat {
+        self.format
+    }
+}
+
+impl Debug for CursorBitmap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CursorBitmap")
+            .field("size", &self.size())
+            .field("stride", &self.stride)
+            .field("format", &self.format)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A video frame received by a [`FrameStream`].
+#[derive(Clone, PartialEq, Eq)]
+pub struct Frame {
+    data: Arc<Vec<u8>>,
+    width: u32,
+    height: u32,
+    stride: usize,
+    format: PixelFormat,
+    timestamp: Option<Duration>,
+    cursor: Option<CursorUpdate>,
+}
+
+impl Frame {
+    /// The pixels, `height` rows of [`stride`](Frame::stride) bytes.
+    ///
+    /// Buffers only updating the cursor repeat the pixels of the previous
+    /// frame, without copying them.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Take the pixels out of the frame.
+    pub fn into_data(self) -> Vec<u8> {
+        Arc::try_unwrap(self.data).unwrap_or_else(|data| data.as_ref().clone())
+    }
+
+    /// The width and height of the frame, in pixels.
+    pub fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// The number of bytes between the start of two rows.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// The layout of the pixels.
+    pub fn format(&self) -> PixelFormat {
+        self.format
+    }
+
+    /// The presentation timestamp set by the compositor, on its monotonic
+    /// clock.
+    pub fn timestamp(&self) -> Option<Duration> {
+        self.timestamp
+    }
+
+    /// The cursor, if the session was set up with
+    /// [`CursorMode::Metadata`](super::CursorMode::Metadata) and the cursor is
+    /// visible.
+    pub fn cursor(&self) -> Option<&CursorUpdate> {
+        self.cursor.as_ref()
+    }
+}
+
+impl Debug for Frame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Frame")
+            .field("size", &self.size())
+            .field("stride", &self.stride)
+            .field("format", &self.format)
+            .field("timestamp", &self.timestamp)
+            .field("cursor", &self.cursor)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A stream of the frames of a screen cast [`Stream`](super::Stream).
+///
+/// The frames are negotiated in shared memory with one of the
+/// [`PixelFormat`]s, so no GPU is involved, and copied out of the PipeWire
+/// buffers. Frames are dropped if the stream isn't polled fast enough.
+///
+/// With [`CursorMode::Metadata`](super::CursorMode::Metadata), the cursor is
+/// left out of the pixels and available as [`Frame::cursor`] instead, to be
+/// drawn by the application.
+///
+/// The stream ends once the PipeWire stream is disconnected, e.g. when the
+/// screen cast session is closed.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use ashpd::{
+///     desktop::{
+///         screencast::{CursorMode, FrameStream, Screencast, SourceType},
+///         PersistMode,
+///     },
+///     WindowIdentifier,
+/// };
+/// use futures_util::StreamExt;
+///
+/// async fn run() -> ashpd::Result<()> {
+///     let proxy = Screencast::new().await?;
+///     let session = proxy.create_session().await?;
+///     proxy
+///         .select_sources(
+///             &session,
+///             CursorMode::Embedded,
+///             SourceType::Monitor.into(),
+///             false,
+///             None,
+///             PersistMode::DoNot,
+///         )
+///         .await?
+///         .response()
+///         .await?;
+///     let response = proxy
+///         .start(&session, &WindowIdentifier::default())
+///         .await?
+///         .response()
+///         .await?;
+///     let fd = proxy.open_pipe_wire_remote(&session).await?;
+///
+///     let node_id = response.streams()[0].pipe_wire_node_id();
+///     let mut frames = FrameStream::new(fd, node_id).await?;
+///     while let Some(frame) = frames.next().await {
+///         println!("{:?} {:?}", frame.size(), frame.format());
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct FrameStream {
+    frames: mpsc::Receiver<Frame>,
+    quit: pipewire::channel::Sender<()>,
+}
+
+impl FrameStream {
+    /// Connect to the node `node_id` of the PipeWire remote `fd`, as returned
+    /// by [`Stream::pipe_wire_node_id`](super::Stream::pipe_wire_node_id) and
+    /// [`Screencast::open_pipe_wire_remote`](super::Screencast::open_pipe_wire_remote).
+    pub async fn new(fd: OwnedFd, node_id: u32) -> Result<Self, pipewire::Error> {
+        let (ready_sender, ready_receiver) = oneshot::channel();
+        let (frames_sender, frames) = mpsc::channel(FRAMES_QUEUE_SIZE);
+        let (quit, quit_receiver) = pipewire::channel::channel();
+
+        std::thread::spawn(move || {
+            let mut ready_sender = Some(ready_sender);
+            if let Err(err) =
+                frame_stream_inner(fd, node_id, frames_sender, quit_receiver, &mut ready_sender)
+            {
+                #[cfg(feature = "tracing")]
+                tracing::error!("Failed to stream PipeWire frames {:#?}", err);
+                if let Some(sender) = ready_sender.take() {
+                    let _ = sender.send(Err(err));
+                }
+            }
+        });
+
+        ready_receiver
+            .await
+            .unwrap_or(Err(pipewire::Error::CreationFailed))?;
+        Ok(Self { frames, quit })
+    }
+}
+
+impl futures_util::Stream for FrameStream {
+    type Item = Frame;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.frames).poll_next(cx)
+    }
+}
+
+impl Drop for FrameStream {
+    fn drop(&mut self) {
+        let _ = self.quit.send(());
+    }
+}
+
+impl Debug for FrameStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FrameStream").finish_non_exhaustive()
+    }
+}
+
+struct StreamData {
+    format: Option<(PixelFormat, Rectangle)>,
+    /// The pixels and stride of the previous frame.
+    last_pixels: Option<(Arc<Vec<u8>>, usize)>,
+    frames: mpsc::Sender<Frame>,
+}
+
+fn frame_stream_inner(
+    fd: OwnedFd,
+    node_id: u32,
+    frames: mpsc::Sender<Frame>,
+    quit: pipewire::channel::Receiver<()>,
+    ready: &mut Option<oneshot::Sender<Result<(), pipewire::Error>>>,
+) -> Result<(), pipewire::Error> {
+    let mainloop = MainLoop::new(None)?;
+    let context = PwContext::new(&mainloop)?;
+    let core = context.connect_fd(fd, None)?;
+
+    let loop_clone = mainloop.clone();
+    let _quit = quit.attach(mainloop.loop_(), move |_| loop_clone.quit());
+
+    let stream = pipewire::stream::Stream::new(
+        &core,
+        "ashpd-screencast",
+        properties! {
+            *pipewire::keys::MEDIA_TYPE => "Video",
+            *pipewire::keys::MEDIA_CATEGORY => "Capture",
+            *pipewire::keys::MEDIA_ROLE => "Screen",
+        },
+    )?;
+
+    let loop_clone = mainloop.clone();
+    let _listener = stream
+        .add_local_listener_with_user_data(StreamData {
+            format: None,
+            last_pixels: None,
+            frames,
+        })
+        .state_changed(move |_, _, _old, new| match new {
+            StreamState::Error(_) | StreamState::Unconnected => {
+                #[cfg(feature = "tracing")]
+                tracing::info!("PipeWire stream {:?}, stopping", new);
+                loop_clone.quit();
+            }
+            _ => (),
+        })
+        .param_changed(on_param_changed)
+        .process(on_process)
+        .register()?;
+
+    let format = serialize(enum_format_object());
+    let mut params = [Pod::from_bytes(&format).expect("invalid format pod")];
+    stream.connect(
+        Direction::Input,
+        Some(node_id),
+        StreamFlags::AUTOCONNECT | StreamFlags::MAP_BUFFERS,
+        &mut params,
+    )?;
+
+    if let Some(ready) = ready.take() {
+        let _ = ready.send(Ok(()));
+    }
+    mainloop.run();
+
+    Ok(())
+}
+
+fn on_param_changed(stream: &StreamRef, data: &mut StreamData, id: u32, param: Option<&Pod>) {
+    let Some(param) = param else {
+        return;
+    };
+    if id != ParamType::Format.as_raw() {
+        return;
+    }
+    match format_utils::parse_format(param) {
+        Ok((media_type, media_subtype))
+            if media_type == MediaType::Video && media_subtype == MediaSubtype::Raw => {}
+        _ => return,
+    }
+    let mut info = VideoInfoRaw::new();
+    if info.parse(param).is_err() {
+        return;
+    }
+    data.format = PixelFormat::from_spa(info.format()).map(|format| (format, info.size()));
+    data.last_pixels = None;
+    #[cfg(feature = "tracing")]
+    tracing::debug!("Negotiated PipeWire format {:?}", data.format);
+
+    // Only accept buffers in memory and ask for the header metadata, holding
+    // the timestamps, and the cursor one.
+    let buffers = serialize(buffers_object());
+    let header = serialize(meta_header_object());
+    let cursor = serialize(meta_cursor_object());
+    let mut params = [
+        Pod::from_bytes(&buffers).expect("invalid buffers pod"),
+        Pod::from_bytes(&header).expect("invalid meta pod"),
+        Pod::from_bytes(&cursor).expect("invalid meta pod"),
+    ];
+    if let Err(_err) = stream.update_params(&mut params) {
+        #[cfg(feature = "tracing")]
+        tracing::warn!("Failed to update the PipeWire stream params {}", _err);
+    }
+}
+
+fn on_process(stream: &StreamRef, data: &mut StreamData) {
+    let Some((format, size)) = data.format else {
+        return;
+    };
+    // SAFETY: the buffer is checked for null and given back to the stream
+    // right after being copied.
+    let frame = unsafe {
+        let buffer = stream.dequeue_raw_buffer();
+        if buffer.is_null() {
+            return;
+        }
+        let frame = read_frame((*buffer).buffer, format, size, &mut data.last_pixels);
+        stream.queue_raw_buffer(buffer);
+        frame
+    };
+    if let Some(frame) = frame {
+        // The frame is dropped if the consumer is lagging behind.
+        let _ = data.frames.try_send(frame);
+    }
+}
+
+/// # Safety
+///
+/// `buffer` must be null or point to a valid buffer dequeued from the stream.
+unsafe fn read_frame(
+    buffer: *mut spa::sys::spa_buffer,
+    format: PixelFormat,
+    size: Rectangle,
+    last_pixels: &mut Option<(Arc<Vec<u8>>, usize)>,
+) -> Option<Frame> {
+    if buffer.is_null() {
+        return None;
+    }
+    let cursor = meta_data(buffer, spa::sys::SPA_META_Cursor).and_then(parse_cursor);
+    let (data, stride) = match read_pixels(buffer, format, size) {
+        Some((pixels, stride)) => {
+            let pixels = Arc::new(pixels);
+            *last_pixels = Some((pixels.clone(), stride));
+            (pixels, stride)
+        }
+        // A buffer only carrying a cursor update.
+        None if cursor.is_some() => last_pixels.clone()?,
+        None => return None,
+    };
+    // SAFETY: `spa_meta_header` only holds integers.
+    let header = meta_data(buffer, spa::sys::SPA_META_Header)
+        .and_then(|meta| read_struct::<spa::sys::spa_meta_header>(meta));
+
+    Some(Frame {
+        data,
+        width: size.width,
+        height: size.height,
+        stride,
+        format,
+        timestamp: header
+            .and_then(|header| u64::try_from(header.pts).ok())
+            .map(Duration::from_nanos),
+        cursor,
+    })
+}
+
+/// # Safety
+///
+/// `buffer` must point to a valid buffer.
+unsafe fn read_pixels(
+    buffer: *mut spa::sys::spa_buffer,
+    format: PixelFormat,
+    size: Rectangle,
+) -> Option<(Vec<u8>, usize)> {
+    if (*buffer).n_datas == 0 || (*buffer).datas.is_null() {
+        return None;
+    }
+    // `Data` is a transparent wrapper of `spa_data`.
+    let data = &mut *((*buffer).datas as *mut Data);
+    let chunk = data.chunk();
+    if chunk.size() == 0 || chunk.flags().contains(ChunkFlags::CORRUPTED) {
+        return None;
+    }
+    let offset = chunk.offset() as usize;
+    let len = chunk.size() as usize;
+    let stride = match usize::try_from(chunk.stride()) {
+        Ok(stride) if stride > 0 => stride,
+        _ => size.width as usize * format.bytes_per_pixel(),
+    };
+    let bytes = data.data()?;
+    let pixels = bytes.get(offset..offset.checked_add(len)?.min(bytes.len()))?;
+    Some((pixels.to_vec(), stride))
+}
+
+/// The metadata of type `type_` of the buffer.
+///
+/// # Safety
+///
+/// `buffer` must point to a valid buffer.
+unsafe fn meta_data<'b>(buffer: *const spa::sys::spa_buffer, type_: u32) -> Option<&'b [u8]> {
+    if (*buffer).n_metas == 0 || (*buffer).metas.is_null() {
+        return None;
+    }
+    std::slice::from_raw_parts((*buffer).metas, (*buffer).n_metas as usize)
+        .iter()
+        .find(|meta| meta.type_ == type_ && !meta.data.is_null())
+        .map(|meta| std::slice::from_raw_parts(meta.data as *const u8, meta.size as usize))
+}
+
+/// Read a C struct from the start of `bytes`.
+///
+/// # Safety
+///
+/// Any bit pattern must be a valid `T`.
+unsafe fn read_struct<T: Copy>(bytes: &[u8]) -> Option<T> {
+    (bytes.len() >= std::mem::size_of::<T>())
+        .then(|| std::ptr::read_unaligned(bytes.as_ptr().cast()))
+}
+
+/// Parse a `spa_meta_cursor` and the `spa_meta_bitmap` following it.
+fn parse_cursor(meta: &[u8]) -> Option<CursorUpdate> {
+    // SAFETY: `spa_meta_cursor` only holds integers.
+    let cursor = unsafe { read_struct::<spa::sys::spa_meta_cursor>(meta) }?;
+    // The cursor is hidden.
+    if cursor.id == 0 {
+        return None;
+    }
+    let bitmap_offset = cursor.bitmap_offset as usize;
+    let bitmap = if bitmap_offset >= std::mem::size_of::<spa::sys::spa_meta_cursor>() {
+        meta.get(bitmap_offset..).and_then(parse_bitmap)
+    } else {
+        None
+    };
+    Some(CursorUpdate {
+        position: (cursor.position.x, cursor.position.y),
+        hotspot: (cursor.hotspot.x, cursor.hotspot.y),
+        bitmap,
+    })
+}
+
+fn parse_bitmap(meta: &[u8]) -> Option<CursorBitmap> {
+    // SAFETY: `spa_meta_bitmap` only holds integers.
+    let bitmap = unsafe { read_struct::<spa::sys::spa_meta_bitmap>(meta) }?;
+    let format = PixelFormat::from_spa(VideoFormat::from_raw(bitmap.format))?;
+    let (width, height) = (bitmap.size.width, bitmap.size.height);
+    if width == 0 || height == 0 {
+        return None;
+    }
+    let stride = match usize::try_from(bitmap.stride) {
+        Ok(stride) if stride > 0 => stride,
+        _ => width as usize * format.bytes_per_pixel(),
+    };
+    let offset = bitmap.offset as usize;
+    let len = stride.checked_mul(height as usize)?;
+    let data = meta.get(offset..offset.checked_add(len)?)?.to_vec();
+    Some(CursorBitmap {
+        data,
+        width,
+        height,
+        stride,
+        format,
+    })
+}
+
+fn serialize(object: Object) -> Vec<u8> {
+    PodSerializer::serialize(Cursor::new(Vec::new()), &Value::Object(object))
+        .expect("failed to serialize pod")
+        .0
+        .into_inner()
+}
+
+fn enum_format_object() -> Object {
+    let formats = PixelFormat::ALL
+        .into_iter()
+        .map(|format| Id(format.to_spa().as_raw()))
+        .collect();
+    Object {
+        type_: SpaTypes::ObjectParamFormat.as_raw(),
+        id: ParamType::EnumFormat.as_raw(),
+        properties: vec![
+            Property::new(
+                FormatProperties::MediaType.as_raw(),
+                Value::Id(Id(MediaType::Video.as_raw())),
+            ),
+            Property::new(
+                FormatProperties::MediaSubtype.as_raw(),
+                Value::Id(Id(MediaSubtype::Raw.as_raw())),
+            ),
+            Property::new(
+                FormatProperties::VideoFormat.as_raw(),
+                Value::Choice(ChoiceValue::Id(Choice(
+                    ChoiceFlags::empty(),
+                    ChoiceEnum::Enum {
+                        default: Id(PixelFormat::Bgrx.to_spa().as_raw()),
+                        alternatives: formats,
+                    },
+                ))),
+            ),
+            Property::new(
+                FormatProperties::VideoSize.as_raw(),
+                Value::Choice(ChoiceValue::Rectangle(Choice(
+                    ChoiceFlags::empty(),
+                    ChoiceEnum::Range {
+                        default: Rectangle {
+                            width: 1920,
+                            height: 1080,
+                        },
+                        min: Rectangle {
+                            width: 1,
+                            height: 1,
+                        },
+                        max: Rectangle {
+                            width: 16384,
+                            height: 16384,
+                        },
+                    },
+                ))),
+            ),
+            Property::new(
+                FormatProperties::VideoFramerate.as_raw(),
+                Value::Choice(ChoiceValue::Fraction(Choice(
+                    ChoiceFlags::empty(),
+                    ChoiceEnum::Range {
+                        default: Fraction { num: 30, denom: 1 },
+                        min: Fraction { num: 0, denom: 1 },
+                        max: Fraction {
+                            num: 1000,
+                            denom: 1,
+                        },
+                    },
+                ))),
+            ),
+        ],
+    }
+}
+
+fn buffers_object() -> Object {
+    let data_types =
+        (1 << spa::sys::SPA_DATA_MemFd as i32) | (1 << spa::sys::SPA_DATA_MemPtr as i32);
+    Object {
+        type_: SpaTypes::ObjectParamBuffers.as_raw(),
+        id: ParamType::Buffers.as_raw(),
+        properties: vec![Property::new(
+            spa::sys::SPA_PARAM_BUFFERS_dataType,
+            Value::Choice(ChoiceValue::Int(Choice(
+                ChoiceFlags::empty(),
+                ChoiceEnum::Flags {
+                    default: data_types,
+                    flags: vec![data_types],
+                },
+            ))),
+        )],
+    }
+}
+
+/// The size of a cursor metadata with a `width`×`height` bitmap.
+fn cursor_meta_size(width: usize, height: usize) -> i32 {
+    (std::mem::size_of::<spa::sys::spa_meta_cursor>()
+        + std::mem::size_of::<spa::sys::spa_meta_bitmap>()
+        + width * height * 4) as i32
+}
+
+fn meta_cursor_object() -> Object {
+    Object {
+        type_: SpaTypes::ObjectParamMeta.as_raw(),
+        id: ParamType::Meta.as_raw(),
+        properties: vec![
+            Property::new(
+                spa::sys::SPA_PARAM_META_type,
+                Value::Id(Id(spa::sys::SPA_META_Cursor)),
+            ),
+            Property::new(
+                spa::sys::SPA_PARAM_META_size,
+                Value::Choice(ChoiceValue::Int(Choice(
+                    ChoiceFlags::empty(),
+                    ChoiceEnum::Range {
+                        default: cursor_meta_size(64, 64),
+                        min: cursor_meta_size(1, 1),
+                        max: cursor_meta_size(1024, 1024),
+                    },
+                ))),
+            ),
+        ],
+    }
+}
+
+fn meta_header_object() -> Object {
+    Object {
+        type_: SpaTypes::ObjectParamMeta.as_raw(),
+        id: ParamType::Meta.as_raw(),
+        properties: vec![
+            Property::new(
+                spa::sys::SPA_PARAM_META_type,
+                Value::Id(Id(spa::sys::SPA_META_Header)),
+            ),
+            Property::new(
+                spa::sys::SPA_PARAM_META_size,
+                Value::Int(std::mem::size_of::<spa::sys::spa_meta_header>() as i32),
+            ),
+        ],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pixel_format_round_trip() {
+        for format in PixelFormat::ALL {
+            assert_eq!(PixelFormat::from_spa(format.to_spa()), Some(format));
+        }
+        assert_eq!(PixelFormat::from_spa(VideoFormat::NV12), None);
+    }
+
+    fn bytes_of<T>(value: &T) -> &[u8] {
+        // SAFETY: the spa structs are plain integers without padding.
+        unsafe {
+            std::slice::from_raw_parts(value as *const T as *const u8, std::mem::size_of::<T>())
+        }
+    }
+
+    #[test]
+    fn cursor_metadata() {
+        let pixels = [0xffu8; 2 * 2 * 4];
+        let mut cursor = spa::sys::spa_meta_cursor {
+            id: 1,
+            flags: 0,
+            position: spa::sys::spa_point { x: 10, y: 20 },
+            hotspot: spa::sys::spa_point { x: 2, y: 3 },
+            bitmap_offset: std::mem::size_of::<spa::sys::spa_meta_cursor>() as u32,
+        };
+        let bitmap = spa::sys::spa_meta_bitmap {
+            format: VideoFormat::BGRA.as_raw(),
+            size: Rectangle {
+                width: 2,
+                height: 2,
+            },
+            stride: 8,
+            offset: std::mem::size_of::<spa::sys::spa_meta_bitmap>() as u32,
+        };
+        let meta = [bytes_of(&cursor), bytes_of(&bitmap), &pixels[..]].concat();
+
+        let update = parse_cursor(&meta).unwrap();
+        assert_eq!(update.position(), (10, 20));
+        assert_eq!(update.hotspot(), (2, 3));
+        let bitmap = update.bitmap().unwrap();
+        assert_eq!(bitmap.size(), (2, 2));
+        assert_eq!(bitmap.format(), PixelFormat::Bgra);
+        assert_eq!(bitmap.data(), &pixels);
+
+        // A truncated bitmap is skipped, the position is still valid.
+        let update = parse_cursor(&meta[..meta.len() - 1]).unwrap();
+        assert_eq!(update.position(), (10, 20));
+        assert!(update.bitmap().is_none());
+
+        cursor.bitmap_offset = 0;
+        let update = parse_cursor(bytes_of(&cursor)).unwrap();
+        assert!(update.bitmap().is_none());
+
+        cursor.id = 0;
+        assert!(parse_cursor(bytes_of(&cursor)).is_none());
+    }
+}
@@ -0,0 +1,294 @@
+use super::{Stream, Streams};
+use crate::desktop::input_capture::Region;
+
+#[derive(Debug, Clone)]
+struct StreamGeometry {
+    node_id: u32,
+    mapping_id: Option<String>,
+    position: (i32, i32),
+    size: (i32, i32),
+    pixel_size: Option<(u32, u32)>,
+}
+
+impl StreamGeometry {
+    fn contains(&self, x: f64, y: f64) -> bool {
+        let (left, top) = (self.position.0 as f64, self.position.1 as f64);
+        x >= left && y >= top && x < left + self.size.0 as f64 && y < top + self.size.1 as f64
+    }
+
+    /// The number of buffer pixels per logical pixel, on each axis.
+    fn scale(&self) -> (f64, f64) {
+        match self.pixel_size {
+            Some((width, height)) => (
+                width as f64 / self.size.0 as f64,
+                height as f64 / self.size.1 as f64,
+            ),
+            None => (1.0, 1.0),
+        }
+    }
+}
+
+/// The layout of the monitors of a screen cast session.
+///
+/// The streams of a session, e.g. one per monitor, are placed in the logical
+/// coordinate space of the compositor, which is also the one of the
+/// [`Region`]s of the input capture portal. The logical size of a stream can
+/// differ from the size of its PipeWire buffers, e.g. with a scaled monitor,
+/// and the remote desktop portal expects coordinates relative to each stream.
+/// The layout converts points between these spaces:
+///
+/// * logical: the coordinate space of the compositor, spanning all the streams.
+/// * stream: the logical coordinate space of a stream, relative to its
+///   top-left corner, as expected by
+///   [`RemoteDesktop::notify_pointer_motion_absolute`](crate::desktop::remote_desktop::RemoteDesktop::notify_pointer_motion_absolute).
+/// * pixel: the pixels of the buffers of a stream, once its size is known with
+///   [`ScreencastLayout::set_pixel_size`].
+///
+/// Streams without a size are left out. Streams without a position, like
+/// window streams, are placed at the origin.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use ashpd::desktop::{
+///     remote_desktop::RemoteDesktop,
+///     screencast::{ScreencastLayout, Streams},
+///     Session,
+/// };
+///
+/// async fn click(
+///     proxy: &RemoteDesktop<'_>,
+///     session: &Session<'_, RemoteDesktop<'_>>,
+///     streams: &Streams,
+///     node_id: u32,
+///     pixel: (f64, f64),
+/// ) -> ashpd::Result<()> {
+///     let mut layout = ScreencastLayout::from(streams);
+///     // The size of the frames received for the stream.
+///     layout.set_pixel_size(node_id, (3840, 2160));
+///     if let Some((x, y)) = layout.pixel_to_stream(node_id, pixel.0, pixel.1) {
+///         proxy
+///             .notify_pointer_motion_absolute(session, node_id, x, y)
+///             .await?;
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ScreencastLayout {
+    streams: Vec<StreamGeometry>,
+}
+
+impl ScreencastLayout {
+    /// Create a layout of `streams`.
+    pub fn new(streams: &[Stream]) -> Self {
+        let streams = streams
+            .iter()
+            .filter_map(|stream| {
+                let size = stream.size().filter(|(w, h)| *w > 0 && *h > 0)?;
+                Some(StreamGeometry {
+                    node_id: stream.pipe_wire_node_id(),
+                    mapping_id: stream.mapping_id().map(ToOwned::to_owned),
+                    position: stream.position().unwrap_or_default(),
+                    size,
+                    pixel_size: None,
+                })
+            })
+            .collect();
+        Self { streams }
+    }
+
+    /// Set the size of the PipeWire buffers of a stream, e.g. from
+    /// `Frame::size` with the `pipewire` feature, to convert from and to its
+    /// pixels.
+    ///
+    /// Until then, a logical pixel is assumed to be a buffer pixel.
+    pub fn set_pixel_size(&mut self, node_id: u32, size: (u32, u32)) {
+        if let Some(stream) = self.stream_mut(node_id) {
+            stream.pixel_size = Some(size).filter(|(w, h)| *w > 0 && *h > 0);
+        }
+    }
+
+    /// The PipeWire node IDs of the streams of the layout.
+    pub fn node_ids(&self) -> impl Iterator<Item = u32> + '_ {
+        self.streams.iter().map(|stream| stream.node_id)
+    }
+
+    /// The top-left corner of the bounding box of the streams, in logical
+    /// coordinates.
+    pub fn position(&self) -> (i32, i32) {
+        let x = self.streams.iter().map(|s| s.position.0).min();
+        let y = self.streams.iter().map(|s| s.position.1).min();
+        (x.unwrap_or_default(), y.unwrap_or_default())
+    }
+
+    /// The size of the bounding box of the streams, in logical coordinates.
+    pub fn size(&self) -> (i32, i32) {
+        let (x, y) = self.position();
+        let right = self.streams.iter().map(|s| s.position.0 + s.size.0).max();
+        let bottom = self.streams.iter().map(|s| s.position.1 + s.size.1).max();
+        (
+            right.map_or(0, |right| right - x),
+            bottom.map_or(0, |bottom| bottom - y),
+        )
+    }
+
+    /// The stream at a logical point.
+    pub fn stream_at(&self, x: f64, y: f64) -> Option<u32> {
+        self.streams
+            .iter()
+            .find(|stream| stream.contains(x, y))
+            .map(|stream| stream.node_id)
+    }
+
+    /// The stream with the given mapping ID, as used by the regions of
+    /// libei and the input capture portal.
+    pub fn stream_for_mapping_id(&self, mapping_id: &str) -> Option<u32> {
+        self.streams
+            .iter()
+            .find(|stream| stream.mapping_id.as_deref() == Some(mapping_id))
+            .map(|stream| stream.node_id)
+    }
+
+    /// The stream covering exactly an input capture [`Region`].
+    pub fn stream_for_region(&self, region: Region) -> Option<u32> {
+        self.streams
+            .iter()
+            .find(|stream| {
+                stream.position == (region.x_offset(), region.y_offset())
+                    && stream.size.0 as i64 == region.width() as i64
+                    && stream.size.1 as i64 == region.height() as i64
+            })
+            .map(|stream| stream.node_id)
+    }
+
+    /// Convert a logical point to the stream containing it and the point
+    /// relative to that stream.
+    pub fn logical_to_stream(&self, x: f64, y: f64) -> Option<(u32, f64, f64)> {
+        let stream = self.streams.iter().find(|stream| stream.contains(x, y))?;
+        Some((
+            stream.node_id,
+            x - stream.position.0 as f64,
+            y - stream.position.1 as f64,
+        ))
+    }
+
+    /// Convert a point relative to a stream to a logical point.
+    pub fn stream_to_logical(&self, node_id: u32, x: f64, y: f64) -> Option<(f64, f64)> {
+        let stream = self.stream(node_id)?;
+        Some((x + stream.position.0 as f64, y + stream.position.1 as f64))
+    }
+
+    /// Convert a pixel of a stream to a point relative to that stream.
+    pub fn pixel_to_stream(&self, node_id: u32, x: f64, y: f64) -> Option<(f64, f64)> {
+        let (scale_x, scale_y) = self.stream(node_id)?.scale();
+        Some((x / scale_x, y / scale_y))
+    }
+
+    /// Convert a point relative to a stream to a pixel of that stream.
+    pub fn stream_to_pixel(&self, node_id: u32, x: f64, y: f64) -> Option<(f64, f64)> {
+        let (scale_x, scale_y) = self.stream(node_id)?.scale();
+        Some((x * scale_x, y * scale_y))
+    }
+
+    /// Convert a pixel of a stream to a logical point.
+    pub fn pixel_to_logical(&self, node_id: u32, x: f64, y: f64) -> Option<(f64, f64)> {
+        let (x, y) = self.pixel_to_stream(node_id, x, y)?;
+        self.stream_to_logical(node_id, x, y)
+    }
+
+    /// Convert a logical point to the stream containing it and the pixel of
+    /// that stream.
+    pub fn logical_to_pixel(&self, x: f64, y: f64) -> Option<(u32, f64, f64)> {
+        let (node_id, x, y) = self.logical_to_stream(x, y)?;
+        let (x, y) = self.stream_to_pixel(node_id, x, y)?;
+        Some((node_id, x, y))
+    }
+
+    fn stream(&self, node_id: u32) -> Option<&StreamGeometry> {
+        self.streams.iter().find(|stream| stream.node_id == node_id)
+    }
+
+    fn stream_mut(&mut self, node_id: u32) -> Option<&mut StreamGeometry> {
+        self.streams
+            .iter_mut()
+            .find(|stream| stream.node_id == node_id)
+    }
+}
+
+impl From<&Streams> for ScreencastLayout {
+    fn from(streams: &Streams) -> Self {
+        Self::new(streams.streams())
+    }
+}
+
+#[cfg(all(test, any(feature = "backend", feature = "testing")))]
+mod tests {
+    use super::*;
+
+    fn layout() -> ScreencastLayout {
+        let streams = [
+            Stream::builder(40)
+                .position((0, 0))
+                .size((1920, 1080))
+                .mapping_id("left")
+                .build(),
+            Stream::builder(41)
+                .position((1920, 0))
+                .size((1280, 1024))
+                .build(),
+            // No size, left out.
+            Stream::builder(42).build(),
+        ];
+        let mut layout = ScreencastLayout::new(&streams);
+        layout.set_pixel_size(40, (3840, 2160));
+        layout
+    }
+
+    #[test]
+    fn bounds_and_lookup() {
+        let layout = layout();
+        assert_eq!(layout.node_ids().collect::<Vec<_>>(), [40, 41]);
+        assert_eq!(layout.position(), (0, 0));
+        assert_eq!(layout.size(), (3200, 1080));
+
+        assert_eq!(layout.stream_at(100.0, 100.0), Some(40));
+        assert_eq!(layout.stream_at(1920.0, 100.0), Some(41));
+        assert_eq!(layout.stream_at(2000.0, 1050.0), None);
+        assert_eq!(layout.stream_for_mapping_id("left"), Some(40));
+        assert_eq!(layout.stream_for_mapping_id("right"), None);
+    }
+
+    #[test]
+    fn mixed_scales() {
+        let layout = layout();
+        assert_eq!(
+            layout.logical_to_stream(2000.0, 100.0),
+            Some((41, 80.0, 100.0))
+        );
+        assert_eq!(
+            layout.stream_to_logical(41, 80.0, 100.0),
+            Some((2000.0, 100.0))
+        );
+
+        // The left monitor is scaled by 2.
+        assert_eq!(
+            layout.pixel_to_stream(40, 200.0, 300.0),
+            Some((100.0, 150.0))
+        );
+        assert_eq!(
+            layout.pixel_to_logical(40, 200.0, 300.0),
+            Some((100.0, 150.0))
+        );
+        assert_eq!(
+            layout.logical_to_pixel(100.0, 150.0),
+            Some((40, 200.0, 300.0))
+        );
+        // The right one isn't.
+        assert_eq!(
+            layout.pixel_to_logical(41, 200.0, 300.0),
+            Some((2120.0, 300.0))
+        );
+        assert_eq!(layout.pixel_to_logical(42, 0.0, 0.0), None);
+    }
+}
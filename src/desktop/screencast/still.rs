@@ -0,0 +1,277 @@
+use std::{io, time::Duration};
+
+use enumflags2::BitFlags;
+use futures_util::{
+    future::{select, Either},
+    StreamExt,
+};
+
+use super::{CursorMode, Frame, FrameStream, PixelFormat, Screencast, SourceType};
+use crate::{blocking, desktop::PersistMode, helpers::sleep, Error, WindowIdentifier};
+
+/// How long to wait for the first frame of the stream.
+const FIRST_FRAME_TIMEOUT: Duration = Duration::from_secs(5);
+
+impl PixelFormat {
+    /// The offsets of the red, green, blue and alpha bytes in a pixel.
+    fn channels(self) -> ([usize; 3], Option<usize>) {
+        match self {
+            Self::Bgrx => ([2, 1, 0], None),
+            Self::Rgbx | Self::Rgb => ([0, 1, 2], None),
+            Self::Xrgb => ([1, 2, 3], None),
+            Self::Xbgr => ([3, 2, 1], None),
+            Self::Bgra => ([2, 1, 0], Some(3)),
+            Self::Rgba => ([0, 1, 2], Some(3)),
+            Self::Argb => ([1, 2, 3], Some(0)),
+            Self::Abgr => ([3, 2, 1], Some(0)),
+            Self::Bgr => ([2, 1, 0], None),
+        }
+    }
+}
+
+impl Frame {
+    /// Encode the frame as a PNG image.
+    pub fn to_png(&self) -> Result<Vec<u8>, Error> {
+        encode_png(self.data(), self.size(), self.stride(), self.format())
+    }
+}
+
+fn encode_png(
+    data: &[u8],
+    (width, height): (u32, u32),
+    stride: usize,
+    format: PixelFormat,
+) -> Result<Vec<u8>, Error> {
+    let ([r, g, b], alpha) = format.channels();
+    let bpp = format.bytes_per_pixel();
+
+    let row_len = width as usize * bpp;
+    let mut pixels = Vec::with_capacity(width as usize * height as usize * 4);
+    for y in 0..height as usize {
+        let start = y * stride;
+        let row = data
+            .get(start..start + row_len)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "Truncated frame"))?;
+        for pixel in row.chunks_exact(bpp) {
+            pixels.extend_from_slice(&[pixel[r], pixel[g], pixel[b]]);
+            if let Some(a) = alpha {
+                pixels.push(pixel[a]);
+            }
+        }
+    }
+
+    let mut png = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png, width, height);
+    encoder.set_color(if alpha.is_some() {
+        png::ColorType::Rgba
+    } else {
+        png::ColorType::Rgb
+    });
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&pixels))
+        .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+    Ok(png)
+}
+
+/// A still image captured with [`capture_still`].
+#[derive(Debug)]
+pub struct Still {
+    frame: Frame,
+    png: Vec<u8>,
+    restore_token: Option<String>,
+}
+
+impl Still {
+    /// The image, encoded as PNG.
+    pub fn png(&self) -> &[u8] {
+        &self.png
+    }
+
+    /// Take the PNG encoded image.
+    pub fn into_png(self) -> Vec<u8> {
+        self.png
+    }
+
+    /// The captured frame.
+    pub fn frame(&self) -> &Frame {
+        &self.frame
+    }
+
+    /// The token to capture the same source again without asking the user,
+    /// if a [`PersistMode`] other than [`PersistMode::DoNot`] was requested.
+    pub fn restore_token(&self) -> Option<&str> {
+        self.restore_token.as_deref()
+    }
+}
+
+/// Capture a single frame of a monitor or a window, as a fallback for the
+/// [`Screenshot`](crate::desktop::screenshot::Screenshot) portal.
+///
+/// A screen cast session is started, the first complete frame of its first
+/// stream is encoded as PNG and the session is closed. Pass the
+/// [`Still::restore_token`] of a previous capture along with a
+/// [`PersistMode`] to capture the same source again without the selection
+/// dialog.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use ashpd::desktop::{screencast::capture_still, PersistMode};
+///
+/// async fn run() -> ashpd::Result<()> {
+///     let mut restore_token = None;
+///     for _ in 0..3 {
+///         let still = capture_still()
+///             .restore_token(restore_token.as_deref())
+///             .persist_mode(PersistMode::ExplicitlyRevoked)
+///             .send()
+///             .await?;
+///         std::fs::write("capture.png", still.png())?;
+///         restore_token = still.restore_token().map(ToOwned::to_owned);
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn capture_still() -> CaptureStillRequest {
+    CaptureStillRequest::default()
+}
+
+/// A [builder-pattern] type to construct a [`Still`].
+///
+/// [builder-pattern]: https://doc.rust-lang.org/1.0.0/style/ownership/builders.html
+#[derive(Debug)]
+pub struct CaptureStillRequest {
+    types: BitFlags<SourceType>,
+    cursor_mode: CursorMode,
+    restore_token: Option<String>,
+    persist_mode: PersistMode,
+    identifier: WindowIdentifier,
+    connection: Option<zbus::Connection>,
+}
+
+impl Default for CaptureStillRequest {
+    fn default() -> Self {
+        Self {
+            types: SourceType::Monitor.into(),
+            cursor_mode: CursorMode::Embedded,
+            restore_token: None,
+            persist_mode: PersistMode::DoNot,
+            identifier: WindowIdentifier::default(),
+            connection: None,
+        }
+    }
+}
+
+impl CaptureStillRequest {
+    #[must_use]
+    /// Sets the connection to send the request on, instead of the session bus.
+    pub fn connection(mut self, connection: impl Into<Option<zbus::Connection>>) -> Self {
+        self.connection = connection.into();
+        self
+    }
+
+    #[must_use]
+    /// Sets a window identifier.
+    pub fn identifier(mut self, identifier: impl Into<Option<WindowIdentifier>>) -> Self {
+        self.identifier = identifier.into().unwrap_or_default();
+        self
+    }
+
+    /// Sets the types of content the user can pick. Default is
+    /// [`SourceType::Monitor`].
+    #[must_use]
+    pub fn types(mut self, types: BitFlags<SourceType>) -> Self {
+        self.types = types;
+        self
+    }
+
+    /// Sets how the cursor is drawn. Default is [`CursorMode::Embedded`].
+    #[must_use]
+    pub fn cursor_mode(mut self, cursor_mode: CursorMode) -> Self {
+        self.cursor_mode = cursor_mode;
+        self
+    }
+
+    /// Sets the token of a previous capture, to restore its source.
+    #[must_use]
+    pub fn restore_token<'a>(mut self, token: impl Into<Option<&'a str>>) -> Self {
+        self.restore_token = token.into().map(ToOwned::to_owned);
+        self
+    }
+
+    /// Sets how long the selected source is remembered. Default is
+    /// [`PersistMode::DoNot`].
+    #[must_use]
+    pub fn persist_mode(mut self, persist_mode: PersistMode) -> Self {
+        self.persist_mode = persist_mode;
+        self
+    }
+
+    /// Capture the [`Still`].
+    pub async fn send(self) -> Result<Still, Error> {
+        let proxy = match self.connection {
+            Some(connection) => Screencast::with_connection(&connection).await?,
+            None => Screencast::new().await?,
+        };
+        // Also close the session when bailing out on an error.
+        let session = proxy.create_session().await?.close_on_drop(true);
+        proxy
+            .select_sources(
+                &session,
+                self.cursor_mode,
+                self.types,
+                false,
+                self.restore_token.as_deref(),
+                self.persist_mode,
+            )
+            .await?
+            .response()
+            .await?;
+        let streams = proxy
+            .start(&session, &self.identifier)
+            .await?
+            .response()
+            .await?;
+        let stream = streams.streams().first().ok_or(Error::NoStreams)?;
+        let fd = proxy.open_pipe_wire_remote(&session).await?;
+
+        let mut frames = FrameStream::new(fd, stream.pipe_wire_node_id()).await?;
+        let frame = match select(frames.next(), Box::pin(sleep(FIRST_FRAME_TIMEOUT))).await {
+            Either::Left((frame, _)) => frame.ok_or(Error::StreamEnded)?,
+            Either::Right(_) => return Err(Error::Timeout),
+        };
+        Ok(Still {
+            png: frame.to_png()?,
+            frame,
+            restore_token: streams.restore_token().map(ToOwned::to_owned),
+        })
+    }
+
+    /// Blocking version of [`send()`][`Self::send`].
+    pub fn send_blocking(self) -> Result<Still, Error> {
+        blocking::block_on(self.send())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn png_encoding() {
+        // A 2x1 BGRx frame, a red and a blue pixel, with a padded stride.
+        let data = [0, 0, 0xff, 0, 0xff, 0, 0, 0, 0xaa, 0xaa];
+        let png = encode_png(&data, (2, 1), 10, PixelFormat::Bgrx).unwrap();
+
+        let mut reader = png::Decoder::new(png.as_slice()).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.width, info.height), (2, 1));
+        assert_eq!(info.color_type, png::ColorType::Rgb);
+        assert_eq!(pixels, [0xff, 0, 0, 0, 0, 0xff]);
+
+        assert!(encode_png(&data[..6], (2, 1), 10, PixelFormat::Bgrx).is_err());
+    }
+}
@@ -15,7 +15,8 @@
 //!         .modal(true)
 //!         .send()
 //!         .await?
-//!         .response()?;
+//!         .response()
+//!         .await?;
 //!     println!("URI: {}", response.uri());
 //!     Ok(())
 //! }
@@ -27,7 +28,7 @@
 //! use ashpd::desktop::Color;
 //!
 //! async fn run() -> ashpd::Result<()> {
-//!     let color = Color::pick().send().await?.response()?;
+//!     let color = Color::pick().send().await?.response().await?;
 //!     println!("({}, {}, {})", color.red(), color.green(), color.blue());
 //!
 //!     Ok(())
@@ -38,7 +39,7 @@ use std::fmt::Debug;
 use zbus::zvariant::{DeserializeDict, SerializeDict, Type};
 
 use super::{HandleToken, Request};
-use crate::{desktop::Color, proxy::Proxy, Error, WindowIdentifier};
+use crate::{blocking, desktop::Color, proxy::Proxy, Error, WindowIdentifier};
 
 #[derive(SerializeDict, Type, Debug, Default)]
 #[zvariant(signature = "dict")]
@@ -49,6 +50,7 @@ struct ScreenshotOptions {
 }
 
 #[derive(DeserializeDict, Type)]
+#[cfg_attr(any(feature = "backend", feature = "testing"), derive(SerializeDict))]
 #[zvariant(signature = "dict")]
 /// The response of a [`ScreenshotRequest`] request.
 pub struct Screenshot {
@@ -56,6 +58,13 @@ pub struct Screenshot {
 }
 
 impl Screenshot {
+    #[cfg(any(feature = "backend", feature = "testing"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "backend", feature = "testing"))))]
+    /// Create a new screenshot response pointing to the given URI.
+    pub fn new(uri: url::Url) -> Self {
+        Self { uri }
+    }
+
     /// Creates a new builder-pattern struct instance to construct
     /// [`Screenshot`].
     ///
@@ -93,6 +102,16 @@ impl<'a> ScreenshotProxy<'a> {
         Ok(Self(proxy))
     }
 
+    /// Create a new instance of [`ScreenshotProxy`] on the given connection.
+    pub async fn with_connection(
+        connection: &zbus::Connection,
+    ) -> Result<ScreenshotProxy<'a>, Error> {
+        let proxy =
+            Proxy::new_desktop_with_connection(connection, "org.freedesktop.portal.Screenshot")
+                .await?;
+        Ok(Self(proxy))
+    }
+
     /// Obtains the color of a single pixel.
     ///
     /// # Arguments
@@ -163,9 +182,17 @@ impl<'a> std::ops::Deref for ScreenshotProxy<'a> {
 pub struct ColorRequest {
     identifier: WindowIdentifier,
     options: ColorOptions,
+    connection: Option<zbus::Connection>,
 }
 
 impl ColorRequest {
+    #[must_use]
+    /// Sets the connection to send the request on, instead of the session bus.
+    pub fn connection(mut self, connection: impl Into<Option<zbus::Connection>>) -> Self {
+        self.connection = connection.into();
+        self
+    }
+
     #[must_use]
     /// Sets a window identifier.
     pub fn identifier(mut self, identifier: WindowIdentifier) -> Self {
@@ -175,9 +202,17 @@ impl ColorRequest {
 
     /// Build the [`Color`].
     pub async fn send(self) -> Result<Request<Color>, Error> {
-        let proxy = ScreenshotProxy::new().await?;
+        let proxy = match self.connection {
+            Some(connection) => ScreenshotProxy::with_connection(&connection).await?,
+            None => ScreenshotProxy::new().await?,
+        };
         proxy.pick_color(&self.identifier, self.options).await
     }
+
+    /// Blocking version of [`send()`][`Self::send`].
+    pub fn send_blocking(self) -> Result<blocking::desktop::Request<Color>, Error> {
+        blocking::block_on(self.send()).map(blocking::desktop::Request::from)
+    }
 }
 
 impl Color {
@@ -198,9 +233,17 @@ impl Color {
 pub struct ScreenshotRequest {
     options: ScreenshotOptions,
     identifier: WindowIdentifier,
+    connection: Option<zbus::Connection>,
 }
 
 impl ScreenshotRequest {
+    #[must_use]
+    /// Sets the connection to send the request on, instead of the session bus.
+    pub fn connection(mut self, connection: impl Into<Option<zbus::Connection>>) -> Self {
+        self.connection = connection.into();
+        self
+    }
+
     #[must_use]
     /// Sets a window identifier.
     pub fn identifier(mut self, identifier: impl Into<Option<WindowIdentifier>>) -> Self {
@@ -225,7 +268,15 @@ impl ScreenshotRequest {
 
     /// Build the [`Screenshot`].
     pub async fn send(self) -> Result<Request<Screenshot>, Error> {
-        let proxy = ScreenshotProxy::new().await?;
+        let proxy = match self.connection {
+            Some(connection) => ScreenshotProxy::with_connection(&connection).await?,
+            None => ScreenshotProxy::new().await?,
+        };
         proxy.screenshot(&self.identifier, self.options).await
     }
+
+    /// Blocking version of [`send()`][`Self::send`].
+    pub fn send_blocking(self) -> Result<blocking::desktop::Request<Screenshot>, Error> {
+        blocking::block_on(self.send()).map(blocking::desktop::Request::from)
+    }
 }
@@ -9,7 +9,7 @@
 //!     let secret = Secret::new().await?;
 //!
 //!     let (mut x1, x2) = std::os::unix::net::UnixStream::pair()?;
-//!     secret.retrieve(&x2.as_fd()).await?;
+//!     secret.retrieve(&x2.as_fd()).await?.response().await?;
 //!     drop(x2);
 //!     let mut buf = Vec::new();
 //!     x1.read_to_end(&mut buf)?;
@@ -19,10 +19,12 @@
 //! ```
 
 use std::os::fd::{AsFd, BorrowedFd};
+#[cfg(not(any(feature = "async-std", feature = "tokio")))]
+use std::{io::Read, os::unix::net::UnixStream};
 
-#[cfg(feature = "async-std")]
+#[cfg(all(feature = "async-std", not(feature = "tokio")))]
 use async_net::unix::UnixStream;
-#[cfg(feature = "async-std")]
+#[cfg(all(feature = "async-std", not(feature = "tokio")))]
 use futures_util::AsyncReadExt;
 #[cfg(feature = "tokio")]
 use tokio::{io::AsyncReadExt, net::UnixStream};
@@ -58,6 +60,13 @@ impl<'a> Secret<'a> {
         Ok(Self(proxy))
     }
 
+    /// Create a new instance of [`Secret`] on the given connection.
+    pub async fn with_connection(connection: &zbus::Connection) -> Result<Secret<'a>, Error> {
+        let proxy =
+            Proxy::new_desktop_with_connection(connection, "org.freedesktop.portal.Secret").await?;
+        Ok(Self(proxy))
+    }
+
     /// Retrieves a master secret for a sandboxed application.
     ///
     /// # Arguments
@@ -95,10 +104,20 @@ pub async fn retrieve() -> Result<Vec<u8>, Error> {
     let proxy = Secret::new().await?;
 
     let (mut x1, x2) = UnixStream::pair()?;
-    proxy.retrieve(&x2.as_fd()).await?;
+    proxy.retrieve(&x2.as_fd()).await?.response().await?;
     drop(x2);
-    let mut buf = Vec::new();
-    x1.read_to_end(&mut buf).await?;
+    #[cfg(any(feature = "async-std", feature = "tokio"))]
+    let buf = {
+        let mut buf = Vec::new();
+        x1.read_to_end(&mut buf).await?;
+        buf
+    };
+    #[cfg(not(any(feature = "async-std", feature = "tokio")))]
+    let buf = crate::helpers::unblock(move || {
+        let mut buf = Vec::new();
+        x1.read_to_end(&mut buf).map(|_| buf)
+    })
+    .await?;
 
     Ok(buf)
 }
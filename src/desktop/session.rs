@@ -1,13 +1,53 @@
-use std::{collections::HashMap, fmt::Debug};
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt::Debug,
+    marker::PhantomData,
+};
 
-use futures_util::Stream;
+use futures_util::{lock::Mutex, Stream, StreamExt};
 use serde::{Deserialize, Serialize, Serializer};
-use zbus::zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, Type};
+use zbus::zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, Signature, Type};
 
 use crate::{desktop::HandleToken, proxy::Proxy, Error};
 
+/// The details the portal sent along when closing a [`Session`].
 pub type SessionDetails = HashMap<String, OwnedValue>;
 
+enum Closed {
+    Pending(Box<zbus::proxy::SignalStream<'static>>),
+    Closed(SessionDetails),
+}
+
+pub(crate) mod sealed {
+    pub trait Sealed {}
+}
+
+/// A portal that creates long lived [`Session`]s.
+///
+/// Used to tie a [`Session`] to the portal that created it, passing it to
+/// another portal fails to compile.
+///
+/// ```rust,compile_fail
+/// use ashpd::desktop::{global_shortcuts::GlobalShortcuts, screencast::Screencast};
+///
+/// async fn run() -> ashpd::Result<()> {
+///     let session = GlobalShortcuts::new().await?.create_session().await?;
+///     Screencast::new()
+///         .await?
+///         .open_pipe_wire_remote(&session)
+///         .await?;
+///     Ok(())
+/// }
+/// ```
+pub trait SessionPortal: sealed::Sealed {}
+
+/// A [`SessionPortal`] whose sessions can be used with the ScreenCast portal.
+///
+/// Both [`Screencast`](crate::desktop::screencast::Screencast) and
+/// [`RemoteDesktop`](crate::desktop::remote_desktop::RemoteDesktop) sessions
+/// can select and record screen cast sources.
+pub trait HasScreencast: SessionPortal {}
+
 /// Shared by all portal interfaces that involve long lived sessions.
 ///
 /// When a method that creates a session is called, if successful, the reply
@@ -19,33 +59,69 @@ pub type SessionDetails = HashMap<String, OwnedValue>;
 /// and a signal [`Session::receive_closed`]. Whether it is allowed to
 /// directly call [`Session::close`] depends on the interface.
 ///
+/// The session is parameterised by the [`SessionPortal`] that created it, e.g.
+/// `Session<'_, Screencast<'_>>`. It can be closed once dropped with
+/// [`close_on_drop()`][`Session::close_on_drop`].
+///
 /// Wrapper of the DBus interface: [`org.freedesktop.portal.Session`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.portal.Session.html).
-#[derive(Type)]
 #[doc(alias = "org.freedesktop.portal.Session")]
-#[zvariant(signature = "o")]
-pub struct Session<'a>(Proxy<'a>);
+pub struct Session<'a, P: SessionPortal> {
+    proxy: Proxy<'a>,
+    /// Subscribed to on creation, so that [`Session::closed`] can't miss the
+    /// signal.
+    closed: Mutex<Closed>,
+    /// The touch slots held down by the gestures performed on a remote
+    /// desktop session.
+    touch_slots: std::sync::Mutex<BTreeSet<u32>>,
+    close_on_drop: bool,
+    _portal: PhantomData<fn() -> P>,
+}
 
-impl<'a> Session<'a> {
+impl<'a, P: SessionPortal> Session<'a, P> {
     /// Create a new instance of [`Session`].
     ///
     /// **Note** A [`Session`] is not supposed to be created manually.
-    pub(crate) async fn new<P>(path: P) -> Result<Session<'a>, Error>
+    pub(crate) async fn new<T>(
+        connection: &zbus::Connection,
+        path: T,
+    ) -> Result<Session<'a, P>, Error>
     where
-        P: TryInto<ObjectPath<'a>>,
-        P::Error: Into<zbus::Error>,
+        T: TryInto<ObjectPath<'a>>,
+        T::Error: Into<zbus::Error>,
     {
-        let proxy = Proxy::new_desktop_with_path("org.freedesktop.portal.Session", path).await?;
-        Ok(Self(proxy))
+        let proxy =
+            Proxy::new_desktop_with_path(connection, "org.freedesktop.portal.Session", path)
+                .await?;
+        let closed = proxy.receive_signal("Closed").await?;
+        Ok(Self {
+            proxy,
+            closed: Mutex::new(Closed::Pending(Box::new(closed))),
+            touch_slots: Default::default(),
+            close_on_drop: false,
+            _portal: PhantomData,
+        })
     }
 
     pub(crate) async fn from_unique_name(
+        connection: &zbus::Connection,
         handle_token: &HandleToken,
-    ) -> Result<Session<'a>, crate::Error> {
-        let path =
-            Proxy::unique_name("/org/freedesktop/portal/desktop/session", handle_token).await?;
+    ) -> Result<Session<'a, P>, crate::Error> {
+        let path = Proxy::unique_name(
+            connection,
+            "/org/freedesktop/portal/desktop/session",
+            handle_token,
+        )?;
         #[cfg(feature = "tracing")]
         tracing::info!("Creating a org.freedesktop.portal.Session {}", path);
-        Self::new(path).await
+        Self::new(connection, path).await
+    }
+
+    /// Whether to [`close()`][`Session::close`] the session when it is
+    /// dropped, defaults to `false`.
+    #[must_use]
+    pub fn close_on_drop(mut self, close_on_drop: bool) -> Self {
+        self.close_on_drop = close_on_drop;
+        self
     }
 
     /// Emitted when a session is closed.
@@ -55,7 +131,37 @@ impl<'a> Session<'a> {
     /// See also [`Closed`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.portal.Session.html#org-freedesktop-portal-session-closed).
     #[doc(alias = "Closed")]
     pub async fn receive_closed(&self) -> Result<impl Stream<Item = SessionDetails>, Error> {
-        self.0.signal("Closed").await
+        self.proxy.signal("Closed").await
+    }
+
+    /// Wait until the session is closed by the portal, with the details of
+    /// the closing.
+    ///
+    /// The signal is received from the creation of the session on, so
+    /// closings before the call aren't missed.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`Closed`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.portal.Session.html#org-freedesktop-portal-session-closed).
+    pub async fn closed(&self) -> Result<SessionDetails, Error> {
+        let mut closed = self.closed.lock().await;
+        if let Closed::Pending(stream) = &mut *closed {
+            let details = loop {
+                let message = stream.next().await.ok_or(Error::NoResponse)?;
+                if let Ok(details) = message.body().deserialize::<SessionDetails>() {
+                    break details;
+                }
+            };
+            *closed = Closed::Closed(details);
+        }
+        match &*closed {
+            Closed::Closed(details) => Ok(details
+                .iter()
+                .map(|(key, value)| Ok((key.clone(), value.try_clone()?)))
+                .collect::<Result<_, zbus::zvariant::Error>>()
+                .map_err(zbus::Error::from)?),
+            Closed::Pending(_) => unreachable!("the session was just closed"),
+        }
     }
 
     /// Closes the portal session to which this object refers and ends all
@@ -66,15 +172,57 @@ impl<'a> Session<'a> {
     /// See also [`Close`](https://flatpak.github.io/xdg-desktop-portal/docs/doc-org.freedesktop.portal.Session.html#org-freedesktop-portal-session-close).
     #[doc(alias = "Close")]
     pub async fn close(&self) -> Result<(), Error> {
-        self.0.call("Close", &()).await
+        self.proxy.call("Close", &()).await
     }
 
     pub(crate) fn path(&self) -> &ObjectPath<'_> {
-        self.0.path()
+        self.proxy.path()
+    }
+
+    pub(crate) fn touch_slots(&self) -> &std::sync::Mutex<BTreeSet<u32>> {
+        &self.touch_slots
+    }
+}
+
+impl<'a, P: SessionPortal> Drop for Session<'a, P> {
+    fn drop(&mut self) {
+        if !self.close_on_drop {
+            return;
+        }
+        let connection = self.proxy.connection().clone();
+        let destination = self.proxy.destination().to_owned();
+        let path = self.path().to_owned();
+        #[cfg(feature = "tracing")]
+        tracing::info!("Closing dropped session {}", path);
+        connection
+            .clone()
+            .executor()
+            .spawn(
+                async move {
+                    let proxy = zbus::Proxy::new_owned(
+                        connection,
+                        destination,
+                        path,
+                        "org.freedesktop.portal.Session",
+                    )
+                    .await;
+                    if let Ok(proxy) = proxy {
+                        let _ = proxy.call_noreply("Close", &()).await;
+                    }
+                },
+                "ashpd session close",
+            )
+            .detach();
     }
 }
 
-impl<'a> Serialize for Session<'a> {
+impl<'a, P: SessionPortal> Type for Session<'a, P> {
+    fn signature() -> Signature<'static> {
+        ObjectPath::signature()
+    }
+}
+
+impl<'a, P: SessionPortal> Serialize for Session<'a, P> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
@@ -83,7 +231,7 @@ impl<'a> Serialize for Session<'a> {
     }
 }
 
-impl<'a> Debug for Session<'a> {
+impl<'a, P: SessionPortal> Debug for Session<'a, P> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_tuple("Session")
             .field(&self.path().as_str())
@@ -135,3 +283,47 @@ impl<'de> Deserialize<'de> for CreateSessionResponse {
         })
     }
 }
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{desktop::screencast::Screencast, helpers::sleep, testing::FakePortal};
+
+    #[test]
+    fn closed_and_close_on_drop() {
+        zbus::block_on(async {
+            let portal = FakePortal::new().await.unwrap();
+            let session = portal
+                .session::<Screencast<'_>>()
+                .await
+                .unwrap()
+                .close_on_drop(true);
+
+            let details = SessionDetails::from([("reason".to_owned(), OwnedValue::from(2u32))]);
+            // Closed before waiting for it.
+            portal
+                .emit_signal_at(
+                    FakePortal::SESSION_PATH,
+                    "org.freedesktop.portal.Session",
+                    "Closed",
+                    &details,
+                )
+                .await
+                .unwrap();
+            assert_eq!(session.closed().await.unwrap(), details);
+            assert_eq!(session.closed().await.unwrap(), details);
+
+            drop(session);
+            // Let the fake portal handle the Close call.
+            sleep(Duration::from_millis(100)).await;
+            let calls = portal.calls("org.freedesktop.portal.Session", "Close");
+            assert_eq!(calls.len(), 1);
+            assert_eq!(
+                calls[0].header().path().unwrap().as_str(),
+                FakePortal::SESSION_PATH
+            );
+        });
+    }
+}
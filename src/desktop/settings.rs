@@ -153,6 +153,14 @@ impl<'a> Settings<'a> {
         Ok(Self(proxy))
     }
 
+    /// Create a new instance of [`Settings`] on the given connection.
+    pub async fn with_connection(connection: &zbus::Connection) -> Result<Settings<'a>, Error> {
+        let proxy =
+            Proxy::new_desktop_with_connection(connection, "org.freedesktop.portal.Settings")
+                .await?;
+        Ok(Self(proxy))
+    }
+
     /// Reads a single value. Returns an error on any unknown namespace or key.
     ///
     /// # Arguments
@@ -306,6 +314,14 @@ impl<'a> Settings<'a> {
     }
 }
 
+impl crate::resilient::sealed::Sealed for Settings<'_> {
+    fn proxy(&self) -> &Proxy<'_> {
+        &self.0
+    }
+}
+
+impl crate::PortalProxy for Settings<'_> {}
+
 impl<'a> std::ops::Deref for Settings<'a> {
     type Target = zbus::Proxy<'a>;
 
@@ -58,6 +58,13 @@ impl<'a> TrashProxy<'a> {
         Ok(Self(proxy))
     }
 
+    /// Create a new instance of [`TrashProxy`] on the given connection.
+    pub async fn with_connection(connection: &zbus::Connection) -> Result<TrashProxy<'a>, Error> {
+        let proxy =
+            Proxy::new_desktop_with_connection(connection, "org.freedesktop.portal.Trash").await?;
+        Ok(Self(proxy))
+    }
+
     /// Sends a file to the trashcan.
     /// Applications are allowed to trash a file if they can open it in
     /// read/write mode.
@@ -74,9 +81,9 @@ impl<'a> TrashProxy<'a> {
     pub async fn trash_file(&self, fd: &BorrowedFd<'_>) -> Result<(), Error> {
         let status = self.0.call("TrashFile", &(Fd::from(fd))).await?;
         match status {
-            TrashStatus::Failed => Err(Error::Portal(PortalError::Failed(
-                "Failed to trash file".to_string(),
-            ))),
+            TrashStatus::Failed => {
+                Err(PortalError::Failed("Failed to trash file".to_string()).into())
+            }
             TrashStatus::Succeeded => Ok(()),
         }
     }
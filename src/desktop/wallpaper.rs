@@ -45,7 +45,7 @@ use serde::{self, Deserialize, Serialize};
 use zbus::zvariant::{Fd, SerializeDict, Type};
 
 use super::Request;
-use crate::{desktop::HandleToken, proxy::Proxy, Error, WindowIdentifier};
+use crate::{blocking, desktop::HandleToken, proxy::Proxy, Error, WindowIdentifier};
 
 #[cfg_attr(feature = "glib", derive(glib::Enum))]
 #[cfg_attr(feature = "glib", enum_type(name = "AshpdSetOn"))]
@@ -123,6 +123,15 @@ impl<'a> WallpaperProxy<'a> {
         Ok(Self(proxy))
     }
 
+    pub async fn with_connection(
+        connection: &zbus::Connection,
+    ) -> Result<WallpaperProxy<'a>, Error> {
+        let proxy =
+            Proxy::new_desktop_with_connection(connection, "org.freedesktop.portal.Wallpaper")
+                .await?;
+        Ok(Self(proxy))
+    }
+
     pub async fn set_wallpaper_file(
         &self,
         identifier: &WindowIdentifier,
@@ -171,9 +180,17 @@ impl<'a> std::ops::Deref for WallpaperProxy<'a> {
 pub struct WallpaperRequest {
     identifier: WindowIdentifier,
     options: WallpaperOptions,
+    connection: Option<zbus::Connection>,
 }
 
 impl WallpaperRequest {
+    #[must_use]
+    /// Sets the connection to send the request on, instead of the session bus.
+    pub fn connection(mut self, connection: impl Into<Option<zbus::Connection>>) -> Self {
+        self.connection = connection.into();
+        self
+    }
+
     #[must_use]
     /// Sets a window identifier.
     pub fn identifier(mut self, identifier: impl Into<Option<WindowIdentifier>>) -> Self {
@@ -199,19 +216,41 @@ impl WallpaperRequest {
 
     /// Build using a URI.
     pub async fn build_uri(self, uri: &url::Url) -> Result<Request<()>, Error> {
-        let proxy = WallpaperProxy::new().await?;
+        let proxy = match self.connection {
+            Some(connection) => WallpaperProxy::with_connection(&connection).await?,
+            None => WallpaperProxy::new().await?,
+        };
         proxy
             .set_wallpaper_uri(&self.identifier, uri, self.options)
             .await
     }
 
+    /// Blocking version of [`build_uri()`][`Self::build_uri`].
+    pub fn build_uri_blocking(
+        self,
+        uri: &url::Url,
+    ) -> Result<blocking::desktop::Request<()>, Error> {
+        blocking::block_on(self.build_uri(uri)).map(blocking::desktop::Request::from)
+    }
+
     /// Build using a file.
     pub async fn build_file(self, file: &BorrowedFd<'_>) -> Result<Request<()>, Error> {
-        let proxy = WallpaperProxy::new().await?;
+        let proxy = match self.connection {
+            Some(connection) => WallpaperProxy::with_connection(&connection).await?,
+            None => WallpaperProxy::new().await?,
+        };
         proxy
             .set_wallpaper_file(&self.identifier, file, self.options)
             .await
     }
+
+    /// Blocking version of [`build_file()`][`Self::build_file`].
+    pub fn build_file_blocking(
+        self,
+        file: &BorrowedFd<'_>,
+    ) -> Result<blocking::desktop::Request<()>, Error> {
+        blocking::block_on(self.build_file(file)).map(blocking::desktop::Request::from)
+    }
 }
 #[cfg(test)]
 mod tests {
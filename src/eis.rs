@@ -0,0 +1,827 @@
+//! # Examples
+//!
+//! Move the pointer and type a key through the EIS socket of a remote desktop
+//! session.
+//!
+//! ```rust,no_run
+//! use ashpd::{
+//!     desktop::{
+//!         remote_desktop::{DeviceType, KeyState, RemoteDesktop},
+//!         PersistMode,
+//!     },
+//!     eis::EiSender,
+//!     WindowIdentifier,
+//! };
+//!
+//! async fn run() -> ashpd::Result<()> {
+//!     let proxy = RemoteDesktop::new().await?;
+//!     let session = proxy.create_session().await?;
+//!     proxy
+//!         .select_devices(
+//!             &session,
+//!             DeviceType::Keyboard | DeviceType::Pointer,
+//!             None,
+//!             PersistMode::DoNot,
+//!         )
+//!         .await?
+//!         .response()
+//!         .await?;
+//!     proxy
+//!         .start(&session, &WindowIdentifier::default())
+//!         .await?
+//!         .response()
+//!         .await?;
+//!
+//!     let mut sender = EiSender::new(proxy.connect_to_eis(&session).await?).await?;
+//!     if let Some(mut pointer) = sender.pointer() {
+//!         pointer.motion(10.0, 10.0)?;
+//!     }
+//!     if let Some(mut keyboard) = sender.keyboard() {
+//!         // KEY_A in linux/input-event-codes.h
+//!         keyboard.key(30, KeyState::Pressed)?;
+//!         keyboard.key(30, KeyState::Released)?;
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug},
+    io,
+    os::{fd::OwnedFd, unix::net::UnixStream},
+    sync::OnceLock,
+};
+
+pub use reis;
+use reis::{
+    ei,
+    event::{Device, DeviceCapability, EiEvent, EiEventConverter},
+    handshake::{EiHandshaker, HandshakeError},
+    PendingRequestResult,
+};
+
+use crate::{desktop::remote_desktop::KeyState, Error};
+
+/// The name the client is registered with by [`EiSender::new`].
+pub(crate) const DEFAULT_NAME: &str = "ashpd";
+
+/// The capabilities bound on every seat of the server.
+pub(crate) const CAPABILITIES: &[DeviceCapability] = &[
+    DeviceCapability::Pointer,
+    DeviceCapability::PointerAbsolute,
+    DeviceCapability::Keyboard,
+    DeviceCapability::Touch,
+    DeviceCapability::Scroll,
+    DeviceCapability::Button,
+];
+
+/// The interfaces and versions supported by the client.
+fn interfaces() -> &'static HashMap<&'static str, u32> {
+    static INTERFACES: OnceLock<HashMap<&'static str, u32>> = OnceLock::new();
+    INTERFACES.get_or_init(|| {
+        HashMap::from([
+            ("ei_connection", 1),
+            ("ei_callback", 1),
+            ("ei_pingpong", 1),
+            ("ei_seat", 1),
+            ("ei_device", 2),
+            ("ei_pointer", 1),
+            ("ei_pointer_absolute", 1),
+            ("ei_scroll", 1),
+            ("ei_button", 1),
+            ("ei_keyboard", 1),
+            ("ei_touchscreen", 1),
+        ])
+    })
+}
+
+/// An error of the EI protocol.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum EisError {
+    /// The handshake with the EIS server failed.
+    Handshake(HandshakeError),
+    /// A message of the EIS server couldn't be parsed.
+    Parse(reis::ParseError),
+    /// The EIS server sent events in an unexpected order.
+    Protocol(reis::event::Error),
+    /// The EIS server closed the connection, with its explanation.
+    Disconnected(String),
+    /// The device isn't available for emulation anymore, it was paused or
+    /// removed by the EIS server.
+    DeviceUnavailable,
+}
+
+impl fmt::Display for EisError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Handshake(e) => write!(f, "Handshake failed: {e}"),
+            Self::Parse(e) => write!(f, "Failed to parse a message: {e}"),
+            Self::Protocol(e) => write!(f, "Protocol error: {e}"),
+            Self::Disconnected(explanation) => write!(f, "Disconnected: {explanation}"),
+            Self::DeviceUnavailable => f.write_str("The device is not available"),
+        }
+    }
+}
+
+impl std::error::Error for EisError {}
+
+/// The EIS socket.
+///
+/// async-io drives its own reactor thread, so waiting for the socket works with
+/// any runtime.
+struct Socket(async_io::Async<ei::Context>);
+
+impl Socket {
+    fn new(fd: OwnedFd) -> io::Result<Self> {
+        let context = ei::Context::new(UnixStream::from(fd))?;
+        async_io::Async::new(context).map(Self)
+    }
+
+    fn context(&self) -> &ei::Context {
+        self.0.get_ref()
+    }
+
+    /// Wait until new data is read, failing at the end of the stream.
+    async fn read(&self) -> io::Result<()> {
+        loop {
+            self.0.readable().await?;
+            if self.context().read()? > 0 {
+                return Ok(());
+            }
+        }
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        self.context().flush().map_err(io::Error::from)
+    }
+}
+
+/// A connection to an EIS server, past the handshake.
+pub(crate) struct EiConnection {
+    socket: Socket,
+    connection: ei::Connection,
+    converter: EiEventConverter,
+}
+
+impl EiConnection {
+    pub(crate) async fn new(
+        fd: OwnedFd,
+        name: &str,
+        context_type: ei::handshake::ContextType,
+    ) -> Result<Self, Error> {
+        let socket = Socket::new(fd)?;
+        let mut handshaker = EiHandshaker::new(name, context_type, interfaces());
+        loop {
+            while let Some(event) = socket.context().pending_event() {
+                let event = match event {
+                    PendingRequestResult::Request(event) => event,
+                    PendingRequestResult::ParseError(err) => {
+                        return Err(EisError::Parse(err).into())
+                    }
+                    PendingRequestResult::InvalidObject(id) => {
+                        return Err(EisError::Handshake(HandshakeError::InvalidObject(id)).into())
+                    }
+                };
+                let response = handshaker.handle_event(event).map_err(|err| match err {
+                    HandshakeError::Io(err) => Error::IO(err),
+                    err => EisError::Handshake(err).into(),
+                })?;
+                if let Some(response) = response {
+                    // The events following the handshake stay buffered in the
+                    // context.
+                    return Ok(Self {
+                        socket,
+                        connection: response.connection,
+                        converter: EiEventConverter::new(response.serial),
+                    });
+                }
+            }
+            socket.read().await?;
+        }
+    }
+
+    /// The serial of the last event of the server.
+    pub(crate) fn serial(&self) -> u32 {
+        self.converter.serial()
+    }
+
+    /// The next event already received, without waiting.
+    pub(crate) fn try_next_event(&mut self) -> Result<Option<EiEvent>, Error> {
+        loop {
+            if let Some(event) = self.converter.next_event() {
+                return Ok(Some(event));
+            }
+            match self.socket.context().pending_event() {
+                Some(PendingRequestResult::Request(event)) => self
+                    .converter
+                    .handle_event(event)
+                    .map_err(EisError::Protocol)?,
+                Some(PendingRequestResult::ParseError(err)) => {
+                    return Err(EisError::Parse(err).into())
+                }
+                // Events of objects released in the meantime.
+                Some(PendingRequestResult::InvalidObject(_)) => {}
+                None => return Ok(None),
+            }
+        }
+    }
+
+    /// Read the pending data of the socket, without waiting.
+    pub(crate) fn read_pending(&self) -> Result<(), Error> {
+        self.socket.context().read()?;
+        Ok(())
+    }
+
+    /// Wait for the next event.
+    pub(crate) async fn next_event(&mut self) -> Result<EiEvent, Error> {
+        loop {
+            if let Some(event) = self.try_next_event()? {
+                return Ok(event);
+            }
+            self.socket.read().await?;
+        }
+    }
+
+    /// Send the buffered requests.
+    pub(crate) fn flush(&self) -> Result<(), Error> {
+        self.socket.flush().map_err(From::from)
+    }
+}
+
+impl Drop for EiConnection {
+    fn drop(&mut self) {
+        self.connection.disconnect();
+        let _ = self.socket.flush();
+    }
+}
+
+impl Debug for EiConnection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EiConnection")
+            .field("serial", &self.serial())
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug)]
+struct DeviceState {
+    device: Device,
+    resumed: bool,
+    emulating: bool,
+}
+
+/// A client emulating input devices on an EIS server, e.g. the one of a
+/// remote desktop session returned by
+/// [`RemoteDesktop::connect_to_eis`](crate::desktop::remote_desktop::RemoteDesktop::connect_to_eis).
+///
+/// The client binds every capability of the seats announced by the server and
+/// keeps track of the devices the server adds, pauses, resumes and removes.
+/// The typed emitters, e.g. [`EiSender::pointer`], send their events right
+/// away, each in a frame of its own.
+///
+/// The events of the server, including its pings, are processed whenever an
+/// event is emitted, or with [`EiSender::dispatch`] while idle.
+#[derive(Debug)]
+pub struct EiSender {
+    connection: EiConnection,
+    devices: Vec<DeviceState>,
+    sequence: u32,
+}
+
+impl EiSender {
+    /// Complete the handshake on the EIS socket `fd` and wait for a device to
+    /// be ready for emulation.
+    pub async fn new(fd: OwnedFd) -> Result<Self, Error> {
+        Self::with_name(fd, DEFAULT_NAME).await
+    }
+
+    /// Same as [`EiSender::new`], registering the client with `name`.
+    pub async fn with_name(fd: OwnedFd, name: &str) -> Result<Self, Error> {
+        let connection = EiConnection::new(fd, name, ei::handshake::ContextType::Sender).await?;
+        let mut sender = Self {
+            connection,
+            devices: Vec::new(),
+            sequence: 0,
+        };
+        while !sender.devices.iter().any(|state| state.resumed) {
+            sender.dispatch().await?;
+        }
+        Ok(sender)
+    }
+
+    /// Wait for the next events of the server and process them.
+    pub async fn dispatch(&mut self) -> Result<(), Error> {
+        let event = self.connection.next_event().await?;
+        self.handle_event(event)?;
+        self.dispatch_pending()
+    }
+
+    /// The devices added by the server.
+    pub fn devices(&self) -> impl Iterator<Item = &Device> {
+        self.devices.iter().map(|state| &state.device)
+    }
+
+    /// A device with relative pointer motion, if any is resumed.
+    pub fn pointer(&mut self) -> Option<Pointer<'_>> {
+        let (device, interface) = self.find_device()?;
+        Some(Pointer {
+            sender: self,
+            device,
+            interface,
+        })
+    }
+
+    /// A device with absolute pointer motion, if any is resumed.
+    pub fn pointer_absolute(&mut self) -> Option<PointerAbsolute<'_>> {
+        let (device, interface) = self.find_device()?;
+        Some(PointerAbsolute {
+            sender: self,
+            device,
+            interface,
+        })
+    }
+
+    /// A device with buttons, if any is resumed.
+    pub fn button(&mut self) -> Option<Button<'_>> {
+        let (device, interface) = self.find_device()?;
+        Some(Button {
+            sender: self,
+            device,
+            interface,
+        })
+    }
+
+    /// A device with scrolling, if any is resumed.
+    pub fn scroll(&mut self) -> Option<Scroll<'_>> {
+        let (device, interface) = self.find_device()?;
+        Some(Scroll {
+            sender: self,
+            device,
+            interface,
+        })
+    }
+
+    /// A keyboard, if any is resumed.
+    pub fn keyboard(&mut self) -> Option<Keyboard<'_>> {
+        let (device, interface) = self.find_device()?;
+        Some(Keyboard {
+            sender: self,
+            device,
+            interface,
+        })
+    }
+
+    /// A touchscreen, if any is resumed.
+    pub fn touchscreen(&mut self) -> Option<Touchscreen<'_>> {
+        let (device, interface) = self.find_device()?;
+        Some(Touchscreen {
+            sender: self,
+            device,
+            interface,
+        })
+    }
+
+    fn find_device<T: ei::Interface>(&mut self) -> Option<(Device, T)> {
+        // Emitters should see the latest state of the devices.
+        let _ = self.dispatch_pending();
+        self.devices
+            .iter()
+            .filter(|state| state.resumed)
+            .find_map(|state| Some((state.device.clone(), state.device.interface::<T>()?)))
+    }
+
+    /// Process the events received so far, without waiting.
+    fn dispatch_pending(&mut self) -> Result<(), Error> {
+        self.connection.read_pending()?;
+        while let Some(event) = self.connection.try_next_event()? {
+            self.handle_event(event)?;
+        }
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: EiEvent) -> Result<(), Error> {
+        match event {
+            EiEvent::Disconnected(event) => {
+                return Err(EisError::Disconnected(event.explanation).into());
+            }
+            EiEvent::SeatAdded(event) => {
+                event.seat.bind_capabilities(CAPABILITIES);
+                self.connection.flush()?;
+            }
+            EiEvent::DeviceAdded(event) => self.devices.push(DeviceState {
+                device: event.device,
+                resumed: false,
+                emulating: false,
+            }),
+            EiEvent::DeviceResumed(event) => {
+                if let Some(state) = self.device_mut(&event.device) {
+                    state.resumed = true;
+                }
+            }
+            EiEvent::DevicePaused(event) => {
+                if let Some(state) = self.device_mut(&event.device) {
+                    state.resumed = false;
+                    state.emulating = false;
+                }
+            }
+            EiEvent::DeviceRemoved(event) => {
+                self.devices.retain(|state| state.device != event.device);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn device_mut(&mut self, device: &Device) -> Option<&mut DeviceState> {
+        self.devices
+            .iter_mut()
+            .find(|state| state.device == *device)
+    }
+
+    /// Send the events of `send` in a frame of `device`.
+    fn emit(&mut self, device: &Device, send: impl FnOnce()) -> Result<(), Error> {
+        self.dispatch_pending()?;
+        let serial = self.connection.serial();
+        let sequence = self.sequence.wrapping_add(1);
+        let state = self
+            .device_mut(device)
+            .filter(|state| state.resumed)
+            .ok_or(EisError::DeviceUnavailable)?;
+        if !state.emulating {
+            state.emulating = true;
+            device.device().start_emulating(serial, sequence);
+            self.sequence = sequence;
+        }
+        send();
+        device.device().frame(serial, timestamp());
+        self.connection.flush()
+    }
+}
+
+impl Drop for EiSender {
+    fn drop(&mut self) {
+        let serial = self.connection.serial();
+        for state in self.devices.iter().filter(|state| state.emulating) {
+            state.device.device().stop_emulating(serial);
+        }
+    }
+}
+
+/// The current time in microseconds of `CLOCK_MONOTONIC`, as expected in
+/// frames.
+fn timestamp() -> u64 {
+    let time = rustix::time::clock_gettime(rustix::time::ClockId::Monotonic);
+    time.tv_sec as u64 * 1_000_000 + time.tv_nsec as u64 / 1_000
+}
+
+/// Relative pointer motion, returned by [`EiSender::pointer`].
+#[derive(Debug)]
+pub struct Pointer<'a> {
+    sender: &'a mut EiSender,
+    device: Device,
+    interface: ei::Pointer,
+}
+
+impl Pointer<'_> {
+    /// The device of the emitter.
+    pub fn device(&self) -> &Device {
+        &self.device
+    }
+
+    /// Move the pointer by (`dx`, `dy`) logical pixels.
+    pub fn motion(&mut self, dx: f32, dy: f32) -> Result<(), Error> {
+        self.sender
+            .emit(&self.device, || self.interface.motion_relative(dx, dy))
+    }
+}
+
+/// Absolute pointer motion, returned by [`EiSender::pointer_absolute`].
+#[derive(Debug)]
+pub struct PointerAbsolute<'a> {
+    sender: &'a mut EiSender,
+    device: Device,
+    interface: ei::PointerAbsolute,
+}
+
+impl PointerAbsolute<'_> {
+    /// The device of the emitter, with the regions the pointer can be moved
+    /// within.
+    pub fn device(&self) -> &Device {
+        &self.device
+    }
+
+    /// Move the pointer to (`x`, `y`), in logical pixels within one of the
+    /// regions of the device.
+    pub fn motion_absolute(&mut self, x: f32, y: f32) -> Result<(), Error> {
+        self.sender
+            .emit(&self.device, || self.interface.motion_absolute(x, y))
+    }
+}
+
+/// Buttons, returned by [`EiSender::button`].
+#[derive(Debug)]
+pub struct Button<'a> {
+    sender: &'a mut EiSender,
+    device: Device,
+    interface: ei::Button,
+}
+
+impl Button<'_> {
+    /// The device of the emitter.
+    pub fn device(&self) -> &Device {
+        &self.device
+    }
+
+    /// Press or release `button`, a button code of
+    /// `linux/input-event-codes.h`, e.g. 272 for `BTN_LEFT`.
+    pub fn button(&mut self, button: u32, state: KeyState) -> Result<(), Error> {
+        let state = match state {
+            KeyState::Pressed => ei::button::ButtonState::Press,
+            KeyState::Released => ei::button::ButtonState::Released,
+        };
+        self.sender
+            .emit(&self.device, || self.interface.button(button, state))
+    }
+}
+
+/// Scrolling, returned by [`EiSender::scroll`].
+#[derive(Debug)]
+pub struct Scroll<'a> {
+    sender: &'a mut EiSender,
+    device: Device,
+    interface: ei::Scroll,
+}
+
+impl Scroll<'_> {
+    /// The device of the emitter.
+    pub fn device(&self) -> &Device {
+        &self.device
+    }
+
+    /// Scroll by (`dx`, `dy`) logical pixels.
+    pub fn scroll(&mut self, dx: f32, dy: f32) -> Result<(), Error> {
+        self.sender
+            .emit(&self.device, || self.interface.scroll(dx, dy))
+    }
+
+    /// Scroll by discrete steps, in fractions of 120 for a mouse wheel
+    /// click.
+    pub fn scroll_discrete(&mut self, dx: i32, dy: i32) -> Result<(), Error> {
+        self.sender
+            .emit(&self.device, || self.interface.scroll_discrete(dx, dy))
+    }
+
+    /// End a scroll sequence on the given axes, e.g. when lifting the fingers
+    /// off a touchpad, or cancel it if `cancel` is `true`.
+    pub fn scroll_stop(&mut self, x: bool, y: bool, cancel: bool) -> Result<(), Error> {
+        self.sender.emit(&self.device, || {
+            self.interface
+                .scroll_stop(x.into(), y.into(), cancel.into())
+        })
+    }
+}
+
+/// A keyboard, returned by [`EiSender::keyboard`].
+#[derive(Debug)]
+pub struct Keyboard<'a> {
+    sender: &'a mut EiSender,
+    device: Device,
+    interface: ei::Keyboard,
+}
+
+impl Keyboard<'_> {
+    /// The device of the emitter, with its keymap.
+    pub fn device(&self) -> &Device {
+        &self.device
+    }
+
+    /// Press or release `key`, a key code of `linux/input-event-codes.h`,
+    /// e.g. 30 for `KEY_A`.
+    pub fn key(&mut self, key: u32, state: KeyState) -> Result<(), Error> {
+        let state = match state {
+            KeyState::Pressed => ei::keyboard::KeyState::Press,
+            KeyState::Released => ei::keyboard::KeyState::Released,
+        };
+        self.sender
+            .emit(&self.device, || self.interface.key(key, state))
+    }
+}
+
+/// A touchscreen, returned by [`EiSender::touchscreen`].
+#[derive(Debug)]
+pub struct Touchscreen<'a> {
+    sender: &'a mut EiSender,
+    device: Device,
+    interface: ei::Touchscreen,
+}
+
+impl Touchscreen<'_> {
+    /// The device of the emitter, with the regions of the touchscreen.
+    pub fn device(&self) -> &Device {
+        &self.device
+    }
+
+    /// Put down the touch point `id` at (`x`, `y`), in logical pixels.
+    pub fn down(&mut self, id: u32, x: f32, y: f32) -> Result<(), Error> {
+        self.sender
+            .emit(&self.device, || self.interface.down(id, x, y))
+    }
+
+    /// Move the touch point `id` to (`x`, `y`), in logical pixels.
+    pub fn motion(&mut self, id: u32, x: f32, y: f32) -> Result<(), Error> {
+        self.sender
+            .emit(&self.device, || self.interface.motion(id, x, y))
+    }
+
+    /// Lift the touch point `id`.
+    pub fn up(&mut self, id: u32) -> Result<(), Error> {
+        self.sender.emit(&self.device, || self.interface.up(id))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::{
+        thread::{self, JoinHandle},
+        time::Duration,
+    };
+
+    use reis::{
+        eis,
+        request::{self, EisRequest, EisRequestConverter},
+    };
+
+    use super::*;
+
+    /// A blocking EIS server, with a seat of every capability.
+    pub(crate) struct Server {
+        context: eis::Context,
+        pub(crate) converter: EisRequestConverter,
+        pub(crate) seat: request::Seat,
+    }
+
+    impl Server {
+        /// Run `run` with a server on a thread, and return the socket of the
+        /// client.
+        pub(crate) fn spawn<T: Send + 'static>(
+            run: impl FnOnce(Server) -> T + Send + 'static,
+        ) -> (OwnedFd, JoinHandle<T>) {
+            let path = std::env::temp_dir().join(format!(
+                "ashpd-eis-{}-{}",
+                std::process::id(),
+                rand::Rng::gen::<u32>(&mut rand::thread_rng())
+            ));
+            let listener = eis::Listener::bind(&path).unwrap();
+            let client = UnixStream::connect(&path).unwrap();
+            let handle = thread::spawn(move || {
+                let context = loop {
+                    match listener.accept().unwrap() {
+                        Some(context) => break context,
+                        None => thread::sleep(Duration::from_millis(1)),
+                    }
+                };
+                run(Self::handshake(context))
+            });
+            (client.into(), handle)
+        }
+
+        fn handshake(context: eis::Context) -> Self {
+            let interfaces = interfaces()
+                .keys()
+                .map(|name| (*name, 1))
+                .collect::<HashMap<_, _>>();
+            let mut handshaker = reis::handshake::EisHandshaker::new(&context, &interfaces, 1);
+            let response = 'handshake: loop {
+                Self::read(&context);
+                while let Some(PendingRequestResult::Request(request)) = context.pending_request() {
+                    if let Some(response) = handshaker.handle_request(request).unwrap() {
+                        break 'handshake response;
+                    }
+                }
+            };
+            let mut converter = EisRequestConverter::new(&response.connection, 1);
+            let seat = converter.add_seat(Some("default"), CAPABILITIES);
+            let server = Self {
+                context,
+                converter,
+                seat,
+            };
+            server.flush();
+            server
+        }
+
+        fn read(context: &eis::Context) {
+            while context.read().unwrap() == 0 {
+                thread::sleep(Duration::from_millis(1));
+            }
+        }
+
+        /// Wait for the next request of the client.
+        pub(crate) fn next_request(&mut self) -> EisRequest {
+            loop {
+                if let Some(request) = self.converter.next_request() {
+                    return request;
+                }
+                match self.context.pending_request() {
+                    Some(PendingRequestResult::Request(request)) => {
+                        self.converter.handle_request(request).unwrap()
+                    }
+                    Some(_) => panic!("Invalid request"),
+                    None => Self::read(&self.context),
+                }
+            }
+        }
+
+        /// Add a resumed device with `capabilities` to the seat.
+        pub(crate) fn add_device(
+            &mut self,
+            name: &str,
+            capabilities: &[DeviceCapability],
+        ) -> request::Device {
+            let device = self.converter.add_device(
+                &self.seat,
+                Some(name),
+                eis::device::DeviceType::Virtual,
+                capabilities,
+            );
+            device.device().resumed(self.converter.next_serial());
+            self.flush();
+            device
+        }
+
+        pub(crate) fn flush(&self) {
+            self.context.flush().unwrap();
+        }
+    }
+
+    #[test]
+    fn sender_emulation() {
+        let (fd, server) = Server::spawn(|mut server| {
+            let EisRequest::Bind(bind) = server.next_request() else {
+                panic!("Expected the seat to be bound");
+            };
+            assert_eq!(bind.capabilities, 0x7e);
+            server.add_device("pointer", &[DeviceCapability::Pointer]);
+            server.add_device("keyboard", &[DeviceCapability::Keyboard]);
+
+            let mut requests = Vec::new();
+            loop {
+                match server.next_request() {
+                    EisRequest::Disconnect => return requests,
+                    EisRequest::PointerMotion(motion) => {
+                        requests.push(format!("motion {} {}", motion.dx, motion.dy))
+                    }
+                    EisRequest::KeyboardKey(key) => {
+                        requests.push(format!("key {} {:?}", key.key, key.state))
+                    }
+                    EisRequest::DeviceStartEmulating(start) => {
+                        requests.push(format!("start {}", start.device.name().unwrap()))
+                    }
+                    EisRequest::DeviceStopEmulating(stop) => {
+                        requests.push(format!("stop {}", stop.device.name().unwrap()))
+                    }
+                    EisRequest::Frame(_) => requests.push("frame".to_owned()),
+                    _ => {}
+                }
+            }
+        });
+
+        zbus::block_on(async {
+            let mut sender = EiSender::new(fd).await.unwrap();
+            // The keyboard may not be processed yet.
+            while sender.keyboard().is_none() {
+                sender.dispatch().await.unwrap();
+            }
+            assert_eq!(sender.devices().count(), 2);
+            assert!(sender.touchscreen().is_none());
+
+            let mut pointer = sender.pointer().unwrap();
+            assert_eq!(pointer.device().name(), Some("pointer"));
+            pointer.motion(1.0, 2.0).unwrap();
+            pointer.motion(3.0, 4.0).unwrap();
+            let mut keyboard = sender.keyboard().unwrap();
+            keyboard.key(30, KeyState::Pressed).unwrap();
+            keyboard.key(30, KeyState::Released).unwrap();
+        });
+
+        assert_eq!(
+            server.join().unwrap(),
+            [
+                "start pointer",
+                "motion 1 2",
+                "frame",
+                "motion 3 4",
+                "frame",
+                "start keyboard",
+                "key 30 Press",
+                "frame",
+                "key 30 Released",
+                "frame",
+                "stop pointer",
+                "stop keyboard",
+            ]
+        );
+    }
+}
@@ -1,4 +1,9 @@
-use zbus::DBusError;
+use std::collections::HashMap;
+
+use zbus::{
+    zvariant::{ObjectPath, OwnedObjectPath, OwnedValue},
+    DBusError,
+};
 
 use crate::desktop::{dynamic_launcher::UnexpectedIconError, request::ResponseError};
 
@@ -28,18 +33,110 @@ pub enum PortalError {
     WindowDestroyed(String),
 }
 
+/// Where a portal call or request failed, along with the results the portal
+/// sent.
+#[derive(Debug, Default, PartialEq)]
+pub struct ErrorDetails {
+    interface: Option<String>,
+    method: Option<String>,
+    request_path: Option<OwnedObjectPath>,
+    results: HashMap<String, OwnedValue>,
+}
+
+impl ErrorDetails {
+    pub(crate) fn call(interface: &str, method: &str) -> Self {
+        Self {
+            interface: Some(interface.to_owned()),
+            method: Some(method.to_owned()),
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn request(
+        call: Option<(&str, &str)>,
+        request_path: &ObjectPath<'_>,
+        results: HashMap<String, OwnedValue>,
+    ) -> Self {
+        Self {
+            interface: call.map(|(interface, _)| interface.to_owned()),
+            method: call.map(|(_, method)| method.to_owned()),
+            request_path: Some(request_path.to_owned().into()),
+            results,
+        }
+    }
+
+    /// The portal interface of the failing method, e.g.
+    /// `org.freedesktop.portal.FileChooser`.
+    pub fn interface(&self) -> Option<&str> {
+        self.interface.as_deref()
+    }
+
+    /// The failing method, e.g. `OpenFile`.
+    pub fn method(&self) -> Option<&str> {
+        self.method.as_deref()
+    }
+
+    /// The object path of the [`Request`](crate::desktop::Request) that
+    /// failed.
+    pub fn request_path(&self) -> Option<&ObjectPath<'_>> {
+        self.request_path.as_deref()
+    }
+
+    /// The results the portal sent along with a non-successful response.
+    pub fn results(&self) -> &HashMap<String, OwnedValue> {
+        &self.results
+    }
+}
+
+impl std::fmt::Display for ErrorDetails {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.interface, &self.method) {
+            (Some(interface), Some(method)) => write!(f, " in {interface}.{method}")?,
+            (Some(interface), None) => write!(f, " in {interface}")?,
+            _ => (),
+        }
+        if let Some(path) = &self.request_path {
+            write!(f, " (request {})", path.as_str())?;
+        }
+        Ok(())
+    }
+}
+
+/// A classification of [`Error`], to branch on without matching the
+/// underlying errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The user cancelled the interaction.
+    UserCancelled,
+    /// The application is not allowed to do this, or the user refused it.
+    PermissionDenied,
+    /// The portal doesn't implement the interface, method or property.
+    NotSupported,
+    /// The interface version of the portal is too old.
+    VersionTooOld,
+    /// The portal service is not running, or went away before responding.
+    PortalMissing,
+    /// The portal didn't respond.
+    Timeout,
+    /// Anything else.
+    Other,
+}
+
 #[derive(Debug)]
 #[non_exhaustive]
 /// The error type for ashpd.
 pub enum Error {
     /// The portal request didn't succeed.
-    Response(ResponseError),
+    Response(ResponseError, Box<ErrorDetails>),
     /// Something Failed on the portal request.
-    Portal(PortalError),
+    Portal(PortalError, Box<ErrorDetails>),
     /// A zbus::fdo specific error.
     Zbus(zbus::Error),
     /// A signal returned no response.
     NoResponse,
+    /// The portal didn't respond to a request in time.
+    Timeout,
     /// Failed to parse a string into an enum variant
     ParseError(&'static str),
     /// Input/Output
@@ -47,6 +144,17 @@ pub enum Error {
     /// A pipewire error
     #[cfg(feature = "pipewire")]
     Pipewire(pipewire::Error),
+    /// The pipewire stream ended before sending a frame.
+    #[cfg(feature = "pipewire")]
+    StreamEnded,
+    /// The screen cast session was started without any stream.
+    NoStreams,
+    /// An EI protocol error
+    #[cfg(feature = "eis")]
+    Eis(crate::eis::EisError),
+    /// A KVM wire format error
+    #[cfg(feature = "kvm")]
+    Kvm(crate::kvm::KvmError),
     /// Invalid AppId
     ///
     /// See <https://developer.gnome.org/documentation/tutorials/application-id.html#rules-for-application-ids>
@@ -63,18 +171,81 @@ pub enum Error {
     UnexpectedIcon,
 }
 
+impl Error {
+    /// Classify the error.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Response(ResponseError::Cancelled, _) => ErrorKind::UserCancelled,
+            Self::Response(ResponseError::Other, _) => ErrorKind::Other,
+            Self::Portal(PortalError::Cancelled(_), _) => ErrorKind::UserCancelled,
+            Self::Portal(PortalError::NotAllowed(_), _) => ErrorKind::PermissionDenied,
+            Self::Portal(PortalError::ZBus(e), _) | Self::Zbus(e) => zbus_error_kind(e),
+            Self::RequiresVersion(_, _) => ErrorKind::VersionTooOld,
+            Self::NoResponse => ErrorKind::PortalMissing,
+            Self::Timeout => ErrorKind::Timeout,
+            _ => ErrorKind::Other,
+        }
+    }
+
+    /// Where a portal call or request failed, for [`Error::Response`] and
+    /// [`Error::Portal`].
+    pub fn details(&self) -> Option<&ErrorDetails> {
+        match self {
+            Self::Response(_, details) | Self::Portal(_, details) => Some(details),
+            _ => None,
+        }
+    }
+}
+
+fn zbus_error_kind(error: &zbus::Error) -> ErrorKind {
+    match error {
+        zbus::Error::MethodError(name, _, _) => dbus_error_kind(name.as_str()),
+        zbus::Error::FDO(e) => dbus_error_kind(e.name().as_str()),
+        zbus::Error::InterfaceNotFound | zbus::Error::Unsupported => ErrorKind::NotSupported,
+        _ => ErrorKind::Other,
+    }
+}
+
+fn dbus_error_kind(name: &str) -> ErrorKind {
+    match name {
+        "org.freedesktop.portal.Error.Cancelled" => ErrorKind::UserCancelled,
+        "org.freedesktop.portal.Error.NotAllowed" | "org.freedesktop.DBus.Error.AccessDenied" => {
+            ErrorKind::PermissionDenied
+        }
+        "org.freedesktop.DBus.Error.ServiceUnknown"
+        | "org.freedesktop.DBus.Error.NameHasNoOwner" => ErrorKind::PortalMissing,
+        "org.freedesktop.DBus.Error.UnknownMethod"
+        | "org.freedesktop.DBus.Error.UnknownInterface"
+        | "org.freedesktop.DBus.Error.UnknownObject"
+        | "org.freedesktop.DBus.Error.UnknownProperty"
+        | "org.freedesktop.DBus.Error.NotSupported" => ErrorKind::NotSupported,
+        "org.freedesktop.DBus.Error.NoReply" | "org.freedesktop.DBus.Error.Timeout" => {
+            ErrorKind::Timeout
+        }
+        _ => ErrorKind::Other,
+    }
+}
+
 impl std::error::Error for Error {}
 
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Response(e) => f.write_str(&format!("Portal request didn't succeed: {e}")),
+            Self::Response(e, details) => write!(f, "Portal request didn't succeed: {e}{details}"),
             Self::Zbus(e) => f.write_str(&format!("ZBus Error: {e}")),
-            Self::Portal(e) => f.write_str(&format!("Portal request failed: {e}")),
+            Self::Portal(e, details) => write!(f, "Portal request failed: {e}{details}"),
             Self::NoResponse => f.write_str("Portal error: no response"),
+            Self::Timeout => f.write_str("Portal error: request timed out"),
             Self::IO(e) => f.write_str(&format!("IO: {e}")),
             #[cfg(feature = "pipewire")]
             Self::Pipewire(e) => f.write_str(&format!("Pipewire: {e}")),
+            #[cfg(feature = "pipewire")]
+            Self::StreamEnded => f.write_str("Pipewire: the stream ended without a frame"),
+            Self::NoStreams => f.write_str("Portal error: no streams were shared"),
+            #[cfg(feature = "eis")]
+            Self::Eis(e) => write!(f, "EIS: {e}"),
+            #[cfg(feature = "kvm")]
+            Self::Kvm(e) => write!(f, "KVM: {e}"),
             Self::ParseError(e) => f.write_str(e),
             Self::InvalidAppID => f.write_str("Invalid app id"),
             Self::NulTerminated(u) => write!(f, "Nul byte found in provided data at position {u}"),
@@ -92,13 +263,13 @@ impl std::fmt::Display for Error {
 
 impl From<ResponseError> for Error {
     fn from(e: ResponseError) -> Self {
-        Self::Response(e)
+        Self::Response(e, Default::default())
     }
 }
 
 impl From<PortalError> for Error {
     fn from(e: PortalError) -> Self {
-        Self::Portal(e)
+        Self::Portal(e, Default::default())
     }
 }
 
@@ -109,6 +280,20 @@ impl From<pipewire::Error> for Error {
     }
 }
 
+#[cfg(feature = "eis")]
+impl From<crate::eis::EisError> for Error {
+    fn from(e: crate::eis::EisError) -> Self {
+        Self::Eis(e)
+    }
+}
+
+#[cfg(feature = "kvm")]
+impl From<crate::kvm::KvmError> for Error {
+    fn from(e: crate::kvm::KvmError) -> Self {
+        Self::Kvm(e)
+    }
+}
+
 impl From<zbus::fdo::Error> for Error {
     fn from(e: zbus::fdo::Error) -> Self {
         Self::Zbus(zbus::Error::FDO(Box::new(e)))
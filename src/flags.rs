@@ -0,0 +1,175 @@
+//! GObject flags counterparts of the [`BitFlags`] types of the portals.
+//!
+//! Each type has the same name and flags as the [`bitflags`](enumflags2)
+//! enum it mirrors, e.g. [`SourceType`] for
+//! [`screencast::SourceType`](crate::desktop::screencast::SourceType), and
+//! converts from and into the matching [`BitFlags`]. Unlike the latter, they
+//! are registered with the GType system and can be used in object
+//! properties, `GSettings` bindings or UI files.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use ashpd::{
+//!     desktop::screencast::{Screencast, SourceType},
+//!     enumflags2::BitFlags,
+//!     flags,
+//! };
+//! use glib::prelude::*;
+//!
+//! async fn run() -> ashpd::Result<()> {
+//!     let proxy = Screencast::new().await?;
+//!     let source_types = flags::SourceType::from(proxy.available_source_types().await?);
+//!     let value = source_types.to_value();
+//!     assert_eq!(value.type_(), flags::SourceType::static_type());
+//!
+//!     let source_types = BitFlags::<SourceType>::from(value.get::<flags::SourceType>().unwrap());
+//!     println!("{:#?}", source_types);
+//!     Ok(())
+//! }
+//! ```
+
+// `glib::flags` doesn't carry over the documentation of the enums it turns
+// into flags, the variants are documented by the types they mirror.
+#![allow(missing_docs)]
+
+use enumflags2::BitFlags;
+
+use crate::{
+    desktop::{dynamic_launcher, inhibit, input_capture, remote_desktop, screencast},
+    documents, flatpak,
+};
+
+#[glib::flags(name = "AshpdSourceType")]
+pub enum SourceType {
+    MONITOR = screencast::SourceType::Monitor as u32,
+    WINDOW = screencast::SourceType::Window as u32,
+    VIRTUAL = screencast::SourceType::Virtual as u32,
+}
+
+#[glib::flags(name = "AshpdCursorMode")]
+pub enum CursorMode {
+    HIDDEN = screencast::CursorMode::Hidden as u32,
+    EMBEDDED = screencast::CursorMode::Embedded as u32,
+    METADATA = screencast::CursorMode::Metadata as u32,
+}
+
+#[glib::flags(name = "AshpdDeviceType")]
+pub enum DeviceType {
+    KEYBOARD = remote_desktop::DeviceType::Keyboard as u32,
+    POINTER = remote_desktop::DeviceType::Pointer as u32,
+    TOUCHSCREEN = remote_desktop::DeviceType::Touchscreen as u32,
+}
+
+#[glib::flags(name = "AshpdCapabilities")]
+pub enum Capabilities {
+    KEYBOARD = input_capture::Capabilities::Keyboard as u32,
+    POINTER = input_capture::Capabilities::Pointer as u32,
+    TOUCHSCREEN = input_capture::Capabilities::Touchscreen as u32,
+}
+
+#[glib::flags(name = "AshpdInhibitFlags")]
+pub enum InhibitFlags {
+    LOGOUT = inhibit::InhibitFlags::Logout as u32,
+    USER_SWITCH = inhibit::InhibitFlags::UserSwitch as u32,
+    SUSPEND = inhibit::InhibitFlags::Suspend as u32,
+    IDLE = inhibit::InhibitFlags::Idle as u32,
+}
+
+#[glib::flags(name = "AshpdLauncherType")]
+pub enum LauncherType {
+    APPLICATION = dynamic_launcher::LauncherType::Application as u32,
+    WEB_APPLICATION = dynamic_launcher::LauncherType::WebApplication as u32,
+}
+
+#[glib::flags(name = "AshpdDocumentFlags")]
+pub enum DocumentFlags {
+    REUSE_EXISTING = documents::DocumentFlags::ReuseExisting as u32,
+    PERSISTENT = documents::DocumentFlags::Persistent as u32,
+    AS_NEEDED_BY_APP = documents::DocumentFlags::AsNeededByApp as u32,
+    EXPORT_DIRECTORY = documents::DocumentFlags::ExportDirectory as u32,
+}
+
+#[glib::flags(name = "AshpdSandboxFlags")]
+pub enum SandboxFlags {
+    DISPLAY_ACCESS = flatpak::SandboxFlags::DisplayAccess as u32,
+    SOUND_ACCESS = flatpak::SandboxFlags::SoundAccess as u32,
+    GPU_ACCESS = flatpak::SandboxFlags::GpuAccess as u32,
+    SESSION_BUS_ACCESS = flatpak::SandboxFlags::SessionBusAccess as u32,
+    ACCESSIBILITY_BUS_ACCESS = flatpak::SandboxFlags::AccessibilityBusAccess as u32,
+}
+
+#[glib::flags(name = "AshpdSpawnFlags")]
+pub enum SpawnFlags {
+    CLEAR_ENV = flatpak::SpawnFlags::ClearEnv as u32,
+    LATEST_VERSION = flatpak::SpawnFlags::LatestVersion as u32,
+    SANDBOX = flatpak::SpawnFlags::Sandbox as u32,
+    NO_NETWORK = flatpak::SpawnFlags::NoNetwork as u32,
+    WATCH_BUS = flatpak::SpawnFlags::WatchBus as u32,
+    EXPOSE_PIDS = flatpak::SpawnFlags::ExposePids as u32,
+    NOTIFY_START = flatpak::SpawnFlags::NotifyStart as u32,
+    SHARE_PIDS = flatpak::SpawnFlags::SharePids as u32,
+    EMPTY_APP = flatpak::SpawnFlags::EmptyApp as u32,
+}
+
+#[glib::flags(name = "AshpdSupportsFlags")]
+pub enum SupportsFlags {
+    EXPOSE_PIDS = flatpak::SupportsFlags::ExposePids as u32,
+}
+
+#[glib::flags(name = "AshpdHostCommandFlags")]
+pub enum HostCommandFlags {
+    CLEAR_ENV = flatpak::HostCommandFlags::ClearEnv as u32,
+    WATCH_BUS = flatpak::HostCommandFlags::WatchBus as u32,
+}
+
+macro_rules! impl_bitflags_conversions {
+    ($($flags:ident => $bitflag:ty),* $(,)?) => {
+        $(
+            impl From<BitFlags<$bitflag>> for $flags {
+                fn from(flags: BitFlags<$bitflag>) -> Self {
+                    Self::from_bits_truncate(flags.bits())
+                }
+            }
+
+            impl From<$flags> for BitFlags<$bitflag> {
+                fn from(flags: $flags) -> Self {
+                    BitFlags::from_bits_truncate(flags.bits())
+                }
+            }
+        )*
+    };
+}
+
+impl_bitflags_conversions!(
+    SourceType => screencast::SourceType,
+    CursorMode => screencast::CursorMode,
+    DeviceType => remote_desktop::DeviceType,
+    Capabilities => input_capture::Capabilities,
+    InhibitFlags => inhibit::InhibitFlags,
+    LauncherType => dynamic_launcher::LauncherType,
+    DocumentFlags => documents::DocumentFlags,
+    SandboxFlags => flatpak::SandboxFlags,
+    SpawnFlags => flatpak::SpawnFlags,
+    SupportsFlags => flatpak::SupportsFlags,
+    HostCommandFlags => flatpak::HostCommandFlags,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bitflags_round_trip() {
+        let types = screencast::SourceType::Monitor | screencast::SourceType::Virtual;
+        let flags = SourceType::from(types);
+        assert_eq!(flags, SourceType::MONITOR | SourceType::VIRTUAL);
+        assert_eq!(BitFlags::from(flags), types);
+
+        let flags = SpawnFlags::CLEAR_ENV | SpawnFlags::EMPTY_APP;
+        assert_eq!(
+            BitFlags::<flatpak::SpawnFlags>::from(flags),
+            flatpak::SpawnFlags::ClearEnv | flatpak::SpawnFlags::EmptyApp
+        );
+    }
+}
@@ -252,6 +252,14 @@ impl<'a> Flatpak<'a> {
         Ok(Self(proxy))
     }
 
+    /// Create a new instance of [`Flatpak`] on the given connection.
+    pub async fn with_connection(connection: &zbus::Connection) -> Result<Flatpak<'a>, Error> {
+        let proxy =
+            Proxy::new_flatpak_with_connection(connection, "org.freedesktop.portal.Flatpak")
+                .await?;
+        Ok(Self(proxy))
+    }
+
     /// Creates an update monitor object that will emit signals
     /// when an update for the caller becomes available, and can be used to
     /// install it.
@@ -273,7 +281,7 @@ impl<'a> Flatpak<'a> {
             .call_versioned::<OwnedObjectPath>("CreateUpdateMonitor", &(options), 2)
             .await?;
 
-        UpdateMonitor::new(path.into_inner()).await
+        UpdateMonitor::new(self.0.connection(), path.into_inner()).await
     }
 
     /// Emitted when a process starts by [`spawn()`][`Flatpak::spawn`].
@@ -378,6 +386,14 @@ impl<'a> Flatpak<'a> {
     }
 }
 
+impl crate::resilient::sealed::Sealed for Flatpak<'_> {
+    fn proxy(&self) -> &Proxy<'_> {
+        &self.0
+    }
+}
+
+impl crate::PortalProxy for Flatpak<'_> {}
+
 impl<'a> std::ops::Deref for Flatpak<'a> {
     type Target = zbus::Proxy<'a>;
 
@@ -121,10 +121,16 @@ impl<'a> UpdateMonitor<'a> {
     ///
     /// **Note** A [`UpdateMonitor`] is not supposed to be created
     /// manually.
-    pub(crate) async fn new(path: ObjectPath<'a>) -> Result<UpdateMonitor<'a>, Error> {
-        let proxy =
-            Proxy::new_flatpak_with_path("org.freedesktop.portal.Flatpak.UpdateMonitor", path)
-                .await?;
+    pub(crate) async fn new(
+        connection: &zbus::Connection,
+        path: ObjectPath<'a>,
+    ) -> Result<UpdateMonitor<'a>, Error> {
+        let proxy = Proxy::new_flatpak_with_path(
+            connection,
+            "org.freedesktop.portal.Flatpak.UpdateMonitor",
+            path,
+        )
+        .await?;
         Ok(Self(proxy))
     }
 
@@ -176,6 +182,14 @@ impl<'a> UpdateMonitor<'a> {
     }
 }
 
+impl crate::resilient::sealed::Sealed for UpdateMonitor<'_> {
+    fn proxy(&self) -> &Proxy<'_> {
+        &self.0
+    }
+}
+
+impl crate::PortalProxy for UpdateMonitor<'_> {}
+
 impl<'a> std::ops::Deref for UpdateMonitor<'a> {
     type Target = zbus::Proxy<'a>;
 
@@ -1,32 +1,132 @@
-#[cfg(feature = "async-std")]
+// When both runtimes are enabled, e.g. by two crates depending on ashpd, tokio
+// wins as it is the one zbus uses. Without any, the few file and socket
+// operations run on a separate thread.
+#[cfg(all(feature = "async-std", not(feature = "tokio")))]
 use async_fs::File;
-#[cfg(feature = "async-std")]
-use futures_util::AsyncReadExt;
+#[cfg(all(feature = "async-std", not(feature = "tokio")))]
+use futures_util::{AsyncReadExt, AsyncWriteExt};
 #[cfg(feature = "tokio")]
-use tokio::{fs::File, io::AsyncReadExt};
+use tokio::{
+    fs::File,
+    io::{AsyncReadExt, AsyncWriteExt},
+};
 
 pub(crate) async fn is_flatpak() -> bool {
-    #[cfg(feature = "async-std")]
+    #[cfg(all(feature = "async-std", not(feature = "tokio")))]
     {
         async_fs::metadata("/.flatpak-info").await.is_ok()
     }
-    #[cfg(not(feature = "async-std"))]
+    #[cfg(not(all(feature = "async-std", not(feature = "tokio"))))]
     {
         std::path::PathBuf::from("/.flatpak-info").exists()
     }
 }
 
+pub(crate) async fn sleep(duration: std::time::Duration) {
+    #[cfg(not(feature = "tokio"))]
+    {
+        async_io::Timer::after(duration).await;
+    }
+    #[cfg(feature = "tokio")]
+    {
+        tokio::time::sleep(duration).await;
+    }
+}
+
+/// Run a blocking I/O function on a separate thread.
+pub(crate) async fn unblock<T, E, F>(f: F) -> Result<T, E>
+where
+    T: Send + 'static,
+    E: From<std::io::Error> + Send + 'static,
+    F: FnOnce() -> Result<T, E> + Send + 'static,
+{
+    let (sender, receiver) = futures_channel::oneshot::channel();
+    std::thread::Builder::new().spawn(move || {
+        let _ = sender.send(f());
+    })?;
+    receiver
+        .await
+        .map_err(|_| std::io::Error::other("blocking task panicked"))?
+}
+
+/// Read the content of `fd` until the end, failing with
+/// [`std::io::ErrorKind::InvalidData`] if it exceeds `limit` bytes.
+pub(crate) async fn read_fd(fd: std::os::fd::OwnedFd, limit: usize) -> std::io::Result<Vec<u8>> {
+    let file = std::fs::File::from(fd);
+    // One more byte than the limit tells content exceeding it apart.
+    let limit = limit as u64 + 1;
+    let mut buffer = Vec::new();
+    #[cfg(all(feature = "async-std", not(feature = "tokio")))]
+    File::from(file)
+        .take(limit)
+        .read_to_end(&mut buffer)
+        .await?;
+    #[cfg(feature = "tokio")]
+    File::from_std(file)
+        .take(limit)
+        .read_to_end(&mut buffer)
+        .await?;
+    #[cfg(not(any(feature = "async-std", feature = "tokio")))]
+    let buffer = unblock(move || {
+        use std::io::Read;
+
+        file.take(limit).read_to_end(&mut buffer).map(|_| buffer)
+    })
+    .await?;
+
+    if buffer.len() as u64 == limit {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "content exceeds the size limit",
+        ));
+    }
+    Ok(buffer)
+}
+
+/// Write `data` to `fd` and close it.
+pub(crate) async fn write_fd(fd: std::os::fd::OwnedFd, data: Vec<u8>) -> std::io::Result<()> {
+    let file = std::fs::File::from(fd);
+    #[cfg(all(feature = "async-std", not(feature = "tokio")))]
+    {
+        let mut file = File::from(file);
+        file.write_all(&data).await?;
+        file.flush().await
+    }
+    #[cfg(feature = "tokio")]
+    {
+        let mut file = File::from_std(file);
+        file.write_all(&data).await?;
+        file.flush().await
+    }
+    #[cfg(not(any(feature = "async-std", feature = "tokio")))]
+    {
+        unblock(move || {
+            use std::io::Write;
+
+            let mut file = file;
+            file.write_all(&data)
+        })
+        .await
+    }
+}
+
 pub(crate) async fn is_snap() -> bool {
     let pid = std::process::id();
     let path = format!("/proc/{pid}/cgroup");
-    let mut file = match File::open(path).await {
-        Ok(file) => file,
-        Err(_) => return false,
+    #[cfg(any(feature = "async-std", feature = "tokio"))]
+    let buffer = {
+        let mut file = match File::open(path).await {
+            Ok(file) => file,
+            Err(_) => return false,
+        };
+        let mut buffer = String::new();
+        file.read_to_string(&mut buffer).await.map(|_| buffer)
     };
+    #[cfg(not(any(feature = "async-std", feature = "tokio")))]
+    let buffer = unblock(move || std::fs::read_to_string(path)).await;
 
-    let mut buffer = String::new();
-    match file.read_to_string(&mut buffer).await {
-        Ok(_) => cgroup_v2_is_snap(&buffer),
+    match buffer {
+        Ok(buffer) => cgroup_v2_is_snap(&buffer),
         Err(_) => false,
     }
 }
@@ -79,4 +179,11 @@ mod tests {
 0::/user.slice/user-1000.slice/user@1000.service/apps.slice/apps-org.gnome.Terminal.slice/vte-spawn-228ae109-a869-4533-8988-65ea4c10b492.scope\n";
         assert!(cgroup_v2_is_snap(data));
     }
+
+    #[test]
+    fn sleep_with_any_runtime() {
+        let start = std::time::Instant::now();
+        zbus::block_on(sleep(std::time::Duration::from_millis(20)));
+        assert!(start.elapsed() >= std::time::Duration::from_millis(20));
+    }
 }
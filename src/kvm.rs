@@ -0,0 +1,1160 @@
+//! Share the keyboard and the pointer of a desktop with another one, like a
+//! software KVM switch.
+//!
+//! The capturing desktop runs a [`Forwarder`]: once the pointer crosses one
+//! of the barriers of its [`InputCapture`] session, the captured events are
+//! forwarded over a stream, e.g. a TCP or a Unix socket. The other desktop
+//! runs a [`Replayer`], which replays them through a [`RemoteDesktop`]
+//! session. When the pointer goes back through the edge it entered at, the
+//! replayer hands it back and the forwarder releases the capture where the
+//! pointer left.
+//!
+//! # Wire format
+//!
+//! Both peers start by sending the magic bytes `ASHPDKVM` and the
+//! [`PROTOCOL_VERSION`] as a little endian `u16`, and stop if the other peer
+//! speaks another version. Each [`Message`] is then sent as a frame: its
+//! length as a little endian `u16`, a tag byte and its fields, little endian
+//! as well. Frames with an unknown tag are skipped.
+//!
+//! # Examples
+//!
+//! The capturing side, passing the pointer through the right edge of the
+//! rightmost monitor.
+//!
+//! ```rust,no_run
+//! use ashpd::{
+//!     desktop::input_capture::{BarrierLayout, Capabilities, Edge, EdgeSpec, InputCapture},
+//!     kvm::Forwarder,
+//! };
+//!
+//! async fn run() -> ashpd::Result<()> {
+//!     let input_capture = InputCapture::new().await?;
+//!     let (session, _capabilities) = input_capture
+//!         .create_session(
+//!             &ashpd::WindowIdentifier::default(),
+//!             Capabilities::Keyboard | Capabilities::Pointer,
+//!         )
+//!         .await?;
+//!     let zones = input_capture.zones(&session).await?.response().await?;
+//!     let mut layout = BarrierLayout::new(&zones).edges(EdgeSpec::Outermost(Edge::Right));
+//!     input_capture
+//!         .set_barrier_layout(&session, &mut layout)
+//!         .await?;
+//!     input_capture.enable(&session).await?;
+//!
+//!     let stream =
+//!         async_io::Async::<std::net::TcpStream>::connect(([192, 168, 1, 2], 24800)).await?;
+//!     Forwarder::new(&input_capture, &session)
+//!         .layout(&layout)
+//!         .run(stream)
+//!         .await
+//! }
+//! ```
+
+use std::{
+    collections::BTreeSet,
+    fmt::{self, Debug},
+    io,
+};
+
+use futures_util::{
+    future::{select, Either},
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadHalf, WriteHalf},
+    pin_mut, stream, Stream, StreamExt,
+};
+
+use crate::{
+    desktop::{
+        input_capture::{BarrierLayout, Edge, InputCapture, InputEvent, PlacedBarrier},
+        remote_desktop::{Axis, KeyState, RemoteDesktop},
+        Session,
+    },
+    Error,
+};
+
+/// The version of the wire format.
+pub const PROTOCOL_VERSION: u16 = 1;
+
+const MAGIC: &[u8; 8] = b"ASHPDKVM";
+
+/// Scroll wheel clicks are reported in fractions of 120.
+const WHEEL_CLICK: i32 = 120;
+
+/// An error of the KVM wire format.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum KvmError {
+    /// The peer doesn't speak the wire format.
+    Handshake,
+    /// The peer speaks another version of the wire format.
+    Version(u16),
+    /// A message is truncated or has invalid fields.
+    Malformed,
+}
+
+impl fmt::Display for KvmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Handshake => f.write_str("The peer doesn't speak the KVM wire format"),
+            Self::Version(version) => write!(
+                f,
+                "The peer speaks version {version} of the wire format, instead of {PROTOCOL_VERSION}"
+            ),
+            Self::Malformed => f.write_str("Malformed message"),
+        }
+    }
+}
+
+impl std::error::Error for KvmError {}
+
+/// A message of the wire format.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum Message {
+    /// The pointer left the capturing desktop through a barrier on `edge`,
+    /// at `fraction` of its length from its top or left end.
+    Enter {
+        /// The edge of the barrier.
+        edge: Edge,
+        /// The position along the barrier, from 0 to 1.
+        fraction: f32,
+    },
+    /// The pointer went back through the edge it entered the replaying
+    /// desktop at, at `fraction` of its length from its top or left end.
+    Leave {
+        /// The position along the edge, from 0 to 1.
+        fraction: f32,
+    },
+    /// A captured input event.
+    Input(InputEvent),
+}
+
+impl Message {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        fn state(state: KeyState) -> u8 {
+            match state {
+                KeyState::Pressed => 1,
+                KeyState::Released => 0,
+            }
+        }
+        fn floats(buf: &mut Vec<u8>, tag: u8, values: &[f32]) {
+            buf.push(tag);
+            for value in values {
+                buf.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+
+        match *self {
+            Self::Enter { edge, fraction } => {
+                buf.extend_from_slice(&[0, edge_tag(edge)]);
+                buf.extend_from_slice(&fraction.to_le_bytes());
+            }
+            Self::Leave { fraction } => floats(buf, 1, &[fraction]),
+            Self::Input(InputEvent::PointerMotion { dx, dy }) => floats(buf, 2, &[dx, dy]),
+            Self::Input(InputEvent::PointerMotionAbsolute { x, y }) => floats(buf, 3, &[x, y]),
+            Self::Input(InputEvent::Button { button, state: s }) => {
+                buf.push(4);
+                buf.extend_from_slice(&button.to_le_bytes());
+                buf.push(state(s));
+            }
+            Self::Input(InputEvent::Scroll { dx, dy }) => floats(buf, 5, &[dx, dy]),
+            Self::Input(InputEvent::ScrollDiscrete { dx, dy }) => {
+                buf.push(6);
+                buf.extend_from_slice(&dx.to_le_bytes());
+                buf.extend_from_slice(&dy.to_le_bytes());
+            }
+            Self::Input(InputEvent::ScrollStop { x, y }) => {
+                buf.extend_from_slice(&[7, x as u8, y as u8])
+            }
+            Self::Input(InputEvent::ScrollCancel { x, y }) => {
+                buf.extend_from_slice(&[8, x as u8, y as u8])
+            }
+            Self::Input(InputEvent::Key { key, state: s }) => {
+                buf.push(9);
+                buf.extend_from_slice(&key.to_le_bytes());
+                buf.push(state(s));
+            }
+            Self::Input(InputEvent::TouchDown { id, x, y }) => {
+                buf.push(10);
+                buf.extend_from_slice(&id.to_le_bytes());
+                buf.extend_from_slice(&x.to_le_bytes());
+                buf.extend_from_slice(&y.to_le_bytes());
+            }
+            Self::Input(InputEvent::TouchMotion { id, x, y }) => {
+                buf.push(11);
+                buf.extend_from_slice(&id.to_le_bytes());
+                buf.extend_from_slice(&x.to_le_bytes());
+                buf.extend_from_slice(&y.to_le_bytes());
+            }
+            Self::Input(InputEvent::TouchUp { id }) => {
+                buf.push(12);
+                buf.extend_from_slice(&id.to_le_bytes());
+            }
+        }
+    }
+
+    /// Decode the payload of a frame, `None` for an unknown tag.
+    fn decode(payload: &[u8]) -> Result<Option<Self>, KvmError> {
+        let mut payload = Payload(payload);
+        let message = match payload.u8()? {
+            0 => Self::Enter {
+                edge: match payload.u8()? {
+                    0 => Edge::Left,
+                    1 => Edge::Right,
+                    2 => Edge::Top,
+                    3 => Edge::Bottom,
+                    _ => return Err(KvmError::Malformed),
+                },
+                fraction: payload.f32()?,
+            },
+            1 => Self::Leave {
+                fraction: payload.f32()?,
+            },
+            2 => Self::Input(InputEvent::PointerMotion {
+                dx: payload.f32()?,
+                dy: payload.f32()?,
+            }),
+            3 => Self::Input(InputEvent::PointerMotionAbsolute {
+                x: payload.f32()?,
+                y: payload.f32()?,
+            }),
+            4 => Self::Input(InputEvent::Button {
+                button: payload.u32()?,
+                state: payload.key_state()?,
+            }),
+            5 => Self::Input(InputEvent::Scroll {
+                dx: payload.f32()?,
+                dy: payload.f32()?,
+            }),
+            6 => Self::Input(InputEvent::ScrollDiscrete {
+                dx: payload.u32()? as i32,
+                dy: payload.u32()? as i32,
+            }),
+            7 => Self::Input(InputEvent::ScrollStop {
+                x: payload.bool()?,
+                y: payload.bool()?,
+            }),
+            8 => Self::Input(InputEvent::ScrollCancel {
+                x: payload.bool()?,
+                y: payload.bool()?,
+            }),
+            9 => Self::Input(InputEvent::Key {
+                key: payload.u32()?,
+                state: payload.key_state()?,
+            }),
+            10 => Self::Input(InputEvent::TouchDown {
+                id: payload.u32()?,
+                x: payload.f32()?,
+                y: payload.f32()?,
+            }),
+            11 => Self::Input(InputEvent::TouchMotion {
+                id: payload.u32()?,
+                x: payload.f32()?,
+                y: payload.f32()?,
+            }),
+            12 => Self::Input(InputEvent::TouchUp { id: payload.u32()? }),
+            _ => return Ok(None),
+        };
+        Ok(Some(message))
+    }
+}
+
+fn edge_tag(edge: Edge) -> u8 {
+    match edge {
+        Edge::Left => 0,
+        Edge::Right => 1,
+        Edge::Top => 2,
+        Edge::Bottom => 3,
+    }
+}
+
+/// The fields of a frame left to decode.
+struct Payload<'a>(&'a [u8]);
+
+impl Payload<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], KvmError> {
+        if self.0.len() < N {
+            return Err(KvmError::Malformed);
+        }
+        let (bytes, rest) = self.0.split_at(N);
+        self.0 = rest;
+        Ok(bytes.try_into().unwrap())
+    }
+
+    fn u8(&mut self) -> Result<u8, KvmError> {
+        self.take::<1>().map(|[byte]| byte)
+    }
+
+    fn u32(&mut self) -> Result<u32, KvmError> {
+        self.take().map(u32::from_le_bytes)
+    }
+
+    fn f32(&mut self) -> Result<f32, KvmError> {
+        self.take().map(f32::from_le_bytes)
+    }
+
+    fn bool(&mut self) -> Result<bool, KvmError> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(KvmError::Malformed),
+        }
+    }
+
+    fn key_state(&mut self) -> Result<KeyState, KvmError> {
+        match self.u8()? {
+            0 => Ok(KeyState::Released),
+            1 => Ok(KeyState::Pressed),
+            _ => Err(KvmError::Malformed),
+        }
+    }
+}
+
+async fn read_message<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Option<Message>, Error> {
+    loop {
+        let mut len = [0; 2];
+        // The stream may only end between two frames.
+        if reader.read(&mut len[..1]).await? == 0 {
+            return Ok(None);
+        }
+        reader.read_exact(&mut len[1..]).await?;
+        let mut payload = vec![0; u16::from_le_bytes(len) as usize];
+        reader.read_exact(&mut payload).await?;
+        if let Some(message) = Message::decode(&payload)? {
+            return Ok(Some(message));
+        }
+    }
+}
+
+async fn write_message<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    message: &Message,
+) -> Result<(), Error> {
+    let mut frame = vec![0; 2];
+    message.encode(&mut frame);
+    let len = (frame.len() - 2) as u16;
+    frame[..2].copy_from_slice(&len.to_le_bytes());
+    writer.write_all(&frame).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+/// A stream speaking the wire format, past the handshake.
+pub struct Connection<S> {
+    reader: ReadHalf<S>,
+    writer: WriteHalf<S>,
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> Connection<S> {
+    /// Exchange the version of the wire format with the peer on `stream`.
+    pub async fn new(mut stream: S) -> Result<Self, Error> {
+        let mut hello = MAGIC.to_vec();
+        hello.extend_from_slice(&PROTOCOL_VERSION.to_le_bytes());
+        stream.write_all(&hello).await?;
+        stream.flush().await?;
+
+        let mut peer = [0; MAGIC.len() + 2];
+        stream
+            .read_exact(&mut peer)
+            .await
+            .map_err(|err| match err.kind() {
+                io::ErrorKind::UnexpectedEof => KvmError::Handshake.into(),
+                _ => Error::from(err),
+            })?;
+        if &peer[..MAGIC.len()] != MAGIC {
+            return Err(KvmError::Handshake.into());
+        }
+        let version = u16::from_le_bytes([peer[MAGIC.len()], peer[MAGIC.len() + 1]]);
+        if version != PROTOCOL_VERSION {
+            return Err(KvmError::Version(version).into());
+        }
+
+        let (reader, writer) = stream.split();
+        Ok(Self { reader, writer })
+    }
+
+    /// Send `message`.
+    pub async fn send(&mut self, message: &Message) -> Result<(), Error> {
+        write_message(&mut self.writer, message).await
+    }
+
+    /// Wait for the next message, `None` once the peer closed the stream.
+    pub async fn receive(&mut self) -> Result<Option<Message>, Error> {
+        read_message(&mut self.reader).await
+    }
+}
+
+impl<S> Debug for Connection<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Connection").finish_non_exhaustive()
+    }
+}
+
+/// The messages read from `reader`, until the peer closes the stream.
+fn messages<R: AsyncRead + Unpin>(reader: R) -> impl Stream<Item = Result<Message, Error>> {
+    stream::unfold(Some(reader), |reader| async move {
+        let mut reader = reader?;
+        match read_message(&mut reader).await {
+            Ok(Some(message)) => Some((Ok(message), Some(reader))),
+            Ok(None) => None,
+            Err(err) => Some((Err(err), None)),
+        }
+    })
+}
+
+/// The position of `cursor` along `barrier`, from 0 to 1.
+fn barrier_fraction(barrier: &PlacedBarrier, (x, y): (f32, f32)) -> f32 {
+    let (x1, y1, x2, y2) = barrier.position();
+    let fraction = match barrier.edge() {
+        Edge::Left | Edge::Right => (y - y1 as f32) / (y2 - y1 + 1) as f32,
+        Edge::Top | Edge::Bottom => (x - x1 as f32) / (x2 - x1 + 1) as f32,
+    };
+    fraction.clamp(0.0, 1.0)
+}
+
+/// The position at `fraction` along `barrier`, a pixel away from it on the
+/// side of its region.
+fn barrier_point(barrier: &PlacedBarrier, fraction: f32) -> (f64, f64) {
+    let (x1, y1, x2, y2) = barrier.position();
+    let along = |start: i32, end: i32| {
+        let offset = (f64::from(fraction) * f64::from(end - start + 1)).floor();
+        (f64::from(start) + offset).min(f64::from(end))
+    };
+    match barrier.edge() {
+        Edge::Left => (f64::from(x1 + 1), along(y1, y2)),
+        Edge::Right => (f64::from(x1 - 1), along(y1, y2)),
+        Edge::Top => (along(x1, x2), f64::from(y1 + 1)),
+        Edge::Bottom => (along(x1, x2), f64::from(y1 - 1)),
+    }
+}
+
+/// The capture in progress.
+#[derive(Debug)]
+struct Activation {
+    id: u32,
+    barrier: Option<PlacedBarrier>,
+    cursor_position: Option<(f32, f32)>,
+}
+
+/// Forward the input captured by an [`InputCapture`] session to a
+/// [`Replayer`].
+///
+/// Each activation of the session is announced with a [`Message::Enter`] on
+/// the edge of the barrier that triggered it, if the barrier is part of the
+/// [`Forwarder::layout`]. The capture is released once the replayer hands
+/// the pointer back, a pixel away from the barrier, and the events of that
+/// activation still queued up are dropped.
+pub struct Forwarder<'a> {
+    proxy: &'a InputCapture<'a>,
+    session: &'a Session<'a, InputCapture<'a>>,
+    layout: Option<&'a BarrierLayout>,
+}
+
+impl Debug for Forwarder<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Forwarder")
+            .field("layout", &self.layout)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> Forwarder<'a> {
+    /// Create a forwarder for the enabled `session`.
+    pub fn new(proxy: &'a InputCapture<'a>, session: &'a Session<'a, InputCapture<'a>>) -> Self {
+        Self {
+            proxy,
+            session,
+            layout: None,
+        }
+    }
+
+    /// Sets the layout the barriers of the session were set up with, to hand
+    /// the pointer off at the matching position.
+    #[must_use]
+    pub fn layout(mut self, layout: &'a BarrierLayout) -> Self {
+        self.layout = Some(layout);
+        self
+    }
+
+    /// Forward the captured events over `stream`, until either the stream or
+    /// the captured events end.
+    pub async fn run<S: AsyncRead + AsyncWrite + Unpin>(self, stream: S) -> Result<(), Error> {
+        let Connection { reader, mut writer } = Connection::new(stream).await?;
+        let events = self.proxy.events(self.session).await?;
+        let messages = messages(reader);
+        pin_mut!(events, messages);
+
+        let mut activation: Option<Activation> = None;
+        // The events of a released activation still queued up are dropped.
+        let mut released = None;
+        loop {
+            match select(events.next(), messages.next()).await {
+                Either::Left((Some(event), _)) => {
+                    let event = event?;
+                    if released == Some(event.activation_id()) {
+                        continue;
+                    }
+                    if activation.as_ref().map(|activation| activation.id)
+                        != Some(event.activation_id())
+                    {
+                        let barrier = self
+                            .layout
+                            .zip(event.barrier_id())
+                            .and_then(|(layout, id)| layout.barrier(id))
+                            .copied();
+                        if let (Some(barrier), Some(cursor)) = (barrier, event.cursor_position()) {
+                            let enter = Message::Enter {
+                                edge: barrier.edge(),
+                                fraction: barrier_fraction(&barrier, cursor),
+                            };
+                            write_message(&mut writer, &enter).await?;
+                        }
+                        activation = Some(Activation {
+                            id: event.activation_id(),
+                            barrier,
+                            cursor_position: event.cursor_position(),
+                        });
+                    }
+                    write_message(&mut writer, &Message::Input(event.event())).await?;
+                }
+                Either::Right((Some(message), _)) => {
+                    if let Message::Leave { fraction } = message? {
+                        if let Some(activation) = activation.take() {
+                            released = Some(activation.id);
+                            self.release(activation, fraction).await?;
+                        }
+                    }
+                }
+                Either::Left((None, _)) | Either::Right((None, _)) => return Ok(()),
+            }
+        }
+    }
+
+    async fn release(&self, activation: Activation, fraction: f32) -> Result<(), Error> {
+        let cursor_position = match (activation.barrier, activation.cursor_position) {
+            (Some(barrier), _) => barrier_point(&barrier, fraction),
+            (None, Some((x, y))) => (f64::from(x), f64::from(y)),
+            // Left to the user to release, e.g. with the compositor shortcut.
+            (None, None) => return Ok(()),
+        };
+        self.proxy
+            .release(self.session, activation.id, cursor_position)
+            .await
+    }
+}
+
+/// The replaying state.
+#[derive(Debug)]
+struct Replay {
+    /// Whether the input is replayed, until the pointer is handed back.
+    active: bool,
+    /// The edge the pointer entered at.
+    entry: Option<Edge>,
+    /// The tracked pointer position.
+    position: Option<(f64, f64)>,
+    keys: BTreeSet<u32>,
+    buttons: BTreeSet<u32>,
+    /// The discrete scrolling not replayed yet, less than a wheel click on
+    /// each axis.
+    scroll: (i32, i32),
+}
+
+/// The position at `fraction` along `edge` of a desktop of `size`.
+fn edge_point(edge: Edge, fraction: f32, (width, height): (u32, u32)) -> (f64, f64) {
+    let (width, height) = (f64::from(width), f64::from(height));
+    let fraction = f64::from(fraction.clamp(0.0, 1.0));
+    match edge {
+        Edge::Left => (0.0, (fraction * height).min(height - 1.0)),
+        Edge::Right => (width - 1.0, (fraction * height).min(height - 1.0)),
+        Edge::Top => ((fraction * width).min(width - 1.0), 0.0),
+        Edge::Bottom => ((fraction * width).min(width - 1.0), height - 1.0),
+    }
+}
+
+/// The pointer at `(x, y)` moved by `(dx, dy)` on the desktop of `size`. It
+/// can only leave the desktop through the `entry` edge.
+fn moved(
+    entry: Option<Edge>,
+    (x, y): (f64, f64),
+    (dx, dy): (f64, f64),
+    (width, height): (u32, u32),
+) -> (f64, f64) {
+    let (x, y) = (x + dx, y + dy);
+    let (max_x, max_y) = (f64::from(width) - 1.0, f64::from(height) - 1.0);
+    match entry {
+        Some(Edge::Left) => (x.min(max_x), y.clamp(0.0, max_y)),
+        Some(Edge::Right) => (x.max(0.0), y.clamp(0.0, max_y)),
+        Some(Edge::Top) => (x.clamp(0.0, max_x), y.min(max_y)),
+        Some(Edge::Bottom) => (x.clamp(0.0, max_x), y.max(0.0)),
+        None => (x.clamp(0.0, max_x), y.clamp(0.0, max_y)),
+    }
+}
+
+/// The fraction along `entry` at which `(x, y)` left the desktop of `size`
+/// through it.
+fn left_through(entry: Edge, (x, y): (f64, f64), (width, height): (u32, u32)) -> Option<f32> {
+    let (width, height) = (f64::from(width), f64::from(height));
+    let fraction = match entry {
+        Edge::Left if x < 0.0 => y / height,
+        Edge::Right if x >= width => y / height,
+        Edge::Top if y < 0.0 => x / width,
+        Edge::Bottom if y >= height => x / width,
+        _ => return None,
+    };
+    Some(fraction as f32)
+}
+
+/// Replay the input forwarded by a [`Forwarder`] with a [`RemoteDesktop`]
+/// session.
+///
+/// The pointer enters at the edge opposite to the one it left the capturing
+/// desktop at. Set the [`Replayer::size`] of the desktop to hand it back once
+/// it goes back through that edge. The keys and buttons still pressed are
+/// released when handing the pointer back and when the stream ends.
+///
+/// **Note** absolute motion and touch events are only replayed if a screen
+/// cast [`Replayer::stream`] is set.
+#[derive(Debug)]
+pub struct Replayer<'a> {
+    proxy: &'a RemoteDesktop<'a>,
+    session: &'a Session<'a, RemoteDesktop<'a>>,
+    size: Option<(u32, u32)>,
+    stream: Option<u32>,
+}
+
+impl<'a> Replayer<'a> {
+    /// Create a replayer for the started `session`.
+    pub fn new(proxy: &'a RemoteDesktop<'a>, session: &'a Session<'a, RemoteDesktop<'a>>) -> Self {
+        Self {
+            proxy,
+            session,
+            size: None,
+            stream: None,
+        }
+    }
+
+    /// Sets the logical size of the desktop, to track the pointer and hand
+    /// it back.
+    #[must_use]
+    pub fn size(mut self, size: (u32, u32)) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Sets the PipeWire node id of the screen cast stream of the session
+    /// covering the desktop, to warp the pointer to where it enters and to
+    /// replay absolute motion and touch events.
+    #[must_use]
+    pub fn stream(mut self, node_id: u32) -> Self {
+        self.stream = Some(node_id);
+        self
+    }
+
+    /// Replay the events received over `stream`, until it ends.
+    pub async fn run<S: AsyncRead + AsyncWrite + Unpin>(self, stream: S) -> Result<(), Error> {
+        let mut connection = Connection::new(stream).await?;
+        let mut replay = Replay {
+            active: true,
+            entry: None,
+            position: None,
+            keys: BTreeSet::new(),
+            buttons: BTreeSet::new(),
+            scroll: (0, 0),
+        };
+        let result = loop {
+            let message = match connection.receive().await {
+                Ok(Some(message)) => message,
+                Ok(None) => break Ok(()),
+                Err(err) => break Err(err),
+            };
+            if let Err(err) = self.handle(&mut replay, &mut connection, message).await {
+                break Err(err);
+            }
+        };
+        let released = self.release_held(&mut replay).await;
+        result.and(released)
+    }
+
+    async fn handle<S: AsyncRead + AsyncWrite + Unpin>(
+        &self,
+        replay: &mut Replay,
+        connection: &mut Connection<S>,
+        message: Message,
+    ) -> Result<(), Error> {
+        match message {
+            Message::Enter { edge, fraction } => {
+                self.release_held(replay).await?;
+                let entry = edge.opposite();
+                replay.active = true;
+                replay.entry = Some(entry);
+                replay.scroll = (0, 0);
+                replay.position = self.size.map(|size| edge_point(entry, fraction, size));
+                if let (Some(stream), Some((x, y))) = (self.stream, replay.position) {
+                    self.proxy
+                        .notify_pointer_motion_absolute(self.session, stream, x, y)
+                        .await?;
+                }
+            }
+            Message::Input(event) if replay.active => {
+                self.replay(replay, event).await?;
+                let left = replay
+                    .entry
+                    .zip(replay.position)
+                    .zip(self.size)
+                    .and_then(|((entry, position), size)| left_through(entry, position, size));
+                if let Some(fraction) = left {
+                    connection.send(&Message::Leave { fraction }).await?;
+                    replay.active = false;
+                    replay.position = None;
+                    self.release_held(replay).await?;
+                }
+            }
+            Message::Input(_) | Message::Leave { .. } => {}
+        }
+        Ok(())
+    }
+
+    async fn replay(&self, replay: &mut Replay, event: InputEvent) -> Result<(), Error> {
+        let session = self.session;
+        match event {
+            InputEvent::PointerMotion { dx, dy } => {
+                if let (Some(position), Some(size)) = (replay.position, self.size) {
+                    let delta = (f64::from(dx), f64::from(dy));
+                    replay.position = Some(moved(replay.entry, position, delta, size));
+                }
+                self.proxy
+                    .notify_pointer_motion(session, f64::from(dx), f64::from(dy))
+                    .await
+            }
+            InputEvent::PointerMotionAbsolute { x, y } => match self.stream {
+                Some(stream) => {
+                    replay.position = Some((f64::from(x), f64::from(y)));
+                    self.proxy
+                        .notify_pointer_motion_absolute(session, stream, f64::from(x), f64::from(y))
+                        .await
+                }
+                None => Ok(()),
+            },
+            InputEvent::Button { button, state } => {
+                match state {
+                    KeyState::Pressed => replay.buttons.insert(button),
+                    KeyState::Released => replay.buttons.remove(&button),
+                };
+                self.proxy
+                    .notify_pointer_button(session, button as i32, state)
+                    .await
+            }
+            InputEvent::Scroll { dx, dy } => {
+                self.proxy
+                    .notify_pointer_axis(session, f64::from(dx), f64::from(dy), false)
+                    .await
+            }
+            InputEvent::ScrollDiscrete { dx, dy } => {
+                for (axis, remainder, delta) in [
+                    (Axis::Horizontal, &mut replay.scroll.0, dx),
+                    (Axis::Vertical, &mut replay.scroll.1, dy),
+                ] {
+                    // High-resolution wheels scroll by fractions of a click.
+                    *remainder += delta;
+                    let steps = *remainder / WHEEL_CLICK;
+                    *remainder %= WHEEL_CLICK;
+                    if steps != 0 {
+                        self.proxy
+                            .notify_pointer_axis_discrete(session, axis, steps)
+                            .await?;
+                    }
+                }
+                Ok(())
+            }
+            InputEvent::ScrollStop { .. } | InputEvent::ScrollCancel { .. } => {
+                self.proxy
+                    .notify_pointer_axis(session, 0.0, 0.0, true)
+                    .await
+            }
+            InputEvent::Key { key, state } => {
+                match state {
+                    KeyState::Pressed => replay.keys.insert(key),
+                    KeyState::Released => replay.keys.remove(&key),
+                };
+                self.proxy
+                    .notify_keyboard_keycode(session, key as i32, state)
+                    .await
+            }
+            InputEvent::TouchDown { id, x, y } => match self.stream {
+                Some(stream) => {
+                    self.proxy
+                        .notify_touch_down(session, stream, id, f64::from(x), f64::from(y))
+                        .await
+                }
+                None => Ok(()),
+            },
+            InputEvent::TouchMotion { id, x, y } => match self.stream {
+                Some(stream) => {
+                    self.proxy
+                        .notify_touch_motion(session, stream, id, f64::from(x), f64::from(y))
+                        .await
+                }
+                None => Ok(()),
+            },
+            InputEvent::TouchUp { id } => match self.stream {
+                Some(_) => self.proxy.notify_touch_up(session, id).await,
+                None => Ok(()),
+            },
+        }
+    }
+
+    /// Release the keys and buttons still pressed, all of them even if
+    /// releasing one fails.
+    async fn release_held(&self, replay: &mut Replay) -> Result<(), Error> {
+        let mut result = Ok(());
+        for key in std::mem::take(&mut replay.keys) {
+            let released = self
+                .proxy
+                .notify_keyboard_keycode(self.session, key as i32, KeyState::Released)
+                .await;
+            result = result.and(released);
+        }
+        for button in std::mem::take(&mut replay.buttons) {
+            let released = self
+                .proxy
+                .notify_pointer_button(self.session, button as i32, KeyState::Released)
+                .await;
+            result = result.and(released);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::os::unix::net::UnixStream;
+
+    use async_io::Async;
+
+    use super::*;
+    use crate::desktop::input_capture::{EdgeSpec, Zones};
+
+    fn pair() -> (Async<UnixStream>, Async<UnixStream>) {
+        let (a, b) = UnixStream::pair().unwrap();
+        (Async::new(a).unwrap(), Async::new(b).unwrap())
+    }
+
+    #[test]
+    fn wire_format() {
+        let messages = [
+            Message::Enter {
+                edge: Edge::Bottom,
+                fraction: 0.25,
+            },
+            Message::Leave { fraction: 1.0 },
+            Message::Input(InputEvent::PointerMotion { dx: 1.5, dy: -2.0 }),
+            Message::Input(InputEvent::Button {
+                button: 272,
+                state: KeyState::Pressed,
+            }),
+            Message::Input(InputEvent::ScrollDiscrete { dx: 0, dy: -120 }),
+            Message::Input(InputEvent::ScrollStop { x: false, y: true }),
+            Message::Input(InputEvent::Key {
+                key: 30,
+                state: KeyState::Released,
+            }),
+            Message::Input(InputEvent::TouchMotion {
+                id: 2,
+                x: 10.0,
+                y: 20.0,
+            }),
+        ];
+
+        zbus::block_on(async {
+            let (a, mut b) = pair();
+            let (a, _) = futures_util::join!(Connection::new(a), async {
+                let mut hello = [0; 10];
+                b.read_exact(&mut hello).await.unwrap();
+                assert_eq!(&hello, b"ASHPDKVM\x01\x00");
+                b.write_all(&hello).await.unwrap();
+                // A message of a later version, and a truncated one.
+                b.write_all(&[2, 0, 0xff, 0]).await.unwrap();
+                b.write_all(&[2, 0, 9, 0]).await.unwrap();
+            });
+            let mut a = a.unwrap();
+            assert!(matches!(
+                a.receive().await,
+                Err(Error::Kvm(KvmError::Malformed))
+            ));
+
+            let (a, b) = pair();
+            let (a, b) = futures_util::join!(Connection::new(a), Connection::new(b));
+            let (mut a, mut b) = (a.unwrap(), b.unwrap());
+            for message in &messages {
+                a.send(message).await.unwrap();
+            }
+            drop(a);
+            for message in &messages {
+                assert_eq!(b.receive().await.unwrap().as_ref(), Some(message));
+            }
+            assert!(b.receive().await.unwrap().is_none());
+
+            let (a, mut b) = pair();
+            let (a, _) = futures_util::join!(Connection::new(a), async {
+                b.write_all(b"ASHPDKVM\x02\x00").await.unwrap();
+            });
+            assert!(matches!(a, Err(Error::Kvm(KvmError::Version(2)))));
+        });
+    }
+
+    #[test]
+    fn hand_off_positions() {
+        let zones = Zones::new(&[(1920, 1080, 0, 0)], 1);
+        let layout = BarrierLayout::new(&zones).edges(EdgeSpec::Outermost(Edge::Right));
+        let barrier = layout.barrier(1).unwrap();
+
+        let fraction = barrier_fraction(barrier, (1919.0, 270.0));
+        assert_eq!(fraction, 0.25);
+        assert_eq!(barrier_point(barrier, fraction), (1919.0, 270.0));
+        assert_eq!(barrier_point(barrier, 1.0), (1919.0, 1079.0));
+
+        assert_eq!(edge_point(Edge::Left, 0.25, (1280, 720)), (0.0, 180.0));
+        assert_eq!(
+            left_through(Edge::Left, (-1.0, 180.0), (1280, 720)),
+            Some(0.25)
+        );
+        assert_eq!(left_through(Edge::Left, (0.0, 180.0), (1280, 720)), None);
+
+        let left = Some(Edge::Left);
+        assert_eq!(
+            moved(left, (10.0, 180.0), (2000.0, -200.0), (1280, 720)),
+            (1279.0, 0.0)
+        );
+        assert_eq!(
+            moved(left, (10.0, 180.0), (-20.0, 0.0), (1280, 720)),
+            (-10.0, 180.0)
+        );
+        assert_eq!(
+            moved(Some(Edge::Bottom), (10.0, 710.0), (0.0, 20.0), (1280, 720)),
+            (10.0, 730.0)
+        );
+        assert_eq!(
+            moved(Some(Edge::Bottom), (10.0, 10.0), (0.0, -20.0), (1280, 720)),
+            (10.0, 0.0)
+        );
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod portal_tests {
+    use std::{collections::HashMap, os::unix::net::UnixStream, sync::mpsc, time::Duration};
+
+    use async_io::Async;
+    use reis::{eis, request::EisRequest};
+    use zbus::zvariant::{self, ObjectPath, OwnedValue, Value};
+
+    use super::*;
+    use crate::{eis::tests::Server, helpers::sleep, testing::FakePortal};
+
+    const INTERFACE: &str = "org.freedesktop.portal.RemoteDesktop";
+    const INPUT_CAPTURE: &str = "org.freedesktop.portal.InputCapture";
+
+    type Options = HashMap<String, OwnedValue>;
+
+    #[test]
+    fn replay_hand_off() {
+        zbus::block_on(async {
+            let portal = FakePortal::new().await.unwrap();
+            let proxy = RemoteDesktop::with_connection(portal.connection())
+                .await
+                .unwrap();
+            let session = portal.session().await.unwrap();
+            portal.reply(INTERFACE, "NotifyPointerMotionAbsolute", ());
+            portal.reply(INTERFACE, "NotifyPointerAxisDiscrete", ());
+            for _ in 0..2 {
+                portal.reply(INTERFACE, "NotifyKeyboardKeycode", ());
+                portal.reply(INTERFACE, "NotifyPointerMotion", ());
+            }
+
+            let (a, b) = UnixStream::pair().unwrap();
+            let replayer = Replayer::new(&proxy, &session)
+                .size((1280, 720))
+                .stream(42)
+                .run(Async::new(a).unwrap());
+            let forwarder = async {
+                let mut connection = Connection::new(Async::new(b).unwrap()).await.unwrap();
+                for message in [
+                    Message::Enter {
+                        edge: Edge::Right,
+                        fraction: 0.25,
+                    },
+                    Message::Input(InputEvent::Key {
+                        key: 30,
+                        state: KeyState::Pressed,
+                    }),
+                    Message::Input(InputEvent::PointerMotion { dx: 10.0, dy: 0.0 }),
+                    Message::Input(InputEvent::ScrollDiscrete { dx: 0, dy: 60 }),
+                    Message::Input(InputEvent::ScrollDiscrete { dx: 0, dy: 90 }),
+                    Message::Input(InputEvent::PointerMotion { dx: -20.0, dy: 0.0 }),
+                    // Dropped until the pointer enters again.
+                    Message::Input(InputEvent::PointerMotion { dx: 5.0, dy: 0.0 }),
+                ] {
+                    connection.send(&message).await.unwrap();
+                }
+                assert_eq!(
+                    connection.receive().await.unwrap(),
+                    Some(Message::Leave { fraction: 0.25 })
+                );
+            };
+            let (result, ()) = futures_util::join!(replayer, forwarder);
+            result.unwrap();
+
+            let calls = portal.calls(INTERFACE, "NotifyPointerMotionAbsolute");
+            let (_, _, stream, x, y) = calls[0]
+                .body()
+                .deserialize::<(ObjectPath<'_>, Options, u32, f64, f64)>()
+                .unwrap();
+            assert_eq!((stream, x, y), (42, 0.0, 180.0));
+
+            let motions = portal
+                .calls(INTERFACE, "NotifyPointerMotion")
+                .iter()
+                .map(|call| {
+                    let (_, _, dx, _) = call
+                        .body()
+                        .deserialize::<(ObjectPath<'_>, Options, f64, f64)>()
+                        .unwrap();
+                    dx
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(motions, [10.0, -20.0]);
+
+            // A single click out of the fractions.
+            let scrolls = portal.calls(INTERFACE, "NotifyPointerAxisDiscrete");
+            let (_, _, axis, steps) = scrolls[0]
+                .body()
+                .deserialize::<(ObjectPath<'_>, Options, u32, i32)>()
+                .unwrap();
+            assert_eq!((scrolls.len(), axis, steps), (1, Axis::Vertical as u32, 1));
+
+            let keys = portal
+                .calls(INTERFACE, "NotifyKeyboardKeycode")
+                .iter()
+                .map(|call| {
+                    call.body()
+                        .deserialize::<(ObjectPath<'_>, Options, i32, u32)>()
+                        .unwrap()
+                        .3
+                })
+                .collect::<Vec<_>>();
+            // Released when handing the pointer back.
+            assert_eq!(keys, [1, 0]);
+        });
+    }
+
+    #[test]
+    fn forward_until_released() {
+        let (ready_tx, ready_rx) = mpsc::channel();
+        let (fd, server) = Server::spawn(move |mut server| {
+            let EisRequest::Bind(_) = server.next_request() else {
+                panic!("Expected the seat to be bound");
+            };
+            ready_rx.recv().unwrap();
+
+            let serial = server.converter.next_serial();
+            let device = server.seat.eis_seat().device(1);
+            device.device_type(eis::device::DeviceType::Virtual);
+            let pointer = device.interface::<eis::Pointer>(1);
+            device.done();
+            device.resumed(serial);
+            device.start_emulating(serial, 7);
+            pointer.motion_relative(1.0, 0.0);
+            device.frame(serial, 1000);
+            server.flush();
+            ready_rx.recv().unwrap();
+
+            // Queued up before the capture got released.
+            pointer.motion_relative(5.0, 0.0);
+            device.frame(serial, 2000);
+            device.stop_emulating(serial);
+            device.start_emulating(serial, 8);
+            pointer.motion_relative(2.0, 0.0);
+            device.frame(serial, 3000);
+            server.flush();
+            server
+        });
+
+        zbus::block_on(async {
+            let portal = FakePortal::new().await.unwrap();
+            let proxy = InputCapture::with_connection(portal.connection())
+                .await
+                .unwrap();
+            let session = portal.session().await.unwrap();
+            portal.reply(INPUT_CAPTURE, "ConnectToEIS", zvariant::OwnedFd::from(fd));
+            portal.reply(INPUT_CAPTURE, "Enable", ());
+            portal.reply(INPUT_CAPTURE, "Release", ());
+
+            let (a, b) = UnixStream::pair().unwrap();
+            let forwarder = Forwarder::new(&proxy, &session).run(Async::new(a).unwrap());
+            let replayer = async {
+                let mut connection = Connection::new(Async::new(b).unwrap()).await.unwrap();
+                while portal.calls(INPUT_CAPTURE, "ConnectToEIS").is_empty() {
+                    sleep(Duration::from_millis(1)).await;
+                }
+                let activated = HashMap::from([
+                    ("activation_id", Value::from(7u32)),
+                    ("cursor_position", Value::from((10.0, 20.0))),
+                    ("barrier_id", Value::from(3u32)),
+                ]);
+                portal
+                    .emit_signal(
+                        INPUT_CAPTURE,
+                        "Activated",
+                        &(
+                            ObjectPath::try_from(FakePortal::SESSION_PATH).unwrap(),
+                            activated,
+                        ),
+                    )
+                    .await
+                    .unwrap();
+                // The signal is received once the reply to a later call is.
+                proxy.enable(&session).await.unwrap();
+                ready_tx.send(()).unwrap();
+
+                assert_eq!(
+                    connection.receive().await.unwrap(),
+                    Some(Message::Input(InputEvent::PointerMotion {
+                        dx: 1.0,
+                        dy: 0.0
+                    }))
+                );
+                connection
+                    .send(&Message::Leave { fraction: 0.5 })
+                    .await
+                    .unwrap();
+                while portal.calls(INPUT_CAPTURE, "Release").is_empty() {
+                    sleep(Duration::from_millis(1)).await;
+                }
+                ready_tx.send(()).unwrap();
+
+                assert_eq!(
+                    connection.receive().await.unwrap(),
+                    Some(Message::Input(InputEvent::PointerMotion {
+                        dx: 2.0,
+                        dy: 0.0
+                    }))
+                );
+            };
+            let (result, ()) = futures_util::join!(forwarder, replayer);
+            result.unwrap();
+
+            let (_, options) = portal.calls(INPUT_CAPTURE, "Release")[0]
+                .body()
+                .deserialize::<(ObjectPath<'_>, Options)>()
+                .unwrap();
+            assert_eq!(u32::try_from(&options["activation_id"]).unwrap(), 7);
+            assert_eq!(
+                <(f64, f64)>::try_from(options["cursor_position"].try_clone().unwrap()).unwrap(),
+                (10.0, 20.0)
+            );
+        });
+
+        server.join().unwrap();
+    }
+}
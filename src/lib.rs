@@ -6,36 +6,65 @@
     html_favicon_url = "https://raw.githubusercontent.com/bilelmoussaoui/ashpd/master/ashpd-demo/data/icons/com.belmoussaoui.ashpd.demo-symbolic.svg"
 )]
 #![doc = include_str!("../README.md")]
-#[cfg(all(all(feature = "tokio", feature = "async-std"), not(doc)))]
-compile_error!("You can't enable both async-std & tokio features at once");
+#[cfg(not(any(feature = "tokio", feature = "async-io")))]
+compile_error!("Either the tokio, async-std or async-io feature must be enabled");
 
 /// Alias for a [`Result`] with the error type `ashpd::Error`.
 pub type Result<T> = std::result::Result<T, Error>;
 
 static IS_SANDBOXED: OnceLock<bool> = OnceLock::new();
 
+#[cfg(feature = "backend")]
+#[cfg_attr(docsrs, doc(cfg(feature = "backend")))]
+/// Implement the `org.freedesktop.impl.portal.*` interfaces of a portal
+/// backend.
+pub mod backend;
+pub mod blocking;
 /// Interact with the user's desktop such as taking a screenshot, setting a
 /// background or querying the user's location.
 pub mod desktop;
 /// Interact with the documents store or transfer files across apps.
 pub mod documents;
+#[cfg(feature = "eis")]
+#[cfg_attr(docsrs, doc(cfg(feature = "eis")))]
+/// Emulate input devices over the EIS socket of a remote desktop session.
+pub mod eis;
 mod error;
+#[cfg(feature = "glib")]
+#[cfg_attr(docsrs, doc(cfg(feature = "glib")))]
+pub mod flags;
+#[cfg(feature = "kvm")]
+#[cfg_attr(docsrs, doc(cfg(feature = "kvm")))]
+pub mod kvm;
 mod window_identifier;
 
 pub use self::window_identifier::WindowIdentifier;
+#[cfg(feature = "backend")]
+pub use self::window_identifier::WindowIdentifierType;
 mod app_id;
 pub use self::app_id::AppID;
 mod file_path;
 pub use self::file_path::FilePath;
 
+mod portals;
+pub use self::portals::{PortalInterface, Portals};
 mod proxy;
+mod resilient;
+pub use self::resilient::{PortalProxy, SignalEvent};
 
 /// Spawn commands outside the sandbox or monitor if the running application has
 /// received an update & install it.
 pub mod flatpak;
 mod helpers;
+#[cfg(feature = "testing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+/// Run a fake portal service to test applications without a desktop session.
+pub mod testing;
 use std::sync::OnceLock;
 
+#[cfg(feature = "backend")]
+#[cfg_attr(docsrs, doc(cfg(feature = "backend")))]
+pub use async_trait::async_trait;
 pub use enumflags2;
 pub use url;
 pub use zbus::{self, zvariant};
@@ -59,4 +88,4 @@ pub async fn is_sandboxed() -> bool {
     new_value
 }
 
-pub use self::error::{Error, PortalError};
+pub use self::error::{Error, ErrorDetails, ErrorKind, PortalError};
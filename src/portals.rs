@@ -0,0 +1,297 @@
+use std::collections::HashMap;
+
+use enumflags2::{BitFlag, BitFlags};
+use futures_util::future::join_all;
+use zbus::{
+    fdo::{self, IntrospectableProxy, PropertiesProxy},
+    names::InterfaceName,
+    proxy::MethodFlags,
+    zvariant::OwnedValue,
+    CacheProperties,
+};
+use zbus_xml::Node;
+
+use crate::{
+    desktop::{
+        dynamic_launcher::LauncherType,
+        input_capture::Capabilities,
+        remote_desktop::DeviceType,
+        screencast::{CursorMode, SourceType},
+    },
+    proxy::{
+        Proxy, DESKTOP_DESTINATION, DESKTOP_PATH, DOCUMENTS_DESTINATION, DOCUMENTS_PATH,
+        FLATPAK_DESTINATION, FLATPAK_PATH,
+    },
+    Error,
+};
+
+/// A portal interface found while probing, along with its properties.
+#[derive(Debug)]
+pub struct PortalInterface {
+    name: String,
+    properties: HashMap<String, OwnedValue>,
+}
+
+impl PortalInterface {
+    /// The interface name, e.g. `org.freedesktop.portal.ScreenCast`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The version of the interface.
+    pub fn version(&self) -> u32 {
+        self.property::<u32>("version").unwrap_or(1)
+    }
+
+    /// The value of a property of the interface, if it exists and is of type
+    /// `T`.
+    pub fn property<T>(&self, name: &str) -> Option<T>
+    where
+        T: TryFrom<OwnedValue>,
+    {
+        let value = self.properties.get(name)?.try_clone().ok()?;
+        T::try_from(value).ok()
+    }
+
+    fn flags<F>(&self, name: &str) -> Option<BitFlags<F>>
+    where
+        F: BitFlag<Numeric = u32>,
+    {
+        self.property::<u32>(name).map(BitFlags::from_bits_truncate)
+    }
+}
+
+/// The portal interfaces available on the session, with their versions and
+/// capabilities.
+///
+/// Useful for hiding features the portal frontend or backends don't support
+/// before trying to use them.
+///
+/// ```rust,no_run
+/// use ashpd::{desktop::screencast::SourceType, Portals};
+///
+/// async fn run() -> ashpd::Result<()> {
+///     let portals = Portals::probe().await?;
+///     if portals.version("org.freedesktop.portal.ScreenCast") >= Some(4) {
+///         println!("Screen cast restore tokens are supported");
+///     }
+///     if portals
+///         .available_source_types()
+///         .is_some_and(|types| types.contains(SourceType::Window))
+///     {
+///         println!("Windows can be recorded");
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Portals(HashMap<String, PortalInterface>);
+
+impl Portals {
+    /// Probe the `org.freedesktop.portal.Desktop`,
+    /// `org.freedesktop.portal.Documents` and `org.freedesktop.portal.Flatpak`
+    /// services on the session bus.
+    ///
+    /// The interfaces are listed by introspecting the portal objects. Only the
+    /// desktop portal is started if it isn't running yet, the other services
+    /// are reported as missing instead of being activated.
+    pub async fn probe() -> Result<Self, Error> {
+        let connection = Proxy::session().await?;
+        Self::probe_with_connection(&connection).await
+    }
+
+    /// Probe the portal services on the given connection.
+    pub async fn probe_with_connection(connection: &zbus::Connection) -> Result<Self, Error> {
+        let services = [
+            (DESKTOP_DESTINATION, DESKTOP_PATH, true),
+            (DOCUMENTS_DESTINATION, DOCUMENTS_PATH, false),
+            (FLATPAK_DESTINATION, FLATPAK_PATH, false),
+        ];
+        let mut probes = Vec::new();
+        for (destination, path, activate) in services {
+            let Some(names) = introspect(connection, destination, path, activate).await? else {
+                continue;
+            };
+            let proxy = PropertiesProxy::builder(connection)
+                .destination(destination)?
+                .path(path)?
+                .cache_properties(CacheProperties::No)
+                .build()
+                .await?;
+            for name in names {
+                let proxy = proxy.clone();
+                probes.push(async move {
+                    let properties = proxy.get_all(Some(name.as_ref()).into()).await;
+                    (name.to_string(), properties)
+                });
+            }
+        }
+
+        let mut found = HashMap::new();
+        for (name, properties) in join_all(probes).await {
+            match properties {
+                Ok(properties) => {
+                    found.insert(name.clone(), PortalInterface { name, properties });
+                }
+                // Failing to reach the bus is not the same as the interface not
+                // being available.
+                Err(fdo::Error::ZBus(err)) => return Err(err.into()),
+                Err(_err) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!("Portal interface {} is not available: {}", name, _err);
+                }
+            }
+        }
+        Ok(Self(found))
+    }
+
+    /// The interface named `name`, if it is available.
+    pub fn interface(&self, name: &str) -> Option<&PortalInterface> {
+        self.0.get(name)
+    }
+
+    /// Whether the interface named `name` is available.
+    pub fn is_available(&self, name: &str) -> bool {
+        self.0.contains_key(name)
+    }
+
+    /// The version of the interface named `name`, if it is available.
+    pub fn version(&self, name: &str) -> Option<u32> {
+        self.interface(name).map(PortalInterface::version)
+    }
+
+    /// All the available interfaces.
+    pub fn interfaces(&self) -> impl Iterator<Item = &PortalInterface> {
+        self.0.values()
+    }
+
+    /// The source types supported by the ScreenCast portal.
+    ///
+    /// See also [`Screencast::available_source_types`](crate::desktop::screencast::Screencast::available_source_types).
+    #[doc(alias = "AvailableSourceTypes")]
+    pub fn available_source_types(&self) -> Option<BitFlags<SourceType>> {
+        self.interface("org.freedesktop.portal.ScreenCast")?
+            .flags("AvailableSourceTypes")
+    }
+
+    /// The cursor modes supported by the ScreenCast portal.
+    ///
+    /// See also [`Screencast::available_cursor_modes`](crate::desktop::screencast::Screencast::available_cursor_modes).
+    #[doc(alias = "AvailableCursorModes")]
+    pub fn available_cursor_modes(&self) -> Option<BitFlags<CursorMode>> {
+        self.interface("org.freedesktop.portal.ScreenCast")?
+            .flags("AvailableCursorModes")
+    }
+
+    /// The device types supported by the RemoteDesktop portal.
+    ///
+    /// See also [`RemoteDesktop::available_device_types`](crate::desktop::remote_desktop::RemoteDesktop::available_device_types).
+    #[doc(alias = "AvailableDeviceTypes")]
+    pub fn available_device_types(&self) -> Option<BitFlags<DeviceType>> {
+        self.interface("org.freedesktop.portal.RemoteDesktop")?
+            .flags("AvailableDeviceTypes")
+    }
+
+    /// The capabilities supported by the InputCapture portal.
+    ///
+    /// See also [`InputCapture::supported_capabilities`](crate::desktop::input_capture::InputCapture::supported_capabilities).
+    #[doc(alias = "SupportedCapabilities")]
+    pub fn supported_capabilities(&self) -> Option<BitFlags<Capabilities>> {
+        self.interface("org.freedesktop.portal.InputCapture")?
+            .flags("SupportedCapabilities")
+    }
+
+    /// The launcher types supported by the DynamicLauncher portal.
+    ///
+    /// See also [`DynamicLauncherProxy::supported_launcher_types`](crate::desktop::dynamic_launcher::DynamicLauncherProxy::supported_launcher_types).
+    #[doc(alias = "SupportedLauncherTypes")]
+    pub fn supported_launcher_types(&self) -> Option<BitFlags<LauncherType>> {
+        self.interface("org.freedesktop.portal.DynamicLauncher")?
+            .flags("SupportedLauncherTypes")
+    }
+}
+
+/// The portal interfaces listed by the introspection data of the object at
+/// `path`, or `None` if the service isn't running and `activate` is `false`.
+async fn introspect(
+    connection: &zbus::Connection,
+    destination: &str,
+    path: &str,
+    activate: bool,
+) -> Result<Option<Vec<InterfaceName<'static>>>, Error> {
+    let proxy = IntrospectableProxy::builder(connection)
+        .destination(destination)?
+        .path(path)?
+        .cache_properties(CacheProperties::No)
+        .build()
+        .await?;
+    let flags = if activate {
+        BitFlags::empty()
+    } else {
+        MethodFlags::NoAutoStart.into()
+    };
+    let xml = match proxy
+        .inner()
+        .call_with_flags::<_, _, String>("Introspect", flags, &())
+        .await
+    {
+        Ok(xml) => xml.unwrap_or_default(),
+        Err(zbus::Error::MethodError(name, _, _))
+            if matches!(
+                name.as_str(),
+                "org.freedesktop.DBus.Error.ServiceUnknown"
+                    | "org.freedesktop.DBus.Error.NameHasNoOwner"
+            ) =>
+        {
+            #[cfg(feature = "tracing")]
+            tracing::debug!("Portal service {} is not running", destination);
+            return Ok(None);
+        }
+        Err(err) => return Err(err.into()),
+    };
+    let node = Node::try_from(xml.as_str()).map_err(|err| zbus::Error::Failure(err.to_string()))?;
+    Ok(Some(
+        node.interfaces()
+            .iter()
+            .map(|interface| interface.name().into_owned())
+            .filter(|name| name.starts_with("org.freedesktop.portal."))
+            .collect(),
+    ))
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::testing::FakePortal;
+
+    #[test]
+    fn probe() {
+        zbus::block_on(async {
+            let portal = FakePortal::new().await.unwrap();
+            portal.set_version("org.freedesktop.portal.ScreenCast", 5);
+            portal.set_property(
+                "org.freedesktop.portal.ScreenCast",
+                "AvailableSourceTypes",
+                (SourceType::Monitor | SourceType::Window).bits(),
+            );
+            portal.set_version("org.freedesktop.portal.Documents", 4);
+
+            let portals = Portals::probe_with_connection(portal.connection())
+                .await
+                .unwrap();
+            assert_eq!(portals.interfaces().count(), 2);
+            assert_eq!(
+                portals.version("org.freedesktop.portal.ScreenCast"),
+                Some(5)
+            );
+            assert_eq!(portals.version("org.freedesktop.portal.Documents"), Some(4));
+            assert!(!portals.is_available("org.freedesktop.portal.Camera"));
+            assert_eq!(
+                portals.available_source_types(),
+                Some(SourceType::Monitor | SourceType::Window)
+            );
+            assert_eq!(portals.available_cursor_modes(), None);
+            assert_eq!(portals.available_device_types(), None);
+        });
+    }
+}
@@ -1,4 +1,12 @@
-use std::{fmt::Debug, future::ready, ops::Deref, sync::OnceLock};
+use std::{
+    fmt::Debug,
+    future::ready,
+    ops::Deref,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        OnceLock,
+    },
+};
 
 use futures_util::{Stream, StreamExt};
 use serde::{Deserialize, Serialize};
@@ -8,7 +16,7 @@ use zbus::Message;
 
 use crate::{
     desktop::{HandleToken, Request},
-    Error, PortalError,
+    Error, ErrorDetails, PortalError,
 };
 
 pub(crate) const DESKTOP_DESTINATION: &str = "org.freedesktop.portal.Desktop";
@@ -28,11 +36,13 @@ static SESSION: OnceLock<zbus::Connection> = OnceLock::new();
 #[derive(Debug)]
 pub struct Proxy<'a> {
     inner: zbus::Proxy<'a>,
-    version: u32,
+    version: AtomicU32,
 }
 
 impl<'a> Proxy<'a> {
-    pub(crate) async fn connection() -> zbus::Result<zbus::Connection> {
+    /// The shared session bus connection, used by the proxies that were not
+    /// given a connection explicitly.
+    pub(crate) async fn session() -> zbus::Result<zbus::Connection> {
         if let Some(cnx) = SESSION.get() {
             Ok(cnx.clone())
         } else {
@@ -42,18 +52,22 @@ impl<'a> Proxy<'a> {
         }
     }
 
-    pub async fn unique_name(
+    pub fn unique_name(
+        connection: &zbus::Connection,
         prefix: &str,
         handle_token: &HandleToken,
     ) -> Result<ObjectPath<'static>, Error> {
-        let connection = Self::connection().await?;
-        let unique_name = connection.unique_name().unwrap();
+        // Peer-to-peer connections have no unique name to derive the path from.
+        let unique_name = connection
+            .unique_name()
+            .ok_or(Error::Zbus(zbus::Error::MissingField))?;
         let unique_identifier = unique_name.trim_start_matches(':').replace('.', "_");
         ObjectPath::try_from(format!("{prefix}/{unique_identifier}/{handle_token}"))
             .map_err(From::from)
     }
 
     pub async fn new<P>(
+        connection: &zbus::Connection,
         interface: &'a str,
         path: P,
         destination: &'a str,
@@ -62,8 +76,7 @@ impl<'a> Proxy<'a> {
         P: TryInto<ObjectPath<'a>>,
         P::Error: Into<zbus::Error>,
     {
-        let connection = Self::connection().await?;
-        let inner: zbus::Proxy = zbus::ProxyBuilder::new(&connection)
+        let inner: zbus::Proxy = zbus::ProxyBuilder::new(connection)
             .interface(interface)?
             .path(path)?
             .destination(destination)?
@@ -71,39 +84,83 @@ impl<'a> Proxy<'a> {
             .await?;
         let version = inner.get_property::<u32>("version").await.unwrap_or(1);
 
-        Ok(Self { inner, version })
+        Ok(Self {
+            inner,
+            version: AtomicU32::new(version),
+        })
     }
 
-    pub async fn new_desktop_with_path<P>(interface: &'a str, path: P) -> Result<Proxy<'a>, Error>
+    pub async fn new_desktop_with_path<P>(
+        connection: &zbus::Connection,
+        interface: &'a str,
+        path: P,
+    ) -> Result<Proxy<'a>, Error>
     where
         P: TryInto<ObjectPath<'a>>,
         P::Error: Into<zbus::Error>,
     {
-        Self::new(interface, path, DESKTOP_DESTINATION).await
+        Self::new(connection, interface, path, DESKTOP_DESTINATION).await
     }
 
     pub async fn new_desktop(interface: &'a str) -> Result<Proxy<'a>, Error> {
-        Self::new(interface, DESKTOP_PATH, DESKTOP_DESTINATION).await
+        let connection = Self::session().await?;
+        Self::new_desktop_with_connection(&connection, interface).await
+    }
+
+    pub async fn new_desktop_with_connection(
+        connection: &zbus::Connection,
+        interface: &'a str,
+    ) -> Result<Proxy<'a>, Error> {
+        Self::new(connection, interface, DESKTOP_PATH, DESKTOP_DESTINATION).await
     }
 
     pub async fn new_documents(interface: &'a str) -> Result<Proxy<'a>, Error> {
-        Self::new(interface, DOCUMENTS_PATH, DOCUMENTS_DESTINATION).await
+        let connection = Self::session().await?;
+        Self::new_documents_with_connection(&connection, interface).await
+    }
+
+    pub async fn new_documents_with_connection(
+        connection: &zbus::Connection,
+        interface: &'a str,
+    ) -> Result<Proxy<'a>, Error> {
+        Self::new(connection, interface, DOCUMENTS_PATH, DOCUMENTS_DESTINATION).await
     }
 
     pub async fn new_flatpak(interface: &'a str) -> Result<Proxy<'a>, Error> {
-        Self::new(interface, FLATPAK_PATH, FLATPAK_DESTINATION).await
+        let connection = Self::session().await?;
+        Self::new_flatpak_with_connection(&connection, interface).await
     }
 
-    pub async fn new_flatpak_with_path<P>(interface: &'a str, path: P) -> Result<Proxy<'a>, Error>
+    pub async fn new_flatpak_with_connection(
+        connection: &zbus::Connection,
+        interface: &'a str,
+    ) -> Result<Proxy<'a>, Error> {
+        Self::new(connection, interface, FLATPAK_PATH, FLATPAK_DESTINATION).await
+    }
+
+    pub async fn new_flatpak_with_path<P>(
+        connection: &zbus::Connection,
+        interface: &'a str,
+        path: P,
+    ) -> Result<Proxy<'a>, Error>
     where
         P: TryInto<ObjectPath<'a>>,
         P::Error: Into<zbus::Error>,
     {
-        Self::new(interface, path, FLATPAK_DESTINATION).await
+        Self::new(connection, interface, path, FLATPAK_DESTINATION).await
     }
 
     pub async fn new_flatpak_development(interface: &'a str) -> Result<Proxy<'a>, Error> {
+        let connection = Self::session().await?;
+        Self::new_flatpak_development_with_connection(&connection, interface).await
+    }
+
+    pub async fn new_flatpak_development_with_connection(
+        connection: &zbus::Connection,
+        interface: &'a str,
+    ) -> Result<Proxy<'a>, Error> {
         Self::new(
+            connection,
             interface,
             FLATPAK_DEVELOPMENT_PATH,
             FLATPAK_DEVELOPMENT_DESTINATION,
@@ -120,12 +177,12 @@ impl<'a> Proxy<'a> {
     where
         T: for<'de> Deserialize<'de> + Type + Debug,
     {
-        let mut request = Request::from_unique_name(handle_token).await?;
-        futures_util::try_join!(request.prepare_response(), async {
-            self.call_method(method_name, &body)
-                .await
-                .map_err(From::from)
-        })?;
+        let request = Request::from_unique_name(self.inner.connection(), handle_token)
+            .await?
+            .called_by(self.interface(), method_name);
+        self.call_method(method_name, &body)
+            .await
+            .map_err(|e| self.call_error(method_name, e))?;
         Ok(request)
     }
 
@@ -140,7 +197,27 @@ impl<'a> Proxy<'a> {
 
     /// Returns the version of the interface
     pub fn version(&self) -> u32 {
-        self.version
+        self.version.load(Ordering::Relaxed)
+    }
+
+    /// Read the version of the interface again, e.g. after the service got
+    /// restarted, bypassing the properties cache.
+    pub(crate) async fn refresh_version(&self) -> Result<u32, Error> {
+        let properties = zbus::fdo::PropertiesProxy::builder(self.inner.connection())
+            .destination(self.inner.destination().to_owned())?
+            .path(self.inner.path().to_owned())?
+            .cache_properties(zbus::CacheProperties::No)
+            .build()
+            .await?;
+        let interface = self.inner.interface().to_owned();
+        let version = properties
+            .get(interface.as_ref(), "version")
+            .await
+            .ok()
+            .and_then(|version| u32::try_from(version).ok())
+            .unwrap_or(1);
+        self.version.store(version, Ordering::Relaxed);
+        Ok(version)
     }
 
     pub(crate) async fn call<R>(
@@ -159,12 +236,19 @@ impl<'a> Proxy<'a> {
         let msg = self
             .call_method(method_name, &body)
             .await
-            .map_err::<PortalError, _>(From::from)?;
+            .map_err(|e| self.call_error(method_name, e))?;
         let reply = msg.body().deserialize::<R>()?;
 
         Ok(reply)
     }
 
+    pub(crate) fn call_error(&self, method_name: &str, error: zbus::Error) -> Error {
+        Error::Portal(
+            PortalError::from(error),
+            Box::new(ErrorDetails::call(self.interface(), method_name)),
+        )
+    }
+
     pub(crate) async fn call_versioned<R>(
         &self,
         method_name: &'static str,
@@ -278,3 +362,28 @@ impl<'a> Deref for Proxy<'a> {
         &self.inner
     }
 }
+
+#[cfg(all(test, feature = "testing"))]
+mod portal_tests {
+    use super::*;
+
+    #[test]
+    fn unique_name_of_p2p_connection() {
+        zbus::block_on(async {
+            let (client, _server) = crate::testing::p2p_pair(false).await.unwrap();
+
+            let handle_token = HandleToken::try_from("ashpd_test").unwrap();
+            assert!(matches!(
+                Proxy::unique_name(
+                    &client,
+                    "/org/freedesktop/portal/desktop/request",
+                    &handle_token
+                ),
+                Err(Error::Zbus(zbus::Error::MissingField))
+            ));
+            assert!(Request::<()>::from_unique_name(&client, &handle_token)
+                .await
+                .is_err());
+        });
+    }
+}
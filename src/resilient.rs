@@ -0,0 +1,191 @@
+use std::future::Future;
+
+use futures_util::{
+    future::{select, Either},
+    stream, Stream, StreamExt,
+};
+
+use crate::Error;
+
+pub(crate) mod sealed {
+    use crate::proxy::Proxy;
+
+    pub trait Sealed {
+        fn proxy(&self) -> &Proxy<'_>;
+    }
+}
+
+/// An item of the streams returned by
+/// [`PortalProxy::receive_resilient`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignalEvent<T> {
+    /// A signal emitted by the portal.
+    Signal(T),
+    /// The portal service was restarted.
+    ///
+    /// The signal stream got subscribed again and the version of the interface
+    /// re-read. The sessions and requests created before the restart are gone
+    /// and have to be set up again.
+    PortalRestarted,
+}
+
+/// A portal proxy with signals.
+///
+/// The signal streams, like
+/// [`Settings::receive_setting_changed`](crate::desktop::settings::Settings::receive_setting_changed),
+/// stop receiving anything once the portal service is restarted or crashes.
+/// [`receive_resilient`](PortalProxy::receive_resilient) wraps them to survive
+/// such restarts.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use ashpd::{desktop::settings::Settings, PortalProxy, SignalEvent};
+/// use futures_util::StreamExt;
+///
+/// async fn run() -> ashpd::Result<()> {
+///     let settings = Settings::new().await?;
+///     let mut changes = settings
+///         .receive_resilient(Settings::receive_setting_changed)
+///         .await?;
+///     while let Some(event) = changes.next().await {
+///         match event {
+///             SignalEvent::Signal(setting) => println!("{:#?}", setting),
+///             SignalEvent::PortalRestarted => println!("The portal was restarted"),
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+pub trait PortalProxy: sealed::Sealed + Sync + Sized {
+    /// Subscribe to a signal stream with `subscribe` and do it again every
+    /// time the portal service gets a new owner on the bus, emitting
+    /// [`SignalEvent::PortalRestarted`].
+    ///
+    /// The stream ends once `subscribe` fails to re-subscribe.
+    fn receive_resilient<'p, F, Fut, S>(
+        &'p self,
+        mut subscribe: F,
+    ) -> impl Future<
+        Output = Result<impl Stream<Item = SignalEvent<S::Item>> + Send + Unpin + 'p, Error>,
+    > + Send
+           + 'p
+    where
+        F: FnMut(&'p Self) -> Fut + Send + 'p,
+        Fut: Future<Output = Result<S, Error>> + Send + 'p,
+        S: Stream + Send + 'p,
+        S::Item: Send,
+    {
+        async move {
+            let proxy = self.proxy();
+            let owners = proxy.receive_owner_changed().await?;
+            let signals = Box::pin(subscribe(self).await?);
+
+            Ok(stream::unfold(
+                (subscribe, signals, owners),
+                move |(mut subscribe, mut signals, mut owners)| async move {
+                    loop {
+                        let event = match select(signals.next(), owners.next()).await {
+                            Either::Left((signal, _)) => Either::Left(signal),
+                            Either::Right((owner, _)) => Either::Right(owner),
+                        };
+                        match event {
+                            Either::Left(Some(signal)) => {
+                                return Some((
+                                    SignalEvent::Signal(signal),
+                                    (subscribe, signals, owners),
+                                ))
+                            }
+                            // The service went away, wait for it to come back.
+                            Either::Right(Some(None)) => continue,
+                            Either::Right(Some(Some(_owner))) => {
+                                #[cfg(feature = "tracing")]
+                                tracing::info!(
+                                    "{} restarted as {}, re-subscribing to {}",
+                                    proxy.destination(),
+                                    _owner,
+                                    proxy.interface()
+                                );
+                                let _ = proxy.refresh_version().await;
+                                match subscribe(self).await {
+                                    Ok(stream) => signals = Box::pin(stream),
+                                    Err(_err) => {
+                                        #[cfg(feature = "tracing")]
+                                        tracing::warn!("Failed to re-subscribe: {}", _err);
+                                        return None;
+                                    }
+                                }
+                                return Some((
+                                    SignalEvent::PortalRestarted,
+                                    (subscribe, signals, owners),
+                                ));
+                            }
+                            Either::Left(None) | Either::Right(None) => return None,
+                        }
+                    }
+                },
+            )
+            .boxed())
+        }
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use std::time::Duration;
+
+    use zbus::zvariant::Value;
+
+    use super::{sealed::Sealed, *};
+    use crate::{desktop::settings::Settings, helpers::sleep, testing::FakePortal};
+
+    const SETTINGS: &str = "org.freedesktop.portal.Settings";
+
+    async fn emit_color_scheme(portal: &FakePortal, value: u32) {
+        portal
+            .emit_signal(
+                SETTINGS,
+                "SettingChanged",
+                &(
+                    "org.freedesktop.appearance",
+                    "color-scheme",
+                    Value::from(value),
+                ),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    fn survives_restart() {
+        zbus::block_on(async {
+            let portal = FakePortal::new().await.unwrap();
+            let settings = Settings::with_connection(portal.connection())
+                .await
+                .unwrap();
+            let mut events = settings
+                .receive_resilient(Settings::receive_setting_changed)
+                .await
+                .unwrap();
+
+            emit_color_scheme(&portal, 1).await;
+            let event = events.next().await.unwrap();
+            assert!(
+                matches!(event, SignalEvent::Signal(setting) if setting.key() == "color-scheme")
+            );
+
+            portal.set_version(SETTINGS, 2);
+            let (event, restarted) = futures_util::join!(events.next(), async {
+                sleep(Duration::from_millis(50)).await;
+                portal.restart().await
+            });
+            restarted.unwrap();
+            assert!(matches!(event, Some(SignalEvent::PortalRestarted)));
+            assert_eq!(settings.proxy().version(), 2);
+
+            emit_color_scheme(&portal, 2).await;
+            let event = events.next().await.unwrap();
+            assert!(matches!(event, SignalEvent::Signal(_)));
+        });
+    }
+}
@@ -0,0 +1,677 @@
+//! # Examples
+//!
+//! Exercise the cancelled path of a file chooser dialog.
+//!
+//! ```rust,no_run
+//! use ashpd::{
+//!     desktop::{file_chooser::SelectedFiles, Response, ResponseError},
+//!     testing::FakePortal,
+//!     Error,
+//! };
+//!
+//! async fn run() -> ashpd::Result<()> {
+//!     let portal = FakePortal::new().await?;
+//!     portal.respond(
+//!         "org.freedesktop.portal.FileChooser",
+//!         "OpenFile",
+//!         Response::<SelectedFiles>::cancelled(),
+//!     );
+//!
+//!     let request = SelectedFiles::open_file()
+//!         .connection(portal.connection().clone())
+//!         .send()
+//!         .await?;
+//!     assert!(matches!(
+//!         request.response().await,
+//!         Err(Error::Response(ResponseError::Cancelled, _))
+//!     ));
+//!     Ok(())
+//! }
+//! ```
+
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Debug,
+    sync::{Arc, Mutex},
+};
+
+use futures_util::{
+    future::{AbortHandle, Abortable, BoxFuture},
+    FutureExt, StreamExt,
+};
+use serde::{Deserialize, Serialize};
+use zbus::{
+    message::Type as MessageType,
+    zvariant::{OwnedObjectPath, OwnedValue, Structure, Type, Value},
+    Guid, Message, MessageStream,
+};
+
+use crate::{
+    desktop::{Response, Session, SessionPortal},
+    proxy::{
+        DESKTOP_DESTINATION, DESKTOP_PATH, DOCUMENTS_DESTINATION, DOCUMENTS_PATH,
+        FLATPAK_DESTINATION, FLATPAK_DEVELOPMENT_DESTINATION, FLATPAK_DEVELOPMENT_PATH,
+        FLATPAK_PATH,
+    },
+    Error,
+};
+
+const PORTAL_UNIQUE_NAME: &str = ":1.1";
+const CLIENT_UNIQUE_NAME: &str = ":1.2";
+
+type Reply =
+    Box<dyn FnOnce(zbus::Connection, Message) -> BoxFuture<'static, zbus::Result<()>> + Send>;
+
+#[derive(Default)]
+struct State {
+    replies: HashMap<(String, String), VecDeque<Reply>>,
+    properties: HashMap<String, HashMap<String, OwnedValue>>,
+    calls: Vec<Message>,
+}
+
+/// A scriptable, in-process replacement of `xdg-desktop-portal`.
+///
+/// The fake service is reachable through a private peer-to-peer
+/// [`connection`](FakePortal::connection) that can be handed to any of the
+/// `with_connection` constructors. It answers the method calls of the
+/// `org.freedesktop.portal.Desktop`, `org.freedesktop.portal.Documents` and
+/// `org.freedesktop.portal.Flatpak` services with the replies queued for them,
+/// in order. A method call with nothing queued fails with
+/// `org.freedesktop.DBus.Error.UnknownMethod`, but is still recorded in
+/// [`calls`](FakePortal::calls).
+///
+/// Only the interfaces with at least one property set, usually their
+/// [version](FakePortal::set_version), are reported by
+/// [`Portals::probe_with_connection`](crate::Portals::probe_with_connection).
+///
+/// The service stops once the [`FakePortal`] is dropped.
+pub struct FakePortal {
+    client: zbus::Connection,
+    server: zbus::Connection,
+    state: Arc<Mutex<State>>,
+    abort_handle: AbortHandle,
+}
+
+impl FakePortal {
+    /// The object path of the sessions created with [`FakePortal::session`].
+    pub const SESSION_PATH: &'static str = "/org/freedesktop/portal/desktop/session/1_2/ashpd_test";
+
+    /// Start a new fake portal service.
+    pub async fn new() -> Result<Self, Error> {
+        let (client, server) = p2p_pair(false).await?;
+        server.set_unique_name(PORTAL_UNIQUE_NAME)?;
+        // Requests and sessions object paths are derived from the unique name of
+        // the application.
+        client.set_unique_name(CLIENT_UNIQUE_NAME)?;
+
+        let state = Arc::new(Mutex::new(State::default()));
+        let (abort_handle, registration) = AbortHandle::new_pair();
+        let messages = MessageStream::from(&server);
+        server
+            .executor()
+            .spawn(
+                Abortable::new(serve(server.clone(), messages, state.clone()), registration),
+                "ashpd fake portal",
+            )
+            .detach();
+
+        Ok(Self {
+            client,
+            server,
+            state,
+            abort_handle,
+        })
+    }
+
+    /// The connection the applications should use to talk to the fake portal.
+    pub fn connection(&self) -> &zbus::Connection {
+        &self.client
+    }
+
+    /// A session of the portal `P`, as if it had been created by the
+    /// application, at [`FakePortal::SESSION_PATH`].
+    pub async fn session<'a, P: SessionPortal>(&self) -> Result<Session<'a, P>, Error> {
+        Session::new(&self.client, Self::SESSION_PATH).await
+    }
+
+    /// Queue a response to a method call that returns a
+    /// [`Request`](crate::desktop::Request).
+    ///
+    /// The method call is answered with the request object path and the
+    /// response is then sent through its `Response` signal.
+    pub fn respond<T>(&self, interface: &str, method: &str, response: Response<T>)
+    where
+        T: for<'de> Deserialize<'de> + Serialize + Type + Send + Sync + 'static,
+    {
+        self.queue(
+            interface,
+            method,
+            Box::new(move |connection, call| {
+                async move {
+                    let path = request_path(&call)?;
+                    connection.reply(&call, &path).await?;
+                    connection
+                        .emit_signal(
+                            None::<()>,
+                            &path,
+                            "org.freedesktop.portal.Request",
+                            "Response",
+                            &response,
+                        )
+                        .await
+                }
+                .boxed()
+            }),
+        );
+    }
+
+    /// Queue a response to a method call that returns a
+    /// [`Request`](crate::desktop::Request) that never comes, as if the user
+    /// left the dialog open.
+    pub fn pending(&self, interface: &str, method: &str) {
+        self.queue(
+            interface,
+            method,
+            Box::new(move |connection, call| {
+                async move {
+                    let path = request_path(&call)?;
+                    connection.reply(&call, &path).await
+                }
+                .boxed()
+            }),
+        );
+    }
+
+    /// Queue a plain reply to a method call.
+    pub fn reply<B>(&self, interface: &str, method: &str, body: B)
+    where
+        B: Serialize + Type + Send + Sync + 'static,
+    {
+        self.queue(
+            interface,
+            method,
+            Box::new(move |connection, call| {
+                async move { connection.reply(&call, &body).await }.boxed()
+            }),
+        );
+    }
+
+    /// Queue a D-Bus error reply to a method call.
+    ///
+    /// The error `name` is expected to be a valid D-Bus error name, like
+    /// `org.freedesktop.portal.Error.NotAllowed`.
+    pub fn reply_error(&self, interface: &str, method: &str, name: &str, message: &str) {
+        let name = name.to_owned();
+        let message = message.to_owned();
+        self.queue(
+            interface,
+            method,
+            Box::new(move |connection, call| {
+                async move { connection.reply_error(&call, name.as_str(), &message).await }.boxed()
+            }),
+        );
+    }
+
+    /// Set the value of a property of `interface`.
+    pub fn set_property(&self, interface: &str, name: &str, value: impl Into<OwnedValue>) {
+        self.state
+            .lock()
+            .unwrap()
+            .properties
+            .entry(interface.to_owned())
+            .or_default()
+            .insert(name.to_owned(), value.into());
+    }
+
+    /// Set the version of `interface`, defaults to `1` otherwise.
+    pub fn set_version(&self, interface: &str, version: u32) {
+        self.set_property(interface, "version", version);
+    }
+
+    /// Emit a signal of `interface` on the object path it is served at.
+    pub async fn emit_signal<B>(&self, interface: &str, signal: &str, body: &B) -> Result<(), Error>
+    where
+        B: Serialize + Type,
+    {
+        self.emit_signal_at(object_path(interface), interface, signal, body)
+            .await
+    }
+
+    /// Emit a signal of `interface` on the given object path.
+    ///
+    /// Useful for signals emitted by per-request objects such as
+    /// [`UpdateMonitor`](crate::flatpak::UpdateMonitor) or
+    /// [`Session`].
+    pub async fn emit_signal_at<B>(
+        &self,
+        path: &str,
+        interface: &str,
+        signal: &str,
+        body: &B,
+    ) -> Result<(), Error>
+    where
+        B: Serialize + Type,
+    {
+        self.server
+            .emit_signal(None::<()>, path, interface, signal, body)
+            .await?;
+        Ok(())
+    }
+
+    /// Simulate a restart of the portal services, as seen by
+    /// [`PortalProxy::receive_resilient`](crate::PortalProxy::receive_resilient).
+    ///
+    /// Emits the `NameOwnerChanged` signals of the bus for the services
+    /// losing their owner and getting it back.
+    pub async fn restart(&self) -> Result<(), Error> {
+        for name in [
+            DESKTOP_DESTINATION,
+            DOCUMENTS_DESTINATION,
+            FLATPAK_DESTINATION,
+            FLATPAK_DEVELOPMENT_DESTINATION,
+        ] {
+            for (old_owner, new_owner) in [(PORTAL_UNIQUE_NAME, ""), ("", PORTAL_UNIQUE_NAME)] {
+                // Match rules require the signal to come from the bus itself.
+                let signal = Message::signal(
+                    "/org/freedesktop/DBus",
+                    "org.freedesktop.DBus",
+                    "NameOwnerChanged",
+                )?
+                .sender("org.freedesktop.DBus")?
+                .build(&(name, old_owner, new_owner))?;
+                self.server.send(&signal).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The method calls of `interface` received so far, in order.
+    pub fn calls(&self, interface: &str, method: &str) -> Vec<Message> {
+        self.state
+            .lock()
+            .unwrap()
+            .calls
+            .iter()
+            .filter(|call| {
+                let header = call.header();
+                header.interface().is_some_and(|i| i.as_str() == interface)
+                    && header.member().is_some_and(|m| m.as_str() == method)
+            })
+            .cloned()
+            .collect()
+    }
+
+    fn queue(&self, interface: &str, method: &str, reply: Reply) {
+        self.state
+            .lock()
+            .unwrap()
+            .replies
+            .entry((interface.to_owned(), method.to_owned()))
+            .or_default()
+            .push_back(reply);
+    }
+}
+
+impl Debug for FakePortal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FakePortal")
+            .field("connection", &self.client)
+            .finish()
+    }
+}
+
+impl Drop for FakePortal {
+    fn drop(&mut self) {
+        self.abort_handle.abort();
+    }
+}
+
+/// A client and a server peer-to-peer connection, connected to each other.
+///
+/// With `object_server`, the server one has its object server running before
+/// receiving any message. Otherwise, the calls received before exporting the
+/// first object are lost.
+pub(crate) async fn p2p_pair(
+    object_server: bool,
+) -> Result<(zbus::Connection, zbus::Connection), Error> {
+    #[cfg(feature = "tokio")]
+    let (client, server) = tokio::net::UnixStream::pair()?;
+    #[cfg(not(feature = "tokio"))]
+    let (client, server) = std::os::unix::net::UnixStream::pair()?;
+
+    let mut server = zbus::connection::Builder::unix_stream(server)
+        .server(Guid::generate())?
+        .p2p();
+    if object_server {
+        server = server.serve_at("/", zbus::fdo::ObjectManager)?;
+    }
+    let pair = futures_util::try_join!(
+        zbus::connection::Builder::unix_stream(client).p2p().build(),
+        server.build(),
+    )?;
+    Ok(pair)
+}
+
+async fn serve(
+    connection: zbus::Connection,
+    mut messages: MessageStream,
+    state: Arc<Mutex<State>>,
+) {
+    while let Some(Ok(message)) = messages.next().await {
+        if message.message_type() != MessageType::MethodCall {
+            continue;
+        }
+        if let Err(err) = handle(&connection, &message, &state).await {
+            #[cfg(feature = "tracing")]
+            tracing::warn!("Failed to reply to {:?}: {}", message, err);
+            let _ = connection
+                .reply_error(
+                    &message,
+                    "org.freedesktop.DBus.Error.Failed",
+                    &err.to_string(),
+                )
+                .await;
+        }
+    }
+}
+
+async fn handle(
+    connection: &zbus::Connection,
+    message: &Message,
+    state: &Mutex<State>,
+) -> zbus::Result<()> {
+    let header = message.header();
+    let interface = header
+        .interface()
+        .map(|i| i.to_string())
+        .unwrap_or_default();
+    let member = header.member().map(|m| m.to_string()).unwrap_or_default();
+
+    match (interface.as_str(), member.as_str()) {
+        // Proxies resolve the owner of the well-known name before listening to
+        // signals.
+        ("org.freedesktop.DBus", "GetNameOwner") => {
+            connection.reply(message, &PORTAL_UNIQUE_NAME).await
+        }
+        // Every interface with properties set is listed on its object.
+        ("org.freedesktop.DBus.Introspectable", "Introspect") => {
+            let path = header.path().map(|p| p.to_string()).unwrap_or_default();
+            let mut xml = String::from("<node>");
+            for interface in state.lock().unwrap().properties.keys() {
+                if object_path(interface) == path {
+                    xml.push_str(&format!("<interface name=\"{interface}\"/>"));
+                }
+            }
+            xml.push_str("</node>");
+            connection.reply(message, &xml).await
+        }
+        ("org.freedesktop.DBus.Properties", "GetAll") => {
+            let interface = message.body().deserialize::<String>()?;
+            let properties: Option<HashMap<String, OwnedValue>> = state
+                .lock()
+                .unwrap()
+                .properties
+                .get(&interface)
+                .map(|properties| {
+                    properties
+                        .iter()
+                        .filter_map(|(k, v)| Some((k.clone(), v.try_clone().ok()?)))
+                        .collect()
+                });
+            match properties {
+                Some(properties) => connection.reply(message, &properties).await,
+                None => {
+                    connection
+                        .reply_error(
+                            message,
+                            "org.freedesktop.DBus.Error.UnknownInterface",
+                            &format!("Unknown interface {interface}"),
+                        )
+                        .await
+                }
+            }
+        }
+        ("org.freedesktop.DBus.Properties", "Get") => {
+            let (interface, name) = message.body().deserialize::<(String, String)>()?;
+            let value = state
+                .lock()
+                .unwrap()
+                .properties
+                .get(&interface)
+                .and_then(|properties| properties.get(&name))
+                .and_then(|value| value.try_clone().ok());
+            match value {
+                Some(value) => connection.reply(message, &value).await,
+                None => {
+                    connection
+                        .reply_error(
+                            message,
+                            "org.freedesktop.DBus.Error.UnknownProperty",
+                            &format!("Unknown property {interface}.{name}"),
+                        )
+                        .await
+                }
+            }
+        }
+        ("org.freedesktop.portal.Request" | "org.freedesktop.portal.Session", "Close") => {
+            state.lock().unwrap().calls.push(message.clone());
+            connection.reply(message, &()).await
+        }
+        _ => {
+            let reply = {
+                let mut state = state.lock().unwrap();
+                state.calls.push(message.clone());
+                state
+                    .replies
+                    .get_mut(&(interface.clone(), member.clone()))
+                    .and_then(VecDeque::pop_front)
+            };
+            match reply {
+                Some(reply) => reply(connection.clone(), message.clone()).await,
+                None => {
+                    connection
+                        .reply_error(
+                            message,
+                            "org.freedesktop.DBus.Error.UnknownMethod",
+                            &format!("No reply queued for {interface}.{member}"),
+                        )
+                        .await
+                }
+            }
+        }
+    }
+}
+
+/// The object path a portal is served at.
+fn object_path(interface: &str) -> &'static str {
+    match interface {
+        "org.freedesktop.portal.Documents" | "org.freedesktop.portal.FileTransfer" => {
+            DOCUMENTS_PATH
+        }
+        "org.freedesktop.portal.Flatpak" => FLATPAK_PATH,
+        "org.freedesktop.Flatpak.Development" => FLATPAK_DEVELOPMENT_PATH,
+        _ => DESKTOP_PATH,
+    }
+}
+
+/// The request object path, built from the sender and the `handle_token`
+/// option of the method call.
+fn request_path(call: &Message) -> zbus::Result<OwnedObjectPath> {
+    let body = call.body();
+    let arguments = body.deserialize::<Structure<'_>>()?;
+    let handle_token = arguments
+        .fields()
+        .iter()
+        .rev()
+        .find_map(|field| match field {
+            Value::Dict(options) => options.iter().find_map(|(key, value)| match (key, value) {
+                (Value::Str(key), Value::Value(value)) if key.as_str() == "handle_token" => {
+                    match &**value {
+                        Value::Str(token) => Some(token.to_string()),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            }),
+            _ => None,
+        })
+        .ok_or_else(|| zbus::Error::Failure("Missing handle_token option".to_owned()))?;
+    let sender = call
+        .header()
+        .sender()
+        .map(|s| s.trim_start_matches(':').replace('.', "_"))
+        .unwrap_or_default();
+    OwnedObjectPath::try_from(format!(
+        "/org/freedesktop/portal/desktop/request/{sender}/{handle_token}"
+    ))
+    .map_err(From::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use zbus::zvariant::Value;
+
+    use super::*;
+    use crate::{
+        desktop::{
+            file_chooser::SelectedFiles,
+            settings::{ColorScheme, Settings},
+            ResponseError,
+        },
+        ErrorKind, PortalError,
+    };
+
+    #[test]
+    fn file_chooser_responses() {
+        zbus::block_on(async {
+            let portal = FakePortal::new().await.unwrap();
+            let uri = url::Url::parse("file:///home/user/file.txt").unwrap();
+            portal.respond(
+                "org.freedesktop.portal.FileChooser",
+                "OpenFile",
+                Response::<SelectedFiles>::cancelled(),
+            );
+            portal.respond(
+                "org.freedesktop.portal.FileChooser",
+                "OpenFile",
+                Response::ok(SelectedFiles::default().uri(uri.clone())),
+            );
+
+            let request = SelectedFiles::open_file()
+                .connection(portal.connection().clone())
+                .title("Open a file")
+                .send()
+                .await
+                .unwrap();
+            let err = request.response().await.unwrap_err();
+            assert!(matches!(err, Error::Response(ResponseError::Cancelled, _)));
+            assert_eq!(err.kind(), ErrorKind::UserCancelled);
+            let details = err.details().unwrap();
+            assert_eq!(
+                details.interface(),
+                Some("org.freedesktop.portal.FileChooser")
+            );
+            assert_eq!(details.method(), Some("OpenFile"));
+            assert!(details.request_path().is_some());
+
+            let request = SelectedFiles::open_file()
+                .connection(portal.connection().clone())
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(request.response().await.unwrap().uris(), &[uri]);
+
+            let calls = portal.calls("org.freedesktop.portal.FileChooser", "OpenFile");
+            assert_eq!(calls.len(), 2);
+            let (_, title, _) = calls[0]
+                .body()
+                .deserialize::<(String, String, HashMap<String, OwnedValue>)>()
+                .unwrap();
+            assert_eq!(title, "Open a file");
+        });
+    }
+
+    #[test]
+    fn request_timeout_and_close() {
+        zbus::block_on(async {
+            let portal = FakePortal::new().await.unwrap();
+            portal.pending("org.freedesktop.portal.FileChooser", "OpenFile");
+            portal.pending("org.freedesktop.portal.FileChooser", "OpenFile");
+
+            let request = SelectedFiles::open_file()
+                .connection(portal.connection().clone())
+                .send()
+                .await
+                .unwrap()
+                .response_timeout(std::time::Duration::from_millis(10));
+            assert!(matches!(request.await, Err(Error::Timeout)));
+            assert!(portal
+                .calls("org.freedesktop.portal.Request", "Close")
+                .is_empty());
+
+            let request = SelectedFiles::open_file()
+                .connection(portal.connection().clone())
+                .send()
+                .await
+                .unwrap()
+                .close_on_drop(true);
+            let path = request.path().to_owned();
+            drop(request);
+            // Let the fake portal handle the Close call.
+            crate::helpers::sleep(std::time::Duration::from_millis(100)).await;
+            let calls = portal.calls("org.freedesktop.portal.Request", "Close");
+            assert_eq!(calls.len(), 1);
+            assert_eq!(calls[0].header().path(), Some(&path));
+        });
+    }
+
+    #[test]
+    fn errors_and_signals() {
+        zbus::block_on(async {
+            let portal = FakePortal::new().await.unwrap();
+            let settings = Settings::with_connection(portal.connection())
+                .await
+                .unwrap();
+
+            portal.reply_error(
+                "org.freedesktop.portal.Settings",
+                "Read",
+                "org.freedesktop.portal.Error.NotFound",
+                "Requested setting not found",
+            );
+            let err = settings.color_scheme().await.unwrap_err();
+            assert!(matches!(err, Error::Portal(PortalError::NotFound(_), _)));
+            assert_eq!(err.details().unwrap().method(), Some("Read"));
+
+            portal.reply_error(
+                "org.freedesktop.portal.Settings",
+                "Read",
+                "org.freedesktop.portal.Error.NotAllowed",
+                "Not allowed",
+            );
+            let err = settings.color_scheme().await.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+
+            portal.reply("org.freedesktop.portal.Settings", "Read", Value::from(1u32));
+            assert_eq!(
+                settings.color_scheme().await.unwrap(),
+                ColorScheme::PreferDark
+            );
+
+            let mut stream = settings.receive_color_scheme_changed().await.unwrap();
+            portal
+                .emit_signal(
+                    "org.freedesktop.portal.Settings",
+                    "SettingChanged",
+                    &(
+                        "org.freedesktop.appearance",
+                        "color-scheme",
+                        Value::from(2u32),
+                    ),
+                )
+                .await
+                .unwrap();
+            assert_eq!(stream.next().await, Some(ColorScheme::PreferLight));
+        });
+    }
+}
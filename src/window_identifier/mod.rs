@@ -263,8 +263,10 @@ impl HasWindowHandle for WindowIdentifier {
 #[derive(Debug, Clone, PartialEq, Eq, Type)]
 #[zvariant(signature = "s")]
 pub enum WindowIdentifierType {
+    /// An X11 window, identified by its XID.
     X11(std::os::raw::c_ulong),
     #[allow(dead_code)]
+    /// A Wayland surface, identified by its xdg-foreign exported handle.
     Wayland(String),
 }
 